@@ -0,0 +1,44 @@
+//! Benchmarks `Cookie::parse()`. Run with `cargo bench --bench parse`.
+
+use std::time::Instant;
+
+use cookie::Cookie;
+
+const HEADERS: &[&str] = &[
+    "session=abc123",
+    "id=a3fWa; Expires=Wed, 21 Oct 2015 07:28:00 GMT; Secure; HttpOnly",
+    "qwerty=219ffwef9w0f; Domain=somecompany.co.uk; Path=/; Max-Age=2592000",
+    "__Host-id=1; Secure; Path=/; SameSite=Lax; Partitioned",
+    "lang=en-US; Path=/; Domain=.example.com; SameSite=None; Secure; HttpOnly; \
+        Max-Age=86400; Expires=Thu, 01 Jan 2099 00:00:00 GMT; Priority=High",
+];
+
+fn bench<F: FnMut() -> usize>(name: &str, iterations: u32, mut f: F) {
+    // Warm up, then measure.
+    let mut sink = 0;
+    for _ in 0..(iterations / 10) {
+        sink += f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        sink += f();
+    }
+
+    let elapsed = start.elapsed();
+    let per_iter = elapsed.as_nanos() / u128::from(iterations);
+    println!("{:<24} {:>8} ns/iter ({:?} total, sink {})", name, per_iter, elapsed, sink);
+}
+
+fn main() {
+    let iterations = std::env::args()
+        .filter_map(|arg| arg.parse().ok())
+        .next()
+        .unwrap_or(200_000);
+
+    for (i, header) in HEADERS.iter().enumerate() {
+        bench(&format!("parse[{}]", i), iterations, || {
+            Cookie::parse(*header).map(|c| c.name().len()).unwrap_or(0)
+        });
+    }
+}
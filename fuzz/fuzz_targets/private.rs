@@ -0,0 +1,14 @@
+#![no_main]
+
+extern crate cookie;
+
+use cookie::{Cookie, CookieJar, Key};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    // A fixed key: we're fuzzing the handling of untrusted values, not keys.
+    let key = Key::from(&[0; 64]);
+    let jar = CookieJar::new();
+    let _ = jar.private(&key).decrypt(Cookie::new("name", data.to_string()));
+    let _ = cookie::inspect_private(data);
+});
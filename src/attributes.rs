@@ -0,0 +1,163 @@
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+/// A set of standard cookie attributes.
+///
+/// An `AttributeSet` is a compact, bitflags-like set whose members are the
+/// associated constants of this type, one for each standard attribute. Sets
+/// can be combined with `|`. The set of attributes present in a cookie is
+/// returned by [`Cookie::present_attributes()`](crate::Cookie::present_attributes()).
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::AttributeSet;
+///
+/// let set = AttributeSet::SECURE | AttributeSet::HTTP_ONLY;
+/// assert!(set.contains(AttributeSet::SECURE));
+/// assert!(set.contains(AttributeSet::SECURE | AttributeSet::HTTP_ONLY));
+/// assert!(!set.contains(AttributeSet::PATH));
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(format!("{:?}", set), "AttributeSet(Secure | HttpOnly)");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AttributeSet(u8);
+
+impl AttributeSet {
+    /// The `Secure` attribute.
+    pub const SECURE: AttributeSet = AttributeSet(1 << 0);
+    /// The `HttpOnly` attribute.
+    pub const HTTP_ONLY: AttributeSet = AttributeSet(1 << 1);
+    /// The `SameSite` attribute.
+    pub const SAME_SITE: AttributeSet = AttributeSet(1 << 2);
+    /// The `Partitioned` attribute.
+    pub const PARTITIONED: AttributeSet = AttributeSet(1 << 3);
+    /// The `Path` attribute.
+    pub const PATH: AttributeSet = AttributeSet(1 << 4);
+    /// The `Domain` attribute.
+    pub const DOMAIN: AttributeSet = AttributeSet(1 << 5);
+    /// The `Max-Age` attribute.
+    pub const MAX_AGE: AttributeSet = AttributeSet(1 << 6);
+    /// The `Expires` attribute.
+    pub const EXPIRES: AttributeSet = AttributeSet(1 << 7);
+
+    const NAMES: [(AttributeSet, &'static str); 8] = [
+        (AttributeSet::SECURE, "Secure"),
+        (AttributeSet::HTTP_ONLY, "HttpOnly"),
+        (AttributeSet::SAME_SITE, "SameSite"),
+        (AttributeSet::PARTITIONED, "Partitioned"),
+        (AttributeSet::PATH, "Path"),
+        (AttributeSet::DOMAIN, "Domain"),
+        (AttributeSet::MAX_AGE, "Max-Age"),
+        (AttributeSet::EXPIRES, "Expires"),
+    ];
+
+    /// Returns an empty set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::AttributeSet;
+    ///
+    /// assert!(AttributeSet::empty().is_empty());
+    /// ```
+    #[inline]
+    pub const fn empty() -> AttributeSet {
+        AttributeSet(0)
+    }
+
+    /// Returns `true` if every attribute in `other` is in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::AttributeSet;
+    ///
+    /// let set = AttributeSet::PATH | AttributeSet::DOMAIN;
+    /// assert!(set.contains(AttributeSet::PATH));
+    /// assert!(!set.contains(AttributeSet::PATH | AttributeSet::SECURE));
+    /// ```
+    #[inline]
+    pub const fn contains(&self, other: AttributeSet) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds every attribute in `other` to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::AttributeSet;
+    ///
+    /// let mut set = AttributeSet::empty();
+    /// set.insert(AttributeSet::MAX_AGE);
+    /// assert!(set.contains(AttributeSet::MAX_AGE));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, other: AttributeSet) {
+        self.0 |= other.0;
+    }
+
+    /// Returns `true` if `self` contains no attributes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::AttributeSet;
+    ///
+    /// assert!(AttributeSet::empty().is_empty());
+    /// assert!(!AttributeSet::EXPIRES.is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of attributes in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::AttributeSet;
+    ///
+    /// assert_eq!(AttributeSet::empty().len(), 0);
+    /// assert_eq!((AttributeSet::PATH | AttributeSet::DOMAIN).len(), 2);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+}
+
+impl BitOr for AttributeSet {
+    type Output = AttributeSet;
+
+    fn bitor(self, rhs: AttributeSet) -> AttributeSet {
+        AttributeSet(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AttributeSet {
+    fn bitor_assign(&mut self, rhs: AttributeSet) {
+        self.insert(rhs)
+    }
+}
+
+impl fmt::Debug for AttributeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AttributeSet(")?;
+        let names = AttributeSet::NAMES.iter()
+            .filter(|(attr, _)| self.contains(*attr))
+            .map(|(_, name)| name);
+
+        for (i, name) in names.enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+
+            write!(f, "{}", name)?;
+        }
+
+        write!(f, ")")
+    }
+}
@@ -1,6 +1,6 @@
 use std::borrow::{Cow, Borrow, BorrowMut};
 
-use crate::{Cookie, SameSite, Expiration};
+use crate::{Cookie, SameSite, Expiration, ValidationError};
 
 /// Structure that follows the builder pattern for building `Cookie` structs.
 ///
@@ -65,6 +65,25 @@ impl<'c> CookieBuilder<'c> {
         CookieBuilder { cookie: Cookie::new(name, value) }
     }
 
+    /// Sets the value of the cookie being built to `value` wrapped in double
+    /// quotes. See [`Cookie::set_quoted_value()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build("foo").quoted_value("bar baz");
+    /// assert_eq!(c.inner().value(), "\"bar baz\"");
+    /// assert_eq!(c.inner().value_trimmed(), "bar baz");
+    /// assert_eq!(c.to_string(), "foo=\"bar baz\"");
+    /// ```
+    #[inline]
+    pub fn quoted_value<V: Into<Cow<'c, str>>>(mut self, value: V) -> Self {
+        self.cookie.set_quoted_value(value);
+        self
+    }
+
     /// Sets the `expires` field in the cookie being built.
     ///
     /// See [`Expiration`] for conversions.
@@ -90,6 +109,25 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Sets the `expires` field in the cookie being built to the date-time
+    /// `duration` from now. See [`Cookie::set_expires_in()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::time::{Duration, OffsetDateTime};
+    ///
+    /// let c = Cookie::build(("foo", "bar")).expires_in(Duration::hours(1));
+    /// let expires = c.inner().expires_datetime().unwrap();
+    /// assert!(expires > OffsetDateTime::now_utc() + Duration::minutes(59));
+    /// ```
+    #[inline]
+    pub fn expires_in(mut self, duration: time::Duration) -> Self {
+        self.cookie.set_expires_in(duration);
+        self
+    }
+
     /// Sets the `max_age` field in the cookie being built.
     ///
     /// # Example
@@ -107,6 +145,29 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Sets the `max_age` field in the cookie being built if `value` is
+    /// `Some`. Otherwise, leaves the field unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::time::Duration;
+    ///
+    /// let c = Cookie::build(("foo", "bar")).max_age_opt(Some(Duration::minutes(30)));
+    /// assert_eq!(c.inner().max_age(), Some(Duration::minutes(30)));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).max_age_opt(None);
+    /// assert_eq!(c.inner().max_age(), None);
+    /// ```
+    #[inline]
+    pub fn max_age_opt(self, value: Option<time::Duration>) -> Self {
+        match value {
+            Some(value) => self.max_age(value),
+            None => self,
+        }
+    }
+
     /// Sets the `domain` field in the cookie being built.
     ///
     /// # Example
@@ -122,6 +183,27 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Sets the `domain` field in the cookie being built if `value` is `Some`.
+    /// Otherwise, leaves the field unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("foo", "bar")).domain_opt(Some("www.rust-lang.org"));
+    /// assert_eq!(c.inner().domain(), Some("www.rust-lang.org"));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).domain_opt(None::<String>);
+    /// assert_eq!(c.inner().domain(), None);
+    /// ```
+    pub fn domain_opt<D: Into<Cow<'c, str>>>(self, value: Option<D>) -> Self {
+        match value {
+            Some(value) => self.domain(value),
+            None => self,
+        }
+    }
+
     /// Sets the `path` field in the cookie being built.
     ///
     /// # Example
@@ -137,6 +219,27 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Sets the `path` field in the cookie being built if `path` is `Some`.
+    /// Otherwise, leaves the field unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("foo", "bar")).path_opt(Some("/"));
+    /// assert_eq!(c.inner().path(), Some("/"));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).path_opt(None::<&str>);
+    /// assert_eq!(c.inner().path(), None);
+    /// ```
+    pub fn path_opt<P: Into<Cow<'c, str>>>(self, path: Option<P>) -> Self {
+        match path {
+            Some(path) => self.path(path),
+            None => self,
+        }
+    }
+
     /// Sets the `secure` field in the cookie being built.
     ///
     /// # Example
@@ -153,6 +256,28 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Sets the `secure` field in the cookie being built if `value` is `Some`.
+    /// Otherwise, leaves the field unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("foo", "bar")).secure_opt(Some(false));
+    /// assert_eq!(c.inner().secure(), Some(false));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).secure_opt(None);
+    /// assert_eq!(c.inner().secure(), None);
+    /// ```
+    #[inline]
+    pub fn secure_opt(self, value: Option<bool>) -> Self {
+        match value {
+            Some(value) => self.secure(value),
+            None => self,
+        }
+    }
+
     /// Sets the `http_only` field in the cookie being built.
     ///
     /// # Example
@@ -169,7 +294,8 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
-    /// Sets the `same_site` field in the cookie being built.
+    /// Sets the `same_site` field in the cookie being built. If `value` is
+    /// `None`, the field is unset. See [`Cookie::set_same_site()`].
     ///
     /// # Example
     ///
@@ -178,13 +304,42 @@ impl<'c> CookieBuilder<'c> {
     ///
     /// let c = Cookie::build(("foo", "bar")).same_site(SameSite::Strict);
     /// assert_eq!(c.inner().same_site(), Some(SameSite::Strict));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).same_site(Some(SameSite::Lax));
+    /// assert_eq!(c.inner().same_site(), Some(SameSite::Lax));
+    ///
+    /// let c = c.same_site(None);
+    /// assert_eq!(c.inner().same_site(), None);
+    /// assert_eq!(c.to_string(), "foo=bar");
     /// ```
     #[inline]
-    pub fn same_site(mut self, value: SameSite) -> Self {
+    pub fn same_site<T: Into<Option<SameSite>>>(mut self, value: T) -> Self {
         self.cookie.set_same_site(value);
         self
     }
 
+    /// Sets the `same_site` field in the cookie being built if `value` is
+    /// `Some`. Otherwise, leaves the field unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, SameSite};
+    ///
+    /// let c = Cookie::build(("foo", "bar")).same_site_opt(Some(SameSite::Strict));
+    /// assert_eq!(c.inner().same_site(), Some(SameSite::Strict));
+    ///
+    /// let c = Cookie::build(("foo", "bar")).same_site_opt(None);
+    /// assert_eq!(c.inner().same_site(), None);
+    /// ```
+    #[inline]
+    pub fn same_site_opt(self, value: Option<SameSite>) -> Self {
+        match value {
+            Some(value) => self.same_site(value),
+            None => self,
+        }
+    }
+
     /// Sets the `partitioned` field in the cookie being built.
     ///
     /// **Note:** _Partitioned_ cookies require the `Secure` attribute to be
@@ -211,6 +366,37 @@ impl<'c> CookieBuilder<'c> {
         self
     }
 
+    /// Appends the extension attribute `name`, with the value `value` if it is
+    /// `Some`, to the cookie being built. See [`Cookie::add_extension()`].
+    ///
+    /// Returns [`ValidationError::InvalidExtension`] if `name` or `value` is
+    /// invalid as described in [`Cookie::add_extension()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, ValidationError};
+    ///
+    /// # fn main() -> Result<(), ValidationError> {
+    /// let c = Cookie::build(("foo", "bar"))
+    ///     .secure(true)
+    ///     .extension("Priority", Some("High"))?
+    ///     .extension("SameParty", None::<&str>)?;
+    ///
+    /// assert_eq!(c.inner().extensions().count(), 2);
+    /// assert_eq!(c.to_string(), "foo=bar; Secure; Priority=High; SameParty");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn extension<N, V>(mut self, name: N, value: Option<V>) -> Result<Self, ValidationError>
+        where N: Into<Cow<'c, str>>,
+              V: Into<Cow<'c, str>>
+    {
+        self.cookie.add_extension(name, value)?;
+        Ok(self)
+    }
+
     /// Makes the cookie being built 'permanent' by extending its expiration and
     /// max age 20 years into the future. See also [`Cookie::make_permanent()`].
     ///
@@ -310,6 +496,11 @@ impl<'c> CookieBuilder<'c> {
     /// `CookieBuilder` implements `Into<Cookie>`, so a value of `CookieBuilder`
     /// can be passed directly into any method that expects a `C: Into<Cookie>`.
     ///
+    /// If `SameSite` was set to [`SameSite::None`] and `Secure` was not set
+    /// explicitly, `Secure` is set to `true` so that [`Cookie::secure()`]
+    /// reflects the `Secure` attribute that is emitted for such cookies. An
+    /// explicit `secure(false)` is left untouched.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -344,7 +535,11 @@ impl<'c> CookieBuilder<'c> {
     /// CookieJar::new().add(c);
     /// ```
     #[inline]
-    pub fn build(self) -> Cookie<'c> {
+    pub fn build(mut self) -> Cookie<'c> {
+        if self.cookie.same_site() == Some(SameSite::None) && self.cookie.secure().is_none() {
+            self.cookie.set_secure(true);
+        }
+
         self.cookie
     }
 
@@ -1,4 +1,13 @@
-use time::OffsetDateTime;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use time::{OffsetDateTime, PrimitiveDateTime, macros::datetime};
+
+/// The latest expiration date-time a cookie can have. RFC 6265 requires dates
+/// not to exceed 9999 years.
+pub(crate) const MAX_DATETIME: OffsetDateTime = datetime!(9999-12-31 23:59:59.999_999 UTC);
 
 /// A cookie's expiration: either a date-time or session.
 ///
@@ -8,6 +17,10 @@ use time::OffsetDateTime;
 ///   * `Some(OffsetDateTime)` -> `Expiration::DateTime`
 ///   * `OffsetDateTime` -> `Expiration::DateTime`
 ///
+/// An `Expiration::DateTime` can also be created from a `SystemTime` via
+/// [`Expiration::from_system_time()`] and converted into a `SystemTime` via
+/// `SystemTime::try_from()`.
+///
 /// ```rust
 /// use cookie::Expiration;
 /// use time::OffsetDateTime;
@@ -34,6 +47,86 @@ pub enum Expiration {
 }
 
 impl Expiration {
+    /// Returns an `Expiration::DateTime` at `time`. Equivalent to
+    /// `Expiration::from(time)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Expiration;
+    /// use time::OffsetDateTime;
+    ///
+    /// let now = OffsetDateTime::now_utc();
+    /// assert_eq!(Expiration::at(now), Expiration::DateTime(now));
+    /// ```
+    pub fn at(time: OffsetDateTime) -> Self {
+        Expiration::DateTime(time)
+    }
+
+    /// Returns an `Expiration::Session`. Equivalent to
+    /// `Expiration::from(None)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Expiration;
+    ///
+    /// assert_eq!(Expiration::session(), Expiration::Session);
+    /// assert!(Expiration::session().is_session());
+    /// ```
+    pub fn session() -> Self {
+        Expiration::Session
+    }
+
+    /// Returns an `Expiration::DateTime` at the latest date-time a cookie can
+    /// expire, the last instant of the year 9999 in UTC.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Expiration;
+    /// use time::macros::datetime;
+    ///
+    /// let expires = Expiration::never();
+    /// assert_eq!(expires.datetime(), Some(datetime!(9999-12-31 23:59:59.999_999 UTC)));
+    /// ```
+    pub fn never() -> Self {
+        Expiration::DateTime(MAX_DATETIME)
+    }
+
+    /// Returns an `Expiration::DateTime` at `time` in UTC. A `time` outside of
+    /// the range of `OffsetDateTime` is clamped to the nearest representable
+    /// date-time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    /// use cookie::{Cookie, Expiration};
+    /// use time::OffsetDateTime;
+    ///
+    /// let expires = Expiration::from_system_time(UNIX_EPOCH + Duration::from_secs(60));
+    /// let expected = OffsetDateTime::from_unix_timestamp(60).unwrap();
+    /// assert_eq!(expires, Expiration::DateTime(expected));
+    ///
+    /// let expires = Expiration::from_system_time(SystemTime::now());
+    /// let c = Cookie::build(("name", "value")).expires(expires).build();
+    /// assert!(c.expires_datetime().is_some());
+    /// ```
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let epoch = OffsetDateTime::UNIX_EPOCH;
+        let datetime = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => time::Duration::try_from(after).ok()
+                .and_then(|after| epoch.checked_add(after))
+                .unwrap_or(MAX_DATETIME),
+            Err(e) => time::Duration::try_from(e.duration()).ok()
+                .and_then(|before| epoch.checked_sub(before))
+                .unwrap_or_else(|| PrimitiveDateTime::MIN.assume_utc()),
+        };
+
+        Expiration::DateTime(datetime)
+    }
+
     /// Returns `true` if `self` is an `Expiration::DateTime`.
     ///
     /// # Example
@@ -134,3 +227,65 @@ impl<T: Into<Option<OffsetDateTime>>> From<T> for Expiration {
         }
     }
 }
+
+impl TryFrom<Expiration> for SystemTime {
+    type Error = ExpirationError;
+
+    /// Converts the date-time of an `Expiration::DateTime` into a
+    /// `SystemTime`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationError::Session`] if `expiration` is
+    /// `Expiration::Session` and [`ExpirationError::OutOfRange`] if the
+    /// date-time can't be represented as a `SystemTime` on this platform.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::convert::TryFrom;
+    /// use std::time::{SystemTime, UNIX_EPOCH};
+    /// use cookie::{Expiration, ExpirationError};
+    /// use time::OffsetDateTime;
+    ///
+    /// let expires = Expiration::from(OffsetDateTime::UNIX_EPOCH);
+    /// assert_eq!(SystemTime::try_from(expires), Ok(UNIX_EPOCH));
+    ///
+    /// let expires = Expiration::Session;
+    /// assert_eq!(SystemTime::try_from(expires), Err(ExpirationError::Session));
+    /// ```
+    fn try_from(expiration: Expiration) -> Result<Self, Self::Error> {
+        let datetime = expiration.datetime().ok_or(ExpirationError::Session)?;
+        let offset = datetime - OffsetDateTime::UNIX_EPOCH;
+        let magnitude = Duration::try_from(offset.abs())
+            .map_err(|_| ExpirationError::OutOfRange)?;
+
+        match offset.is_negative() {
+            true => UNIX_EPOCH.checked_sub(magnitude),
+            false => UNIX_EPOCH.checked_add(magnitude),
+        }.ok_or(ExpirationError::OutOfRange)
+    }
+}
+
+/// Error returned when converting an [`Expiration`] into a `SystemTime` fails.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ExpirationError {
+    /// The expiration is `Expiration::Session`, which has no date-time.
+    Session,
+    /// The expiration's date-time can't be represented as a `SystemTime`.
+    OutOfRange,
+}
+
+impl fmt::Display for ExpirationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpirationError::Session => write!(f, "session expiration has no date-time"),
+            ExpirationError::OutOfRange => {
+                write!(f, "expiration date-time is out of range for `SystemTime`")
+            }
+        }
+    }
+}
+
+impl Error for ExpirationError { }
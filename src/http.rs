@@ -0,0 +1,209 @@
+use std::convert::TryFrom;
+
+use ::http::header::{HeaderMap, HeaderValue, InvalidHeaderValue, COOKIE, SET_COOKIE};
+
+use crate::{Cookie, CookieJar};
+
+impl Cookie<'_> {
+    /// Renders `self`, as by its `Display` implementation, directly into a
+    /// `HeaderValue` suitable for a `Set-Cookie` header. The rendered string
+    /// is moved into the `HeaderValue` without being copied.
+    ///
+    /// Names and values are not percent-encoded. As such, if the name, value,
+    /// or any attribute of `self` contains a byte that is invalid in a header
+    /// value, such as a control character, an error is returned. Use
+    /// [`Cookie::to_encoded_header_value()`] to percent-encode the name and
+    /// value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("name", "value")).path("/").secure(true).build();
+    /// let value = c.to_header_value().unwrap();
+    /// assert_eq!(value, "name=value; Secure; Path=/");
+    ///
+    /// assert!(Cookie::new("name", "line\nbreak").to_header_value().is_err());
+    /// ```
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "http")))]
+    pub fn to_header_value(&self) -> Result<HeaderValue, InvalidHeaderValue> {
+        HeaderValue::try_from(self.to_string())
+    }
+
+    /// Like [`Cookie::to_header_value()`] but percent-encodes the name and
+    /// value, as by [`Cookie::encoded()`].
+    ///
+    /// An error is only returned if an attribute of `self` contains a byte
+    /// that is invalid in a header value: the encoded name and value are
+    /// always valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("my name", "line\nbreak")).path("/").build();
+    /// let value = c.to_encoded_header_value().unwrap();
+    /// assert_eq!(value, "my%20name=line%0Abreak; Path=/");
+    /// ```
+    #[cfg(feature = "percent-encode")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(all(feature = "http", feature = "percent-encode"))))]
+    pub fn to_encoded_header_value(&self) -> Result<HeaderValue, InvalidHeaderValue> {
+        HeaderValue::try_from(self.encoded().to_string())
+    }
+}
+
+impl CookieJar {
+    /// Creates a cookie jar from the `Cookie` request headers in `headers`.
+    ///
+    /// Every cookie in every `Cookie` header is added to the jar as an
+    /// _original_ cookie, as if by [`CookieJar::add_original()`]. Cookies are
+    /// parsed as by [`Cookie::split_parse()`]: names and values are not
+    /// percent-decoded. Header values that aren't visible ASCII and cookies
+    /// that fail to parse are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    /// use http::header::{HeaderMap, HeaderValue, COOKIE};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.append(COOKIE, HeaderValue::from_static("name=value; other=two"));
+    /// headers.append(COOKIE, HeaderValue::from_static("third=3"));
+    ///
+    /// let jar = CookieJar::from_header_map(&headers);
+    /// assert_eq!(jar.get("name").map(|c| c.value()), Some("value"));
+    /// assert_eq!(jar.get("third").map(|c| c.value()), Some("3"));
+    /// assert_eq!(jar.iter().count(), 3);
+    /// assert_eq!(jar.delta().count(), 0);
+    /// ```
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "http")))]
+    pub fn from_header_map(headers: &HeaderMap) -> CookieJar {
+        let mut jar = CookieJar::new();
+        let values = headers.get_all(COOKIE).iter().filter_map(|v| v.to_str().ok());
+        for cookie in values.flat_map(Cookie::split_parse).filter_map(|c| c.ok()) {
+            jar.add_original(cookie.into_owned());
+        }
+
+        jar
+    }
+}
+
+impl<M> CookieJar<M> {
+    /// Appends a `Set-Cookie` header to `headers` for every cookie in the
+    /// jar's [`delta`](CookieJar::delta()). Existing headers are retained.
+    ///
+    /// Cookies are rendered via their `Display` implementation; names and
+    /// values are not percent-encoded. If any cookie doesn't render to a valid
+    /// header value, an error is returned and `headers` is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar};
+    /// use http::header::{HeaderMap, SET_COOKIE};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add(Cookie::build(("name", "value")).path("/"));
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// jar.write_deltas_to_header_map(&mut headers).unwrap();
+    /// assert_eq!(headers[SET_COOKIE], "name=value; Path=/");
+    /// ```
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "http")))]
+    pub fn write_deltas_to_header_map(
+        &self,
+        headers: &mut HeaderMap
+    ) -> Result<(), InvalidHeaderValue> {
+        let values = self.delta()
+            .map(|c| HeaderValue::from_str(&c.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for value in values {
+            headers.append(SET_COOKIE, value);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::http::header::{HeaderMap, HeaderValue, COOKIE, SET_COOKIE, CONTENT_TYPE};
+
+    use crate::{Cookie, CookieJar};
+
+    #[test]
+    fn to_header_value() {
+        let cookie = Cookie::build(("name", "value"))
+            .domain("rust-lang.org")
+            .path("/docs")
+            .http_only(true)
+            .same_site(crate::SameSite::Strict)
+            .build();
+
+        let value = cookie.to_header_value().unwrap();
+        assert_eq!(value.to_str().unwrap(), cookie.to_string());
+
+        let cookie = Cookie::new("name", "bad\r\nvalue");
+        assert!(cookie.to_header_value().is_err());
+
+        #[cfg(feature = "percent-encode")] {
+            let value = cookie.to_encoded_header_value().unwrap();
+            assert_eq!(value.to_str().unwrap(), cookie.encoded().to_string());
+            assert_eq!(value, "name=bad%0D%0Avalue");
+
+            let cookie = Cookie::build(("name", "value")).path("/bad\npath").build();
+            assert!(cookie.to_encoded_header_value().is_err());
+        }
+    }
+
+    #[test]
+    fn from_header_map() {
+        let jar = CookieJar::from_header_map(&HeaderMap::new());
+        assert_eq!(jar.iter().count(), 0);
+
+        let mut headers = HeaderMap::new();
+        headers.append(CONTENT_TYPE, HeaderValue::from_static("a=b"));
+        headers.append(COOKIE, HeaderValue::from_static("a=1; b=2"));
+        headers.append(COOKIE, HeaderValue::from_static("c=3;;=bad;d"));
+        headers.append(COOKIE, HeaderValue::from_bytes(b"e=\xff").unwrap());
+
+        let jar = CookieJar::from_header_map(&headers);
+        let mut cookies: Vec<_> = jar.iter().map(|c| c.name_value()).collect();
+        cookies.sort();
+        assert_eq!(cookies, [("a", "1"), ("b", "2"), ("c", "3")]);
+        assert_eq!(jar.delta().count(), 0);
+    }
+
+    #[test]
+    fn write_deltas_to_header_map() {
+        let mut headers = HeaderMap::new();
+        headers.append(SET_COOKIE, HeaderValue::from_static("existing=0"));
+        headers.append(COOKIE, HeaderValue::from_static("original=1; gone=2"));
+
+        let mut jar = CookieJar::from_header_map(&headers);
+        jar.add(Cookie::build(("new", "3")).http_only(true));
+        jar.remove("gone");
+        jar.write_deltas_to_header_map(&mut headers).unwrap();
+
+        let mut values: Vec<_> = headers.get_all(SET_COOKIE).iter()
+            .map(|v| v.to_str().unwrap())
+            .collect();
+
+        values.sort();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0], "existing=0");
+        assert!(values[1].starts_with("gone=; Max-Age=0; Expires="));
+        assert_eq!(values[2], "new=3; HttpOnly");
+
+        let mut jar = CookieJar::new();
+        jar.add(("ok", "1"));
+        jar.add(("bad", "line\nbreak"));
+        let mut headers = HeaderMap::new();
+        assert!(jar.write_deltas_to_header_map(&mut headers).is_err());
+        assert!(headers.is_empty());
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 
-#[cfg(feature = "signed")] use crate::secure::SignedJar;
+#[cfg(feature = "signed")] use crate::secure::{SignatureAlgorithm, SignedJar};
 #[cfg(feature = "private")] use crate::secure::PrivateJar;
 #[cfg(any(feature = "signed", feature = "private"))] use crate::secure::Key;
 
 use crate::delta::DeltaCookie;
 use crate::prefix::{Prefix, PrefixedJar};
+use crate::store::{BackedJar, CookieStore};
 use crate::Cookie;
 
 /// A collection of cookies that tracks its modifications.
@@ -82,15 +84,35 @@ use crate::Cookie;
 /// jar.remove("a");
 /// assert_eq!(jar.delta().count(), 2);
 /// ```
-#[derive(Default, Debug, Clone)]
-pub struct CookieJar {
+///
+/// # Metadata
+///
+/// A jar can attach application metadata of type `M`, which defaults to `()`,
+/// to its cookies. See [`CookieJar::set_meta()`].
+#[derive(Debug, Clone)]
+pub struct CookieJar<M = ()> {
     original_cookies: HashSet<DeltaCookie>,
     delta_cookies: HashSet<DeltaCookie>,
+    /// The maximum number of cookies in the jar, if any.
+    limit: Option<usize>,
+    /// The order in which the cookies in the jar were added. Only tracked
+    /// when `limit` is `Some`.
+    order: AddOrder,
+    /// The maximum serialized size of a cookie accepted by `try_add`, if any.
+    max_cookie_size: Option<usize>,
+    /// Application metadata attached to cookies, keyed by cookie name.
+    meta: HashMap<String, M>,
+    /// Whether `add` adds original cookies. See [`CookieJar::seeding()`].
+    seeding: bool,
 }
 
 impl CookieJar {
     /// Creates an empty cookie jar.
     ///
+    /// The jar's metadata type is `()`. A jar with another metadata type `M`
+    /// can be created via `CookieJar::<M>::default()` or any of the other
+    /// constructors, such as `CookieJar::<M>::with_limit()`.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -102,6 +124,296 @@ impl CookieJar {
     pub fn new() -> CookieJar {
         CookieJar::default()
     }
+}
+
+impl<M> Default for CookieJar<M> {
+    fn default() -> Self {
+        CookieJar {
+            original_cookies: HashSet::new(),
+            delta_cookies: HashSet::new(),
+            limit: None,
+            order: AddOrder::default(),
+            max_cookie_size: None,
+            meta: HashMap::new(),
+            seeding: false,
+        }
+    }
+}
+
+impl<M> CookieJar<M> {
+    /// Creates an empty cookie jar that holds at most `max` cookies. See
+    /// [`CookieJar::set_limit()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar: CookieJar = CookieJar::with_limit(2);
+    /// jar.add(("a", "1"));
+    /// jar.add(("b", "2"));
+    /// jar.add(("c", "3"));
+    ///
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert!(jar.get("a").is_none());
+    /// ```
+    pub fn with_limit(max: usize) -> Self {
+        let mut jar = CookieJar::default();
+        jar.set_limit(max);
+        jar
+    }
+
+    /// Creates a cookie jar containing the cookies in `iter`, each added as if
+    /// by [`CookieJar::add()`]. All of the cookies thus appear in the jar's
+    /// [`delta`](CookieJar::delta()).
+    ///
+    /// This is useful when building a jar of new cookies to send to a client.
+    /// To instead seed a jar with cookies received from a client, which don't
+    /// count towards the delta, use [`CookieJar::add_original()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar};
+    ///
+    /// let jar: CookieJar = CookieJar::from_deltas(vec![
+    ///     Cookie::new("a", "one"),
+    ///     Cookie::build(("b", "two")).path("/").build(),
+    /// ]);
+    ///
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert_eq!(jar.delta().count(), 2);
+    /// ```
+    pub fn from_deltas<I, C>(iter: I) -> Self
+        where I: IntoIterator<Item = C>, C: Into<Cookie<'static>>
+    {
+        let mut jar = CookieJar::default();
+        for cookie in iter {
+            jar.add(cookie);
+        }
+
+        jar
+    }
+
+    /// Decrypts and deserializes `blob`, as returned by
+    /// [`CookieJar::seal_jar()`] with the same `key`, into a new jar containing
+    /// the sealed cookies as _original_ cookies.
+    ///
+    /// Returns [`OpenError::Unauthentic`] if `blob` was not sealed with `key`
+    /// or was tampered with and [`OpenError::Malformed`] if it decrypts but
+    /// does not contain a serialized jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key, OpenError};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.add(("name", "value"));
+    ///
+    /// let blob = jar.seal_jar(&key).unwrap();
+    /// let opened: CookieJar = CookieJar::open_jar(&blob, &key).unwrap();
+    /// assert_eq!(opened.get("name").unwrap().value(), "value");
+    /// assert_eq!(opened.delta().count(), 0);
+    ///
+    /// let result = CookieJar::<()>::open_jar(&blob, &Key::generate());
+    /// assert_eq!(result.unwrap_err(), OpenError::Unauthentic);
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn open_jar(blob: &str, key: &Key) -> Result<Self, OpenError> {
+        let bytes = crate::secure::open(key.encryption(), SEALED_JAR_AAD, blob)
+            .map_err(|_| OpenError::Unauthentic)?;
+
+        let mut jar = CookieJar::default();
+        let mut bytes = &bytes[..];
+        while !bytes.is_empty() {
+            jar.add_original(read_sealed_cookie(&mut bytes)?);
+        }
+
+        Ok(jar)
+    }
+    /// Sets the maximum number of cookies this jar holds to `limit`. If
+    /// `limit` is `None`, the jar is unbounded, the default.
+    ///
+    /// Whenever adding a cookie via [`CookieJar::add()`] or
+    /// [`CookieJar::add_original()`] causes the jar to exceed its limit, the
+    /// least-recently added cookies are removed, as if by
+    /// [`CookieJar::remove()`], until the jar is within its limit. Evicting an
+    /// _original_ cookie thus adds a removal cookie to the delta.
+    /// Replacing a cookie counts as adding it anew. Cookies already in the jar
+    /// when a limit is first set are considered to have been added before any
+    /// cookie added afterwards, in an unspecified order among themselves, and
+    /// are evicted immediately if the jar exceeds `limit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add(("a", "1"));
+    /// jar.add(("b", "2"));
+    ///
+    /// jar.set_limit(2);
+    /// jar.add(("a", "one"));
+    /// jar.add(("c", "3"));
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert!(jar.get("b").is_none());
+    ///
+    /// jar.set_limit(None);
+    /// jar.add(("d", "4"));
+    /// assert_eq!(jar.iter().count(), 3);
+    /// ```
+    pub fn set_limit<L: Into<Option<usize>>>(&mut self, limit: L) {
+        let limit = limit.into();
+        if limit.is_none() {
+            self.order = AddOrder::default();
+        }
+
+        self.limit = limit;
+        self.sync_order();
+        self.evict();
+    }
+
+    /// Records that the cookie named `name` was just added.
+    fn track(&mut self, name: &str) {
+        if self.limit.is_some() {
+            self.order.push_back(name);
+        }
+    }
+
+    /// Stops tracking the order of the cookie named `name`.
+    fn untrack(&mut self, name: &str) {
+        if self.limit.is_some() {
+            self.order.remove(name);
+        }
+    }
+
+    /// Brings the tracked order in line with the cookies in the jar after a
+    /// change that bypasses `track` and `untrack`. Cookies that weren't
+    /// tracked are considered least-recently added.
+    fn sync_order(&mut self) {
+        if self.limit.is_none() {
+            return;
+        }
+
+        let stale: Vec<String> = self.order.names()
+            .filter(|name| !self.contains(name))
+            .map(String::from)
+            .collect();
+
+        for name in stale {
+            self.order.remove(&name);
+        }
+
+        let untracked: Vec<String> = self.names()
+            .filter(|name| !self.order.contains(name))
+            .map(String::from)
+            .collect();
+
+        for name in untracked {
+            self.order.push_front(&name);
+        }
+    }
+
+    /// Removes least-recently added cookies until the jar is within its limit.
+    fn evict(&mut self) {
+        if let Some(limit) = self.limit {
+            while self.order.len() > limit {
+                let name = match self.order.pop_front() {
+                    Some(name) => name,
+                    None => break,
+                };
+
+                if let Some(cookie) = self.get(&name).cloned() {
+                    self.remove(cookie);
+                }
+            }
+        }
+    }
+
+    /// Sets the maximum serialized size, in bytes, of a cookie accepted by
+    /// [`CookieJar::try_add()`] to `max`. If `max` is `None`, cookies of any
+    /// size are accepted, the default.
+    ///
+    /// The size of a cookie is the length of its `Set-Cookie` header value as
+    /// rendered by its `Display` implementation. Browsers commonly reject
+    /// cookies larger than 4096 bytes. This policy is _only_ enforced by
+    /// [`CookieJar::try_add()`]: [`CookieJar::add()`] and
+    /// [`CookieJar::add_original()`] accept cookies of any size.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.set_max_cookie_size(8);
+    /// assert!(jar.try_add(("a", "1")).is_ok());
+    /// assert!(jar.try_add(("a", "too large")).is_err());
+    /// assert_eq!(jar.get("a").map(|c| c.value()), Some("1"));
+    ///
+    /// jar.set_max_cookie_size(None);
+    /// assert!(jar.try_add(("a", "too large")).is_ok());
+    /// ```
+    pub fn set_max_cookie_size<S: Into<Option<usize>>>(&mut self, max: S) {
+        self.max_cookie_size = max.into();
+    }
+
+    /// Enables or disables _seeding_ mode. Seeding mode is disabled by default.
+    ///
+    /// **This is a stateful toggle.** While seeding mode is enabled, every call
+    /// to [`CookieJar::add()`], including those made indirectly via
+    /// [`CookieJar::try_add()`] or a child jar, behaves exactly like
+    /// [`CookieJar::add_original()`]: cookies are added as originals and do
+    /// _not_ appear in the [delta](CookieJar::delta()). This lasts until
+    /// seeding mode is disabled by calling this method with `false`. Cookies
+    /// added while seeding remain originals after seeding mode is disabled.
+    ///
+    /// This is intended for replaying stored cookies into a jar. As with
+    /// `add_original()`, a cookie added while seeding does not replace a
+    /// cookie of the same name previously added with seeding mode disabled,
+    /// and seeding should not take place after calling
+    /// [`CookieJar::remove()`]. [`CookieJar::remove()`] itself is unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.seeding(true);
+    /// assert!(jar.is_seeding());
+    /// jar.add(("stored", "value"));
+    /// assert_eq!(jar.delta().count(), 0);
+    ///
+    /// jar.seeding(false);
+    /// jar.add(("new", "value"));
+    /// assert_eq!(jar.delta().count(), 1);
+    /// assert_eq!(jar.iter().count(), 2);
+    /// ```
+    pub fn seeding(&mut self, enabled: bool) {
+        self.seeding = enabled;
+    }
+
+    /// Returns `true` if seeding mode is enabled. See [`CookieJar::seeding()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// assert!(!jar.is_seeding());
+    ///
+    /// jar.seeding(true);
+    /// assert!(jar.is_seeding());
+    /// ```
+    pub fn is_seeding(&self) -> bool {
+        self.seeding
+    }
 
     /// Returns a reference to the `Cookie` inside this jar with the name
     /// `name`. If no such cookie exists, returns `None`.
@@ -124,6 +436,99 @@ impl CookieJar {
             .and_then(|c| if c.removed { None } else { Some(&c.cookie) })
     }
 
+    /// Returns `true` if this jar contains a cookie with the name `name`, that
+    /// is, if [`CookieJar::get()`] would return `Some`. A cookie that was
+    /// removed via [`CookieJar::remove()`] is not contained in the jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// assert!(!jar.contains("name"));
+    ///
+    /// jar.add_original(("name", "value"));
+    /// assert!(jar.contains("name"));
+    ///
+    /// jar.remove("name");
+    /// assert!(!jar.contains("name"));
+    /// ```
+    pub fn contains(&self, name: &str) -> bool {
+        match self.delta_cookies.get(name) {
+            Some(cookie) => !cookie.removed,
+            None => self.original_cookies.contains(name),
+        }
+    }
+
+    /// Returns `true` if this jar contains an _original_ cookie, added via
+    /// [`CookieJar::add_original()`], with the name `name`. Changes made via
+    /// [`CookieJar::add()`] and [`CookieJar::remove()`] are not considered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("new", "value"));
+    /// jar.remove("name");
+    ///
+    /// assert!(jar.contains_original("name"));
+    /// assert!(!jar.contains_original("new"));
+    /// ```
+    pub fn contains_original(&self, name: &str) -> bool {
+        self.original_cookies.contains(name)
+    }
+
+    /// Returns `true` if the delta contains a removal for the cookie named
+    /// `name`.
+    pub(crate) fn is_removed(&self, name: &str) -> bool {
+        matches!(self.delta_cookies.get(name), Some(c) if c.removed)
+    }
+
+    /// Returns an iterator over every cookie in this jar with the name `name`,
+    /// that is, every such cookie [`CookieJar::iter()`] would yield.
+    ///
+    /// A cookie added via [`CookieJar::add()`] or removed via
+    /// [`CookieJar::remove()`] supersedes an _original_ cookie of the same
+    /// name, so the original is never yielded alongside it.
+    ///
+    /// **Note:** Cookies in a jar are currently identified by name alone, so
+    /// this iterator yields at most one cookie, the same one returned by
+    /// [`CookieJar::get()`]. Cookies that share a name but differ in `Path` or
+    /// `Domain` replace one another when added. Unlike `get()`, this method
+    /// will yield all such cookies should the jar come to distinguish them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// assert_eq!(jar.get_all("name").count(), 0);
+    ///
+    /// jar.add_original(("name", "original"));
+    /// let values: Vec<_> = jar.get_all("name").map(|c| c.value()).collect();
+    /// assert_eq!(values, ["original"]);
+    ///
+    /// jar.add(("name", "new"));
+    /// let values: Vec<_> = jar.get_all("name").map(|c| c.value()).collect();
+    /// assert_eq!(values, ["new"]);
+    ///
+    /// jar.remove("name");
+    /// assert_eq!(jar.get_all("name").count(), 0);
+    /// ```
+    pub fn get_all(&self, name: &str) -> impl Iterator<Item = &Cookie<'static>> + '_ {
+        let delta = self.delta_cookies.get(name);
+        let original = self.original_cookies.get(name).filter(|_| delta.is_none());
+        delta.into_iter()
+            .chain(original)
+            .filter(|c| !c.removed)
+            .map(|c| &c.cookie)
+    }
+
     /// Adds an "original" `cookie` to this jar. If an original cookie with the
     /// same name already exists, it is replaced with `cookie`. Cookies added
     /// with `add` take precedence and are not replaced by this method.
@@ -150,12 +555,86 @@ impl CookieJar {
     /// assert_eq!(jar.delta().count(), 0);
     /// ```
     pub fn add_original<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
-        self.original_cookies.replace(DeltaCookie::added(cookie.into()));
+        let cookie = cookie.into();
+        if !self.is_removed(cookie.name()) {
+            self.track(cookie.name());
+        }
+
+        self.original_cookies.replace(DeltaCookie::added(cookie));
+        self.evict();
+    }
+
+    /// Adds every cookie in `cookies` to this jar as an "original" cookie, as
+    /// if by [`CookieJar::add_original()`]. Like `add_original()`, this does
+    /// not affect the [delta](#method.delta) computation.
+    ///
+    /// This method is intended to seed a jar with a client's cookies when
+    /// they become available only after the jar is constructed. As with
+    /// `add_original()`, it must be called before any cookie is removed via
+    /// [`CookieJar::remove()`]. This is checked with a `debug_assert!`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.seed_originals(vec![Cookie::new("name", "value"), Cookie::new("second", "two")]);
+    ///
+    /// assert_eq!(jar.get("name").map(|c| c.value()), Some("value"));
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert_eq!(jar.delta().count(), 0);
+    /// ```
+    pub fn seed_originals<I, C>(&mut self, cookies: I)
+        where I: IntoIterator<Item = C>, C: Into<Cookie<'static>>
+    {
+        debug_assert!(self.delta_cookies.iter().all(|c| !c.removed),
+            "`CookieJar::seed_originals()` called after `CookieJar::remove()`");
+
+        for cookie in cookies {
+            self.add_original(cookie);
+        }
+    }
+
+    /// Parses `header`, the value of a `Cookie` request header, and adds each
+    /// cookie in it to this jar as an "original" cookie, as if by
+    /// [`CookieJar::seed_originals()`].
+    ///
+    /// Cookies are parsed as by [`Cookie::split_parse()`]: names and values
+    /// are not percent-decoded, and cookies that fail to parse are ignored.
+    /// Existing original cookies are retained unless `header` contains a
+    /// cookie of the same name, in which case they are replaced. Calling this
+    /// method once for each `Cookie` header thus merges all of them into the
+    /// jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.extend_originals_from_header("name=value; other=two");
+    /// jar.extend_originals_from_header("third=3; other=2");
+    ///
+    /// assert_eq!(jar.get("name").map(|c| c.value()), Some("value"));
+    /// assert_eq!(jar.get("other").map(|c| c.value()), Some("2"));
+    /// assert_eq!(jar.iter().count(), 3);
+    /// assert_eq!(jar.delta().count(), 0);
+    /// ```
+    pub fn extend_originals_from_header(&mut self, header: &str) {
+        let cookies = Cookie::split_parse(header)
+            .filter_map(|c| c.ok())
+            .map(|c| c.into_owned());
+
+        self.seed_originals(cookies);
     }
 
     /// Adds `cookie` to this jar. If a cookie with the same name already
     /// exists, it is replaced with `cookie`.
     ///
+    /// If [seeding mode](CookieJar::seeding()) is enabled, this method instead
+    /// adds `cookie` as if by [`CookieJar::add_original()`].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -171,7 +650,53 @@ impl CookieJar {
     /// assert_eq!(jar.delta().count(), 2);
     /// ```
     pub fn add<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
-        self.delta_cookies.replace(DeltaCookie::added(cookie.into()));
+        if self.seeding {
+            return self.add_original(cookie);
+        }
+
+        let cookie = cookie.into();
+        self.track(cookie.name());
+        self.delta_cookies.replace(DeltaCookie::added(cookie));
+        self.evict();
+    }
+
+    /// Adds `cookie` to this jar exactly as [`CookieJar::add()`] does if it
+    /// satisfies the jar's policies, returning an error and leaving the jar
+    /// unchanged otherwise.
+    ///
+    /// Currently, the only such policy is the maximum cookie size configured
+    /// via [`CookieJar::set_max_cookie_size()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{AddError, CookieJar};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.set_max_cookie_size(16);
+    /// assert!(jar.try_add(("name", "value")).is_ok());
+    ///
+    /// let err = jar.try_add(("name", "a much longer value")).unwrap_err();
+    /// assert_eq!(err, AddError::TooLarge { size: 24, limit: 16 });
+    /// assert_eq!(jar.get("name").map(|c| c.value()), Some("value"));
+    /// ```
+    pub fn try_add<C: Into<Cookie<'static>>>(&mut self, cookie: C) -> Result<(), AddError> {
+        let cookie = cookie.into();
+        self.check_policies(&cookie)?;
+        self.add(cookie);
+        Ok(())
+    }
+
+    /// Returns an error if `cookie` violates any of the jar's policies.
+    fn check_policies(&self, cookie: &Cookie<'_>) -> Result<(), AddError> {
+        if let Some(limit) = self.max_cookie_size {
+            let size = cookie.to_string().len();
+            if size > limit {
+                return Err(AddError::TooLarge { size, limit });
+            }
+        }
+
+        Ok(())
     }
 
     /// Removes `cookie` from this jar. If an _original_ cookie with the same
@@ -182,7 +707,10 @@ impl CookieJar {
     ///
     /// A "removal" cookie is a cookie that has the same name as the original
     /// cookie but has an empty value, a max-age of 0, and an expiration date
-    /// far in the past. See also [`Cookie::make_removal()`].
+    /// far in the past. See also [`Cookie::make_removal()`]. All other
+    /// attributes of `cookie`, including `Secure`, `SameSite`, and
+    /// `Partitioned`, are preserved in the removal cookie, as some user agents
+    /// only remove a cookie when these match those of the cookie that was set.
     ///
     /// # Example
     ///
@@ -229,10 +757,12 @@ impl CookieJar {
     /// ```
     pub fn remove<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
         let mut cookie = cookie.into();
+        self.untrack(cookie.name());
         if self.original_cookies.contains(cookie.name()) {
             cookie.make_removal();
             self.delta_cookies.replace(DeltaCookie::removed(cookie));
         } else {
+            self.meta.remove(cookie.name());
             self.delta_cookies.remove(cookie.name());
         }
     }
@@ -274,321 +804,1635 @@ impl CookieJar {
     /// # }
     /// ```
     pub fn force_remove<N: AsRef<str>>(&mut self, name: N) {
+        self.untrack(name.as_ref());
+        self.meta.remove(name.as_ref());
         self.original_cookies.remove(name.as_ref());
         self.delta_cookies.remove(name.as_ref());
     }
 
-    /// Removes all delta cookies, i.e. all cookies not added via
-    /// [`CookieJar::add_original()`], from this `CookieJar`. This undoes any
-    /// changes from [`CookieJar::add()`] and [`CookieJar::remove()`]
-    /// operations.
+    /// Merges `other` into `self`.
+    ///
+    /// The cookies in `other` are applied to `self` in two steps:
+    ///
+    ///   1. Every _original_ cookie in `other` is added to `self` as if by
+    ///      [`CookieJar::add_original()`]. It replaces any original cookie of
+    ///      the same name in `self` but, as with `add_original()`, it does not
+    ///      override a cookie of the same name added to or removed from `self`
+    ///      via `add()` or `remove()`, and it does not affect the delta.
+    ///
+    ///   2. Every _delta_ cookie in `other` is applied to `self`: cookies added
+    ///      to `other` are added to `self` as if by [`CookieJar::add()`],
+    ///      replacing any cookie of the same name, and cookies removed from
+    ///      `other` are removed from `self` as if by [`CookieJar::remove()`].
+    ///      As a result, a removal appears in the delta of `self` only if
+    ///      `self` or `other` has an original cookie of the same name.
+    ///
+    /// In short, `other` wins name conflicts between originals and between
+    /// delta cookies, while a delta cookie in either jar takes precedence over
+    /// an original cookie in the other. Metadata attached to cookies in
+    /// `other` replaces metadata attached to cookies of the same name in
+    /// `self`. The limits of `self` are retained and enforced.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{CookieJar, Cookie};
+    /// use cookie::CookieJar;
     ///
     /// let mut jar = CookieJar::new();
+    /// jar.add_original(("a", "1"));
+    /// jar.add_original(("b", "2"));
+    /// jar.add(("c", "3"));
+    ///
+    /// let mut upstream = CookieJar::new();
+    /// upstream.add_original(("a", "one"));
+    /// upstream.add_original(("b", "2"));
+    /// upstream.add(("c", "three"));
+    /// upstream.remove("b");
+    ///
+    /// jar.merge(upstream);
+    /// assert_eq!(jar.get("a").map(|c| c.value()), Some("one"));
+    /// assert_eq!(jar.get("b"), None);
+    /// assert_eq!(jar.get("c").map(|c| c.value()), Some("three"));
+    ///
+    /// // The removal of `b` and the addition of `c` are in the delta.
+    /// assert_eq!(jar.delta().count(), 2);
+    /// ```
+    pub fn merge(&mut self, other: CookieJar<M>) {
+        for original in other.original_cookies {
+            self.add_original(original.cookie);
+        }
+
+        for delta in other.delta_cookies {
+            match delta.removed {
+                true => self.remove(delta.cookie),
+                false => self.add(delta.cookie),
+            }
+        }
+
+        self.meta.extend(other.meta);
+    }
+
+    /// Attaches the metadata `value` to the cookie named `name`, replacing any
+    /// metadata previously attached to it.
     ///
-    /// // Only original cookies will remain after calling `reset_delta`.
-    /// jar.add_original(("name", "value"));
-    /// jar.add_original(("language", "Rust"));
+    /// Metadata is of the jar's type `M`, which is `()` for a jar created via
+    /// [`CookieJar::new()`]. A jar with another metadata type can be created
+    /// via [`Default`], as in `CookieJar::<M>::default()`.
     ///
-    /// // These operations, represented by delta cookies, will be reset.
-    /// jar.add(("language", "C++"));
-    /// jar.remove("name");
+    /// Metadata is for application bookkeeping only: it is never emitted,
+    /// serialized, or part of the delta. It is keyed by name, so it remains
+    /// attached when a cookie is replaced by one of the same name via
+    /// [`CookieJar::add()`], and it is dropped when the cookie is removed via
+    /// [`CookieJar::remove()`] or [`CookieJar::force_remove()`]. The metadata
+    /// of an _original_ cookie removed via `remove()` is retained, however, as
+    /// [`CookieJar::reset_delta()`] brings the cookie back. A
+    /// [`Checkpoint`] also captures metadata, which [`CookieJar::restore()`]
+    /// restores along with the delta.
     ///
-    /// // All is normal.
-    /// assert_eq!(jar.get("name"), None);
-    /// assert_eq!(jar.get("language").map(Cookie::value), Some("C++"));
-    /// assert_eq!(jar.iter().count(), 1);
-    /// assert_eq!(jar.delta().count(), 2);
+    /// # Example
     ///
-    /// // Resetting undoes delta operations.
-    /// jar.reset_delta();
-    /// assert_eq!(jar.get("name").map(Cookie::value), Some("value"));
-    /// assert_eq!(jar.get("language").map(Cookie::value), Some("Rust"));
-    /// assert_eq!(jar.iter().count(), 2);
-    /// assert_eq!(jar.delta().count(), 0);
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Source { OAuth, Refresh }
+    ///
+    /// let mut jar = CookieJar::<Source>::default();
+    /// jar.add(("session", "abc"));
+    /// jar.set_meta("session", Source::OAuth);
+    /// assert_eq!(jar.get_meta("session"), Some(&Source::OAuth));
+    ///
+    /// jar.add(("session", "def"));
+    /// assert_eq!(jar.get_meta("session"), Some(&Source::OAuth));
+    ///
+    /// jar.set_meta("session", Source::Refresh);
+    /// assert_eq!(jar.get_meta("session"), Some(&Source::Refresh));
+    ///
+    /// jar.remove("session");
+    /// assert_eq!(jar.get_meta("session"), None);
     /// ```
-    pub fn reset_delta(&mut self) {
-        self.delta_cookies = HashSet::new();
+    pub fn set_meta<N: Into<String>>(&mut self, name: N, value: M) {
+        self.meta.insert(name.into(), value);
     }
 
-    /// Returns an iterator over cookies that represent the changes to this jar
-    /// over time. These cookies can be rendered directly as `Set-Cookie` header
-    /// values to affect the changes made to this jar on the client.
+    /// Returns the metadata attached to the cookie named `name` via
+    /// [`CookieJar::set_meta()`], if any.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{CookieJar, Cookie};
-    ///
-    /// let mut jar = CookieJar::new();
-    /// jar.add_original(("name", "value"));
-    /// jar.add_original(("second", "two"));
-    ///
-    /// // Add new cookies.
-    /// jar.add(("new", "third"));
-    /// jar.add(("another", "fourth"));
-    /// jar.add(("yac", "fifth"));
+    /// use cookie::CookieJar;
     ///
-    /// // Remove some cookies.
-    /// jar.remove(("name"));
-    /// jar.remove(("another"));
+    /// let mut jar = CookieJar::<u32>::default();
+    /// assert_eq!(jar.get_meta("name"), None);
     ///
-    /// // Delta contains two new cookies ("new", "yac") and a removal ("name").
-    /// assert_eq!(jar.delta().count(), 3);
+    /// jar.set_meta("name", 42);
+    /// assert_eq!(jar.get_meta("name"), Some(&42));
     /// ```
-    pub fn delta(&self) -> Delta {
-        Delta { iter: self.delta_cookies.iter() }
+    pub fn get_meta(&self, name: &str) -> Option<&M> {
+        self.meta.get(name)
     }
 
-    /// Returns an iterator over all of the cookies present in this jar.
+    /// Detaches and returns the metadata attached to the cookie named `name`
+    /// via [`CookieJar::set_meta()`], if any. The cookie itself is unaffected.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{CookieJar, Cookie};
-    ///
-    /// let mut jar = CookieJar::new();
-    ///
-    /// jar.add_original(("name", "value"));
-    /// jar.add_original(("second", "two"));
-    ///
-    /// jar.add(("new", "third"));
-    /// jar.add(("another", "fourth"));
-    /// jar.add(("yac", "fifth"));
+    /// use cookie::CookieJar;
     ///
-    /// jar.remove("name");
-    /// jar.remove("another");
+    /// let mut jar = CookieJar::<String>::default();
+    /// jar.add(("name", "value"));
+    /// jar.set_meta("name", "meta".to_string());
     ///
-    /// // There are three cookies in the jar: "second", "new", and "yac".
-    /// # assert_eq!(jar.iter().count(), 3);
-    /// for cookie in jar.iter() {
-    ///     match cookie.name() {
-    ///         "second" => assert_eq!(cookie.value(), "two"),
-    ///         "new" => assert_eq!(cookie.value(), "third"),
-    ///         "yac" => assert_eq!(cookie.value(), "fifth"),
-    ///         _ => unreachable!("there are only three cookies in the jar")
-    ///     }
-    /// }
+    /// assert_eq!(jar.remove_meta("name"), Some("meta".to_string()));
+    /// assert_eq!(jar.get_meta("name"), None);
+    /// assert!(jar.get("name").is_some());
     /// ```
-    pub fn iter(&self) -> Iter {
-        Iter {
-            delta_cookies: self.delta_cookies.iter()
-                .chain(self.original_cookies.difference(&self.delta_cookies)),
-        }
+    pub fn remove_meta(&mut self, name: &str) -> Option<M> {
+        self.meta.remove(name)
     }
 
-    /// Returns a read-only `PrivateJar` with `self` as its parent jar using the
-    /// key `key` to verify/decrypt cookies retrieved from the child jar. Any
-    /// retrievals from the child jar will be made from the parent jar.
+    /// Removes all session cookies, that is, all cookies for which
+    /// [`Cookie::is_session()`] returns `true`, from this jar, as if by
+    /// [`CookieJar::force_remove()`].
+    ///
+    /// This mirrors a browser discarding session cookies when it is closed:
+    /// the cookies simply vanish. As such, **no** _removal_ cookies are added
+    /// to the delta, and session cookies added via [`CookieJar::add()`] are
+    /// dropped from the delta.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{Cookie, CookieJar, Key};
-    ///
-    /// // Generate a secure key.
-    /// let key = Key::generate();
+    /// use cookie::{Cookie, CookieJar};
+    /// use cookie::time::Duration;
     ///
-    /// // Add a private (signed + encrypted) cookie.
     /// let mut jar = CookieJar::new();
-    /// jar.private_mut(&key).add(("private", "text"));
-    ///
-    /// // The cookie's contents are encrypted.
-    /// assert_ne!(jar.get("private").unwrap().value(), "text");
-    ///
-    /// // They can be decrypted and verified through the child jar.
-    /// assert_eq!(jar.private(&key).get("private").unwrap().value(), "text");
+    /// jar.add_original(("session", "1"));
+    /// jar.add_original(Cookie::build(("persistent", "2")).max_age(Duration::days(1)));
+    /// jar.add(("new_session", "3"));
     ///
-    /// // A tampered with cookie does not validate but still exists.
-    /// let mut cookie = jar.get("private").unwrap().clone();
-    /// jar.add(("private", cookie.value().to_string() + "!"));
-    /// assert!(jar.private(&key).get("private").is_none());
-    /// assert!(jar.get("private").is_some());
+    /// jar.clear_session_cookies();
+    /// assert_eq!(jar.iter().count(), 1);
+    /// assert!(jar.get("persistent").is_some());
+    /// assert_eq!(jar.delta().count(), 0);
     /// ```
-    #[cfg(feature = "private")]
-    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
-    pub fn private<'a>(&'a self, key: &Key) -> PrivateJar<&'a Self> {
-        PrivateJar::new(self, key)
+    pub fn clear_session_cookies(&mut self) {
+        let names: Vec<String> = self.iter()
+            .filter(|c| c.is_session())
+            .map(|c| c.name().to_string())
+            .collect();
+
+        for name in names {
+            self.force_remove(name);
+        }
     }
 
-    /// Returns a read/write `PrivateJar` with `self` as its parent jar using
-    /// the key `key` to sign/encrypt and verify/decrypt cookies added/retrieved
-    /// from the child jar.
+    /// Removes every cookie for which [`Cookie::is_expired_at()`] returns
+    /// `true` at `now` from this jar, as if by [`CookieJar::force_remove()`],
+    /// and returns the names of the removed cookies in an unspecified order.
     ///
-    /// Any modifications to the child jar will be reflected on the parent jar,
-    /// and any retrievals from the child jar will be made from the parent jar.
+    /// This mirrors a browser's periodic sweep of expired cookies: the cookies
+    /// simply vanish. As such, **no** _removal_ cookies are added to the
+    /// delta, and expired cookies added via [`CookieJar::add()`] are dropped
+    /// from the delta.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{Cookie, CookieJar, Key};
-    ///
-    /// // Generate a secure key.
-    /// let key = Key::generate();
+    /// use cookie::{Cookie, CookieJar};
+    /// use cookie::time::{Duration, macros::datetime};
     ///
-    /// // Add a private (signed + encrypted) cookie.
     /// let mut jar = CookieJar::new();
-    /// jar.private_mut(&key).add(("private", "text"));
+    /// jar.add_original(Cookie::build(("old", "1")).expires(datetime!(2020-01-01 0:00 UTC)));
+    /// jar.add_original(Cookie::build(("live", "2")).expires(datetime!(2030-01-01 0:00 UTC)));
+    /// jar.add(("session", "3"));
     ///
-    /// // Remove a cookie using the child jar.
-    /// jar.private_mut(&key).remove("private");
+    /// let expired = jar.expire(datetime!(2025-01-01 0:00 UTC));
+    /// assert_eq!(expired, ["old"]);
+    /// assert!(jar.get("old").is_none());
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert_eq!(jar.delta().count(), 1);
     /// ```
-    #[cfg(feature = "private")]
-    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
-    pub fn private_mut<'a>(&'a mut self, key: &Key) -> PrivateJar<&'a mut Self> {
-        PrivateJar::new(self, key)
+    pub fn expire(&mut self, now: time::OffsetDateTime) -> Vec<String> {
+        let names: Vec<String> = self.iter()
+            .filter(|c| c.is_expired_at(now))
+            .map(|c| c.name().to_string())
+            .collect();
+
+        for name in &names {
+            self.force_remove(name);
+        }
+
+        names
     }
 
-    /// Returns a read-only `SignedJar` with `self` as its parent jar using the
-    /// key `key` to verify cookies retrieved from the child jar. Any retrievals
-    /// from the child jar will be made from the parent jar.
+    /// Removes every cookie in this jar as if by calling
+    /// [`CookieJar::remove()`] on each cookie returned by
+    /// [`CookieJar::iter()`].
+    ///
+    /// As with `remove()`, a _removal_ cookie is added to the delta for every
+    /// original cookie, while cookies that were only added via
+    /// [`CookieJar::add()`] are simply dropped from the delta. Each removal
+    /// cookie retains the `path` and `domain` of the cookie it removes. This
+    /// is useful to expire all of a client's cookies, as on logout.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{Cookie, CookieJar, Key};
-    ///
-    /// // Generate a secure key.
-    /// let key = Key::generate();
+    /// use cookie::CookieJar;
     ///
-    /// // Add a signed cookie.
     /// let mut jar = CookieJar::new();
-    /// jar.signed_mut(&key).add(("signed", "text"));
-    ///
-    /// // The cookie's contents are signed but still in plaintext.
-    /// assert_ne!(jar.get("signed").unwrap().value(), "text");
-    /// assert!(jar.get("signed").unwrap().value().contains("text"));
+    /// jar.add_original(("name", "value"));
+    /// jar.add_original(("second", "two"));
+    /// jar.add(("new", "three"));
     ///
-    /// // They can be verified through the child jar.
-    /// assert_eq!(jar.signed(&key).get("signed").unwrap().value(), "text");
+    /// jar.remove_all();
+    /// assert_eq!(jar.iter().count(), 0);
     ///
-    /// // A tampered with cookie does not validate but still exists.
-    /// let mut cookie = jar.get("signed").unwrap().clone();
-    /// jar.add(("signed", cookie.value().to_string() + "!"));
-    /// assert!(jar.signed(&key).get("signed").is_none());
-    /// assert!(jar.get("signed").is_some());
+    /// // The delta contains removals for the two original cookies only.
+    /// let mut removed: Vec<_> = jar.delta().map(|c| c.name()).collect();
+    /// removed.sort();
+    /// assert_eq!(removed, ["name", "second"]);
     /// ```
-    #[cfg(feature = "signed")]
-    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
-    pub fn signed<'a>(&'a self, key: &Key) -> SignedJar<&'a Self> {
-        SignedJar::new(self, key)
+    pub fn remove_all(&mut self) {
+        let cookies: Vec<Cookie<'static>> = self.iter().cloned().collect();
+        for cookie in cookies {
+            self.remove(cookie);
+        }
     }
 
-    /// Returns a read/write `SignedJar` with `self` as its parent jar using the
-    /// key `key` to sign/verify cookies added/retrieved from the child jar.
+    /// Returns a removal cookie, as by [`Cookie::to_removal()`], for every
+    /// cookie the client may currently hold according to this jar, without
+    /// modifying the jar. Each removal cookie retains the `path` and `domain`
+    /// of the cookie it removes.
     ///
-    /// Any modifications to the child jar will be reflected on the parent jar,
-    /// and any retrievals from the child jar will be made from the parent jar.
+    /// The returned removals cover every cookie in [`CookieJar::iter()`] as
+    /// well as every original cookie replaced via [`CookieJar::add()`] with a
+    /// cookie of a different `path` or `domain`, as the client then holds
+    /// both. Original cookies that were removed via [`CookieJar::remove()`]
+    /// are already removed by the [delta](CookieJar::delta()) and are
+    /// excluded, as are exact duplicates.
+    ///
+    /// A server may alternatively emit a `Clear-Site-Data: "cookies"` header
+    /// to remove all cookies for its origin, including those unknown to the
+    /// jar. Support for that header varies across clients, however, so these
+    /// removals serve as a fallback or a replacement.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::{CookieJar, Key};
-    ///
-    /// // Generate a secure key.
-    /// let key = Key::generate();
+    /// use cookie::{Cookie, CookieJar};
     ///
-    /// // Add a signed cookie.
     /// let mut jar = CookieJar::new();
-    /// jar.signed_mut(&key).add(("signed", "text"));
+    /// jar.add_original(Cookie::build(("name", "value")).path("/"));
+    /// jar.add(Cookie::build(("new", "value")).path("/docs"));
     ///
-    /// // Remove a cookie.
-    /// jar.signed_mut(&key).remove("signed");
+    /// let removals = jar.clear_all_removals();
+    /// assert_eq!(removals.len(), 2);
+    /// assert!(removals.iter().all(|c| c.value().is_empty()));
+    ///
+    /// // The jar itself is unmodified.
+    /// assert_eq!(jar.iter().count(), 2);
     /// ```
-    #[cfg(feature = "signed")]
-    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
-    pub fn signed_mut<'a>(&'a mut self, key: &Key) -> SignedJar<&'a mut Self> {
-        SignedJar::new(self, key)
+    pub fn clear_all_removals(&self) -> Vec<Cookie<'static>> {
+        let shadowed = self.original_cookies.iter()
+            .filter(|c| matches!(self.delta_cookies.get(c.name()), Some(d) if !d.removed))
+            .map(|c| &c.cookie);
+
+        let mut removals: Vec<Cookie<'static>> = vec![];
+        for cookie in self.iter().chain(shadowed) {
+            let duplicate = removals.iter().any(|r| r.name() == cookie.name()
+                && r.path() == cookie.path()
+                && r.domain() == cookie.domain());
+
+            if !duplicate {
+                removals.push(cookie.to_removal());
+            }
+        }
+
+        removals
     }
 
-    /// Returns a read-only `PrefixedJar` with `self` as its parent jar that
-    /// prefixes the name of cookies with `prefix`. Any retrievals from the
-    /// child jar will be made from the parent jar.
-    ///
-    /// **Note:** Cookie prefixes are specified in an HTTP draft! Their meaning
-    /// and definition are subject to change.
+    /// Removes all delta cookies, i.e. all cookies not added via
+    /// [`CookieJar::add_original()`], from this `CookieJar`. This undoes any
+    /// changes from [`CookieJar::add()`] and [`CookieJar::remove()`]
+    /// operations.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use cookie::CookieJar;
-    /// use cookie::prefix::{Host, Secure};
+    /// use cookie::{CookieJar, Cookie};
     ///
-    /// // Add a `Host` prefixed cookie.
     /// let mut jar = CookieJar::new();
-    /// jar.prefixed_mut(Host).add(("h0st", "value"));
-    /// jar.prefixed_mut(Secure).add(("secur3", "value"));
     ///
-    /// // The cookie's name is prefixed in the parent jar.
-    /// assert!(matches!(jar.get("h0st"), None));
-    /// assert!(matches!(jar.get("__Host-h0st"), Some(_)));
-    /// assert!(matches!(jar.get("secur3"), None));
-    /// assert!(matches!(jar.get("__Secure-secur3"), Some(_)));
+    /// // Only original cookies will remain after calling `reset_delta`.
+    /// jar.add_original(("name", "value"));
+    /// jar.add_original(("language", "Rust"));
     ///
-    /// // The prefixed jar automatically removes the prefix.
-    /// assert_eq!(jar.prefixed(Host).get("h0st").unwrap().name(), "h0st");
-    /// assert_eq!(jar.prefixed(Host).get("h0st").unwrap().value(), "value");
-    /// assert_eq!(jar.prefixed(Secure).get("secur3").unwrap().name(), "secur3");
-    /// assert_eq!(jar.prefixed(Secure).get("secur3").unwrap().value(), "value");
+    /// // These operations, represented by delta cookies, will be reset.
+    /// jar.add(("language", "C++"));
+    /// jar.remove("name");
     ///
-    /// // Only the correct prefixed jar retrieves the cookie.
-    /// assert!(matches!(jar.prefixed(Host).get("secur3"), None));
-    /// assert!(matches!(jar.prefixed(Secure).get("h0st"), None));
+    /// // All is normal.
+    /// assert_eq!(jar.get("name"), None);
+    /// assert_eq!(jar.get("language").map(Cookie::value), Some("C++"));
+    /// assert_eq!(jar.iter().count(), 1);
+    /// assert_eq!(jar.delta().count(), 2);
+    ///
+    /// // Resetting undoes delta operations.
+    /// jar.reset_delta();
+    /// assert_eq!(jar.get("name").map(Cookie::value), Some("value"));
+    /// assert_eq!(jar.get("language").map(Cookie::value), Some("Rust"));
+    /// assert_eq!(jar.iter().count(), 2);
+    /// assert_eq!(jar.delta().count(), 0);
     /// ```
-    #[inline(always)]
-    pub fn prefixed<'a, P: Prefix>(&'a self, prefix: P) -> PrefixedJar<P, &'a Self> {
-        let _ = prefix;
-        PrefixedJar::new(self)
+    pub fn reset_delta(&mut self) {
+        for cookie in std::mem::take(&mut self.delta_cookies) {
+            if !self.original_cookies.contains(cookie.name()) {
+                self.meta.remove(cookie.name());
+            }
+        }
+
+        self.sync_order();
     }
 
-    /// Returns a read/write `PrefixedJar` with `self` as its parent jar that
-    /// prefixes the name of cookies with `prefix` and makes the cookie conform
-    /// to the prefix's requirements. This means that added cookies:
-    ///
-    ///   1. Have the [`Prefix::PREFIX`] prepended to their name.
-    ///   2. Modify the cookie via [`Prefix::conform()`] so that it conforms to
-    ///      the prefix's requirements.
-    ///
-    /// Any modifications to the child jar will be reflected on the parent jar,
-    /// and any retrievals from the child jar will be made from the parent jar.
+    /// Returns a [`Checkpoint`] of the current delta state and metadata of
+    /// `self` which can later be passed to [`CookieJar::restore()`] to revert
+    /// any changes made via [`CookieJar::add()`], [`CookieJar::remove()`], and
+    /// [`CookieJar::set_meta()`] in the interim.
     ///
-    /// **Note:** Cookie prefixes are specified in an HTTP draft! Their meaning
-    /// and definition are subject to change.
+    /// Only the delta and metadata are captured: original cookies are not, so
+    /// the effects of [`CookieJar::add_original()`] and
+    /// [`CookieJar::force_remove()`] on original cookies are not reverted by a
+    /// restore.
     ///
     /// # Example
     ///
     /// ```rust
     /// use cookie::CookieJar;
-    /// use cookie::prefix::{Host, Secure};
     ///
-    /// // Add some prefixed cookies.
     /// let mut jar = CookieJar::new();
-    /// jar.prefixed_mut(Host).add(("one", "1"));
-    /// jar.prefixed_mut(Secure).add((2.to_string(), "2"));
-    /// jar.prefixed_mut(Host).add((format!("{:0b}", 3), "0b11"));
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("kept", "1"));
     ///
-    /// // Fetch cookies with either `prefixed()` or `prefixed_mut()`.
-    /// assert_eq!(jar.prefixed(Host).get("one").unwrap().value(), "1");
-    /// assert_eq!(jar.prefixed(Secure).get("2").unwrap().value(), "2");
-    /// assert_eq!(jar.prefixed_mut(Host).get("11").unwrap().value(), "0b11");
+    /// let checkpoint = jar.checkpoint();
+    /// jar.add(("tentative", "2"));
+    /// jar.remove("name");
+    /// assert_eq!(jar.delta().count(), 3);
     ///
-    /// // Remove cookies.
-    /// jar.prefixed_mut(Host).remove("one");
-    /// assert!(jar.prefixed(Host).get("one").is_none());
+    /// // Something went wrong: revert.
+    /// jar.restore(checkpoint);
+    /// assert_eq!(jar.delta().count(), 1);
+    /// assert!(jar.get("tentative").is_none());
+    /// assert_eq!(jar.get("name").map(|c| c.value()), Some("value"));
     /// ```
-    pub fn prefixed_mut<'a, P: Prefix>(&'a mut self, prefix: P) -> PrefixedJar<P, &'a mut Self> {
-        let _ = prefix;
-        PrefixedJar::new(self)
+    pub fn checkpoint(&self) -> Checkpoint<M>
+        where M: Clone
+    {
+        Checkpoint {
+            delta_cookies: self.delta_cookies.clone(),
+            order: self.order.clone(),
+            meta: self.meta.clone(),
+        }
     }
-}
 
-use std::collections::hash_set::Iter as HashSetIter;
+    /// Restores the delta state and metadata of `self` to that captured in
+    /// `checkpoint` by [`CookieJar::checkpoint()`]. See
+    /// [`CookieJar::checkpoint()`] for details.
+    pub fn restore(&mut self, checkpoint: Checkpoint<M>) {
+        self.delta_cookies = checkpoint.delta_cookies;
+        self.meta = checkpoint.meta;
+        if self.limit.is_some() {
+            self.order = checkpoint.order;
+            self.sync_order();
+            self.evict();
+        }
+    }
 
-/// Iterator over the changes to a cookie jar.
+    /// Returns an iterator over cookies that represent the changes to this jar
+    /// over time. These cookies can be rendered directly as `Set-Cookie` header
+    /// values to affect the changes made to this jar on the client.
+    ///
+    /// Each cookie must be sent in its own `Set-Cookie` header. Unlike most
+    /// headers, `Set-Cookie` headers cannot be combined into one by joining
+    /// their values with `,` as cookie attributes, such as `Expires`, may
+    /// themselves contain `,`. Clients parse a joined value as a single cookie.
+    /// [`CookieJar::delta_lines()`] yields the header value for each cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add_original(("second", "two"));
+    ///
+    /// // Add new cookies.
+    /// jar.add(("new", "third"));
+    /// jar.add(("another", "fourth"));
+    /// jar.add(("yac", "fifth"));
+    ///
+    /// // Remove some cookies.
+    /// jar.remove(("name"));
+    /// jar.remove(("another"));
+    ///
+    /// // Delta contains two new cookies ("new", "yac") and a removal ("name").
+    /// assert_eq!(jar.delta().count(), 3);
+    /// ```
+    pub fn delta(&self) -> Delta<'_> {
+        Delta { iter: self.delta_cookies.iter() }
+    }
+
+    /// Returns an iterator over the `Set-Cookie` header values, one for each
+    /// cookie in the [delta](CookieJar::delta()), in the same order.
+    ///
+    /// Each value must be sent as a separate `Set-Cookie` header: joining the
+    /// values into one header, with `,` or any other separator, is invalid and
+    /// results in clients setting at most one, likely mangled, cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add(("name", "value"));
+    /// jar.add(("second", "two"));
+    ///
+    /// let mut lines: Vec<_> = jar.delta_lines().collect();
+    /// lines.sort();
+    /// assert_eq!(lines, ["name=value", "second=two"]);
+    ///
+    /// # let mut response = vec![];
+    /// // Emit one header per cookie.
+    /// for line in jar.delta_lines() {
+    ///     # /*
+    ///     response.append_header("Set-Cookie", line);
+    ///     # */
+    ///     # response.push(("Set-Cookie", line));
+    /// }
+    /// # assert_eq!(response.len(), 2);
+    /// ```
+    pub fn delta_lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.delta().map(|cookie| cookie.to_string())
+    }
+
+    /// Returns an iterator over the cookies in the [delta](CookieJar::delta())
+    /// that are additions: cookies added via [`CookieJar::add()`] and not
+    /// subsequently removed. Together with [`CookieJar::delta_removals()`],
+    /// this partitions the delta.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("new", "value"));
+    /// jar.remove("name");
+    ///
+    /// let additions: Vec<_> = jar.delta_additions().map(|c| c.name()).collect();
+    /// assert_eq!(additions, ["new"]);
+    /// ```
+    pub fn delta_additions(&self) -> impl Iterator<Item = &Cookie<'static>> + '_ {
+        self.delta_cookies.iter()
+            .filter(|c| !c.removed)
+            .map(|c| &c.cookie)
+    }
+
+    /// Returns an iterator over the cookies in the [delta](CookieJar::delta())
+    /// that are removals: the removal cookies, as generated by
+    /// [`CookieJar::remove()`], for original cookies that were removed.
+    /// Together with [`CookieJar::delta_additions()`], this partitions the
+    /// delta.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("new", "value"));
+    /// jar.remove("name");
+    ///
+    /// let removals: Vec<_> = jar.delta_removals().collect();
+    /// assert_eq!(removals.len(), 1);
+    /// assert_eq!(removals[0].name_value(), ("name", ""));
+    /// assert_eq!(removals[0].max_age(), Some(cookie::time::Duration::ZERO));
+    /// ```
+    pub fn delta_removals(&self) -> impl Iterator<Item = &Cookie<'static>> + '_ {
+        self.delta_cookies.iter()
+            .filter(|c| c.removed)
+            .map(|c| &c.cookie)
+    }
+
+    /// Returns `true` if the jar has pending changes, that is, if
+    /// [`CookieJar::delta()`] would yield at least one cookie.
+    ///
+    /// Original cookies, added via [`CookieJar::add_original()`], do not make
+    /// a jar dirty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// assert!(!jar.is_dirty());
+    ///
+    /// jar.add(("new", "value"));
+    /// assert!(jar.is_dirty());
+    /// ```
+    pub fn is_dirty(&self) -> bool {
+        !self.delta_cookies.is_empty()
+    }
+
+    /// Like [`CookieJar::delta()`], but skips cookies added via
+    /// [`CookieJar::add()`] that are identical, in name, value, and all
+    /// attributes, to the original cookie of the same name. Re-sending such a
+    /// cookie to the client is a no-op. Removal cookies are always included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(Cookie::build(("same", "value")).path("/"));
+    /// jar.add_original(("changed", "one"));
+    ///
+    /// jar.add(Cookie::build(("same", "value")).path("/"));
+    /// jar.add(("changed", "two"));
+    /// assert_eq!(jar.delta().count(), 2);
+    ///
+    /// let delta: Vec<_> = jar.delta_changed_only().collect();
+    /// assert_eq!(delta.len(), 1);
+    /// assert_eq!(delta[0].name_value(), ("changed", "two"));
+    /// ```
+    pub fn delta_changed_only(&self) -> impl Iterator<Item = &Cookie<'static>> + '_ {
+        self.delta_cookies.iter()
+            .filter(move |c| c.removed || !self.is_unchanged(c))
+            .map(|c| &c.cookie)
+    }
+
+    /// Removes every cookie added via [`CookieJar::add()`] that is identical,
+    /// in name, value, and all attributes, to the original cookie of the same
+    /// name from the delta. Afterwards, [`CookieJar::delta()`] yields exactly
+    /// the cookies yielded by [`CookieJar::delta_changed_only()`] before.
+    ///
+    /// Since such a cookie is identical to the original cookie it shadows, the
+    /// contents of the jar are unaffected. Removal cookies are always kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(Cookie::build(("same", "value")).path("/"));
+    /// jar.add_original(("changed", "one"));
+    ///
+    /// jar.add(Cookie::build(("same", "value")).path("/"));
+    /// jar.add(("changed", "two"));
+    /// assert_eq!(jar.delta().count(), 2);
+    ///
+    /// jar.minimize_delta();
+    /// let delta: Vec<_> = jar.delta().collect();
+    /// assert_eq!(delta.len(), 1);
+    /// assert_eq!(delta[0].name_value(), ("changed", "two"));
+    /// assert_eq!(jar.get("same").map(|c| c.value()), Some("value"));
+    /// ```
+    pub fn minimize_delta(&mut self) {
+        let originals = &self.original_cookies;
+        self.delta_cookies.retain(|c| {
+            c.removed || !matches!(originals.get(c.name()), Some(o) if o.cookie.eq_exact(c))
+        });
+    }
+
+    /// Returns `true` if `cookie` is identical to the original cookie with the
+    /// same name.
+    fn is_unchanged(&self, cookie: &Cookie<'_>) -> bool {
+        matches!(self.original_cookies.get(cookie.name()), Some(o) if o.cookie.eq_exact(cookie))
+    }
+
+    /// Returns an iterator over all of the cookies present in this jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie};
+    ///
+    /// let mut jar = CookieJar::new();
+    ///
+    /// jar.add_original(("name", "value"));
+    /// jar.add_original(("second", "two"));
+    ///
+    /// jar.add(("new", "third"));
+    /// jar.add(("another", "fourth"));
+    /// jar.add(("yac", "fifth"));
+    ///
+    /// jar.remove("name");
+    /// jar.remove("another");
+    ///
+    /// // There are three cookies in the jar: "second", "new", and "yac".
+    /// # assert_eq!(jar.iter().count(), 3);
+    /// for cookie in jar.iter() {
+    ///     match cookie.name() {
+    ///         "second" => assert_eq!(cookie.value(), "two"),
+    ///         "new" => assert_eq!(cookie.value(), "third"),
+    ///         "yac" => assert_eq!(cookie.value(), "fifth"),
+    ///         _ => unreachable!("there are only three cookies in the jar")
+    ///     }
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            delta_cookies: self.delta_cookies.iter()
+                .chain(self.original_cookies.difference(&self.delta_cookies)),
+        }
+    }
+
+    /// Returns an iterator over all of the cookies present in this jar, that
+    /// is, those returned by [`CookieJar::iter()`], sorted by name and then by
+    /// path length, longest first, as in RFC 6265 §5.4.
+    ///
+    /// Unlike `iter()`, whose order is unspecified, the order is deterministic,
+    /// making this method suitable for reproducible output. The cookies are
+    /// collected into a `Vec` and sorted on each call, so this method
+    /// allocates and takes `O(n log n)` time in the number of cookies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("b", "2"));
+    /// jar.add(("c", "3"));
+    /// jar.add(("a", "1"));
+    ///
+    /// let names: Vec<_> = jar.iter_sorted().map(|c| c.name()).collect();
+    /// assert_eq!(names, ["a", "b", "c"]);
+    /// ```
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &Cookie<'static>> + '_ {
+        let path_len = |c: &Cookie<'_>| c.path().map_or(0, str::len);
+        let mut cookies: Vec<_> = self.iter().collect();
+        cookies.sort_by(|a, b| {
+            a.name().cmp(b.name()).then_with(|| path_len(b).cmp(&path_len(a)))
+        });
+
+        cookies.into_iter()
+    }
+
+    /// Returns a vector of clones of all of the cookies present in this jar,
+    /// that is, of the cookies returned by [`CookieJar::iter()`], in the same
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("new", "third"));
+    /// jar.remove("name");
+    ///
+    /// let cookies = jar.to_vec();
+    /// assert_eq!(cookies.len(), 1);
+    /// assert_eq!(cookies[0].name_value(), ("new", "third"));
+    /// ```
+    pub fn to_vec(&self) -> Vec<Cookie<'static>> {
+        self.iter().cloned().collect()
+    }
+
+    /// Consumes this jar and returns a vector of all of the cookies present in
+    /// it, as [`CookieJar::to_vec()`] would, without cloning them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(("new", "third"));
+    ///
+    /// let mut cookies = jar.into_vec();
+    /// cookies.sort_by(|a, b| a.name().cmp(b.name()));
+    /// assert_eq!(cookies[0].name_value(), ("name", "value"));
+    /// assert_eq!(cookies[1].name_value(), ("new", "third"));
+    /// ```
+    pub fn into_vec(self) -> Vec<Cookie<'static>> {
+        let CookieJar { original_cookies, delta_cookies, .. } = self;
+        let originals: Vec<_> = original_cookies.into_iter()
+            .filter(|c| !delta_cookies.contains(c))
+            .collect();
+
+        delta_cookies.into_iter()
+            .chain(originals)
+            .filter(|c| !c.removed)
+            .map(|c| c.cookie)
+            .collect()
+    }
+
+    /// Returns an iterator over the names of all of the cookies present in
+    /// this jar. This is equivalent to `jar.iter().map(|c| c.name())`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add_original(("second", "two"));
+    /// jar.add(("new", "third"));
+    /// jar.remove("name");
+    ///
+    /// let mut names: Vec<_> = jar.names().collect();
+    /// names.sort();
+    /// assert_eq!(names, ["new", "second"]);
+    /// ```
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.iter().map(|cookie| cookie.name())
+    }
+
+    /// Returns the `Cookie` request header value and the `Set-Cookie` response
+    /// header values for `self`.
+    ///
+    /// The first element is every cookie in the jar, as returned by
+    /// [`CookieJar::iter()`], rendered as `name=value` and joined by `; `. The
+    /// second element is every cookie in [`CookieJar::delta()`] rendered as a
+    /// `Set-Cookie` header value. Names and values are not percent-encoded.
+    /// The order of cookies in either element is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(Cookie::build(("new", "third")).path("/"));
+    ///
+    /// let (request, response) = jar.headers();
+    /// let mut pairs: Vec<_> = request.split("; ").collect();
+    /// pairs.sort();
+    /// assert_eq!(pairs, ["name=value", "new=third"]);
+    /// assert_eq!(response, ["new=third; Path=/"]);
+    /// ```
+    pub fn headers(&self) -> (String, Vec<String>) {
+        let request = self.iter()
+            .map(|c| c.stripped().to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let response = self.delta().map(|c| c.to_string()).collect();
+        (request, response)
+    }
+
+    /// Returns a stable key identifying the values of the cookies named in
+    /// `names`, suitable as a cache key or `Vary` key.
+    ///
+    /// The key is the name and value pairs of the cookies in the jar whose name
+    /// is in `names`, sorted by name and joined by `; `. Each pair is written
+    /// as `{name length}:{name}={value length}:{value}` so that a name or
+    /// value containing `=` or `; ` can't be mistaken for another pair. Cookies
+    /// not named in `names` are ignored, as are names without a corresponding
+    /// cookie. The key depends neither on the order of `names` nor on the
+    /// order in which cookies were added.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("theme", "dark"));
+    /// jar.add_original(("session", "abc123"));
+    /// jar.add(("lang", "en"));
+    ///
+    /// assert_eq!(jar.cache_key(&["theme", "lang", "missing"]), "4:lang=2:en; 5:theme=4:dark");
+    /// assert_eq!(jar.cache_key(&["lang", "theme"]), "4:lang=2:en; 5:theme=4:dark");
+    /// ```
+    pub fn cache_key(&self, names: &[&str]) -> String {
+        let mut names = names.to_vec();
+        names.sort_unstable();
+        names.dedup();
+
+        names.iter()
+            .filter_map(|name| self.get(name))
+            .map(|c| format!("{}:{}={}:{}", c.name().len(), c.name(), c.value().len(), c.value()))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Returns a read-only `PrivateJar` with `self` as its parent jar using the
+    /// key `key` to verify/decrypt cookies retrieved from the child jar. Any
+    /// retrievals from the child jar will be made from the parent jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar, Key};
+    ///
+    /// // Generate a secure key.
+    /// let key = Key::generate();
+    ///
+    /// // Add a private (signed + encrypted) cookie.
+    /// let mut jar = CookieJar::new();
+    /// jar.private_mut(&key).add(("private", "text"));
+    ///
+    /// // The cookie's contents are encrypted.
+    /// assert_ne!(jar.get("private").unwrap().value(), "text");
+    ///
+    /// // They can be decrypted and verified through the child jar.
+    /// assert_eq!(jar.private(&key).get("private").unwrap().value(), "text");
+    ///
+    /// // A tampered with cookie does not validate but still exists.
+    /// let mut cookie = jar.get("private").unwrap().clone();
+    /// jar.add(("private", cookie.value().to_string() + "!"));
+    /// assert!(jar.private(&key).get("private").is_none());
+    /// assert!(jar.get("private").is_some());
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn private<'a>(&'a self, key: &Key) -> PrivateJar<&'a Self, M> {
+        PrivateJar::new(self, key)
+    }
+
+    /// Returns a read/write `PrivateJar` with `self` as its parent jar using
+    /// the key `key` to sign/encrypt and verify/decrypt cookies added/retrieved
+    /// from the child jar.
+    ///
+    /// Any modifications to the child jar will be reflected on the parent jar,
+    /// and any retrievals from the child jar will be made from the parent jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar, Key};
+    ///
+    /// // Generate a secure key.
+    /// let key = Key::generate();
+    ///
+    /// // Add a private (signed + encrypted) cookie.
+    /// let mut jar = CookieJar::new();
+    /// jar.private_mut(&key).add(("private", "text"));
+    ///
+    /// // Remove a cookie using the child jar.
+    /// jar.private_mut(&key).remove("private");
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn private_mut<'a>(&'a mut self, key: &Key) -> PrivateJar<&'a mut Self, M> {
+        PrivateJar::new(self, key)
+    }
+
+    /// Returns a read-only `PrivateJar` with `self` as its parent jar that
+    /// decrypts cookies with any of the keys in `keys`, trying each in order.
+    /// This allows cookies encrypted with an older key to be read while the
+    /// first key, the primary key, is rotated in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key};
+    ///
+    /// let (old_key, new_key) = (Key::generate(), Key::generate());
+    /// let mut jar = CookieJar::new();
+    /// jar.private_mut(&old_key).add(("private", "text"));
+    ///
+    /// assert!(jar.private(&new_key).get("private").is_none());
+    ///
+    /// let private = jar.private_rotatable(&[&new_key, &old_key]);
+    /// assert_eq!(private.get("private").unwrap().value(), "text");
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn private_rotatable<'a>(&'a self, keys: &[&Key]) -> PrivateJar<&'a Self, M> {
+        PrivateJar::new_rotatable(self, keys)
+    }
+
+    /// Returns a read/write `PrivateJar` with `self` as its parent jar that
+    /// encrypts cookies with the first key in `keys` and decrypts cookies with
+    /// any of the keys in `keys`, trying each in order.
+    ///
+    /// To re-encrypt a cookie with the primary key, retrieve it from the child
+    /// jar and add it back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key};
+    ///
+    /// let (old_key, new_key) = (Key::generate(), Key::generate());
+    /// let mut jar = CookieJar::new();
+    /// jar.private_mut(&old_key).add(("private", "text"));
+    ///
+    /// let mut private = jar.private_rotatable_mut(&[&new_key, &old_key]);
+    /// let cookie = private.get("private").unwrap();
+    /// private.add(cookie);
+    ///
+    /// assert_eq!(jar.private(&new_key).get("private").unwrap().value(), "text");
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn private_rotatable_mut<'a>(&'a mut self, keys: &[&Key]) -> PrivateJar<&'a mut Self, M> {
+        PrivateJar::new_rotatable(self, keys)
+    }
+
+    /// Serializes every cookie in the jar, as returned by [`CookieJar::iter()`],
+    /// and encrypts the result with authenticated encryption under `key`,
+    /// returning a single base64 blob suitable for storage at rest. The blob
+    /// can be decrypted and deserialized with [`CookieJar::open_jar()`].
+    ///
+    /// Unlike a [`PrivateJar`], which encrypts the value of each cookie it
+    /// manages individually, this method encrypts the jar as a whole, cookie
+    /// names and attributes included. Every field of every cookie is
+    /// preserved exactly, as by [`Cookie::eq_exact()`], but the delta is not.
+    ///
+    /// Returns [`SealError::TooLong`] if a cookie's name, value, or attribute
+    /// is longer than `u32::MAX` bytes or if it has more than `u32::MAX`
+    /// extensions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    /// jar.add(Cookie::build(("other", "thing")).path("/").secure(true));
+    ///
+    /// let blob = jar.seal_jar(&key).unwrap();
+    /// assert!(!blob.contains("name"));
+    ///
+    /// let opened: CookieJar = CookieJar::open_jar(&blob, &key).unwrap();
+    /// assert_eq!(opened.get("name").unwrap().value(), "value");
+    /// assert_eq!(opened.get("other").unwrap().path(), Some("/"));
+    /// assert_eq!(opened.get("other").unwrap().secure(), Some(true));
+    /// ```
+    #[cfg(feature = "private")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+    pub fn seal_jar(&self, key: &Key) -> Result<String, SealError> {
+        let mut bytes = vec![];
+        for cookie in self.iter() {
+            write_sealed_cookie(&mut bytes, cookie)?;
+        }
+
+        Ok(crate::secure::seal(key.encryption(), SEALED_JAR_AAD, &bytes))
+    }
+
+    /// Returns a read-only `SignedJar` with `self` as its parent jar using the
+    /// key `key` to verify cookies retrieved from the child jar. Any retrievals
+    /// from the child jar will be made from the parent jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar, Key};
+    ///
+    /// // Generate a secure key.
+    /// let key = Key::generate();
+    ///
+    /// // Add a signed cookie.
+    /// let mut jar = CookieJar::new();
+    /// jar.signed_mut(&key).add(("signed", "text"));
+    ///
+    /// // The cookie's contents are signed but still in plaintext.
+    /// assert_ne!(jar.get("signed").unwrap().value(), "text");
+    /// assert!(jar.get("signed").unwrap().value().contains("text"));
+    ///
+    /// // They can be verified through the child jar.
+    /// assert_eq!(jar.signed(&key).get("signed").unwrap().value(), "text");
+    ///
+    /// // A tampered with cookie does not validate but still exists.
+    /// let mut cookie = jar.get("signed").unwrap().clone();
+    /// jar.add(("signed", cookie.value().to_string() + "!"));
+    /// assert!(jar.signed(&key).get("signed").is_none());
+    /// assert!(jar.get("signed").is_some());
+    /// ```
+    #[cfg(feature = "signed")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+    pub fn signed<'a>(&'a self, key: &Key) -> SignedJar<&'a Self, M> {
+        SignedJar::new(self, key)
+    }
+
+    /// Returns a read/write `SignedJar` with `self` as its parent jar using the
+    /// key `key` to sign/verify cookies added/retrieved from the child jar.
+    ///
+    /// Any modifications to the child jar will be reflected on the parent jar,
+    /// and any retrievals from the child jar will be made from the parent jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key};
+    ///
+    /// // Generate a secure key.
+    /// let key = Key::generate();
+    ///
+    /// // Add a signed cookie.
+    /// let mut jar = CookieJar::new();
+    /// jar.signed_mut(&key).add(("signed", "text"));
+    ///
+    /// // Remove a cookie.
+    /// jar.signed_mut(&key).remove("signed");
+    /// ```
+    #[cfg(feature = "signed")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+    pub fn signed_mut<'a>(&'a mut self, key: &Key) -> SignedJar<&'a mut Self, M> {
+        SignedJar::new(self, key)
+    }
+
+    /// Returns a read-only `SignedJar` with `self` as its parent jar using the
+    /// key `key` and the HMAC algorithm `algorithm` to verify cookies retrieved
+    /// from the child jar. Any retrievals from the child jar will be made from
+    /// the parent jar.
+    ///
+    /// This is identical to [`CookieJar::signed()`], which uses the default
+    /// [`SignatureAlgorithm::Sha256`], except for the choice of algorithm. A
+    /// cookie verifies only with the algorithm and key it was signed with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key, SignatureAlgorithm};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.signed_mut_with(&key, SignatureAlgorithm::Sha512).add(("name", "value"));
+    ///
+    /// let signed = jar.signed_with(&key, SignatureAlgorithm::Sha512);
+    /// assert_eq!(signed.get("name").unwrap().value(), "value");
+    /// assert!(jar.signed(&key).get("name").is_none());
+    /// ```
+    #[cfg(feature = "signed")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+    pub fn signed_with<'a>(
+        &'a self,
+        key: &Key,
+        algorithm: SignatureAlgorithm
+    ) -> SignedJar<&'a Self, M> {
+        SignedJar::with_algorithm(self, key, algorithm)
+    }
+
+    /// Returns a read/write `SignedJar` with `self` as its parent jar using the
+    /// key `key` and the HMAC algorithm `algorithm` to sign/verify cookies
+    /// added/retrieved from the child jar.
+    ///
+    /// This is identical to [`CookieJar::signed_mut()`], which uses the default
+    /// [`SignatureAlgorithm::Sha256`], except for the choice of algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Key, SignatureAlgorithm};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.signed_mut_with(&key, SignatureAlgorithm::Sha384).add(("name", "value"));
+    ///
+    /// // The value is prefixed with a 64 byte base64 digest.
+    /// assert_eq!(jar.get("name").unwrap().value().len(), 64 + "value".len());
+    /// ```
+    #[cfg(feature = "signed")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+    pub fn signed_mut_with<'a>(
+        &'a mut self,
+        key: &Key,
+        algorithm: SignatureAlgorithm
+    ) -> SignedJar<&'a mut Self, M> {
+        SignedJar::with_algorithm(self, key, algorithm)
+    }
+
+    /// Returns a read-only `PrefixedJar` with `self` as its parent jar that
+    /// prefixes the name of cookies with `prefix`. Any retrievals from the
+    /// child jar will be made from the parent jar.
+    ///
+    /// **Note:** Cookie prefixes are specified in an HTTP draft! Their meaning
+    /// and definition are subject to change.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    /// use cookie::prefix::{Host, Secure};
+    ///
+    /// // Add a `Host` prefixed cookie.
+    /// let mut jar = CookieJar::new();
+    /// jar.prefixed_mut(Host).add(("h0st", "value"));
+    /// jar.prefixed_mut(Secure).add(("secur3", "value"));
+    ///
+    /// // The cookie's name is prefixed in the parent jar.
+    /// assert!(matches!(jar.get("h0st"), None));
+    /// assert!(matches!(jar.get("__Host-h0st"), Some(_)));
+    /// assert!(matches!(jar.get("secur3"), None));
+    /// assert!(matches!(jar.get("__Secure-secur3"), Some(_)));
+    ///
+    /// // The prefixed jar automatically removes the prefix.
+    /// assert_eq!(jar.prefixed(Host).get("h0st").unwrap().name(), "h0st");
+    /// assert_eq!(jar.prefixed(Host).get("h0st").unwrap().value(), "value");
+    /// assert_eq!(jar.prefixed(Secure).get("secur3").unwrap().name(), "secur3");
+    /// assert_eq!(jar.prefixed(Secure).get("secur3").unwrap().value(), "value");
+    ///
+    /// // Only the correct prefixed jar retrieves the cookie.
+    /// assert!(matches!(jar.prefixed(Host).get("secur3"), None));
+    /// assert!(matches!(jar.prefixed(Secure).get("h0st"), None));
+    /// ```
+    #[inline(always)]
+    pub fn prefixed<P: Prefix>(&self, prefix: P) -> PrefixedJar<P, &Self, M> {
+        let _ = prefix;
+        PrefixedJar::new(self)
+    }
+
+    /// Returns a read/write `PrefixedJar` with `self` as its parent jar that
+    /// prefixes the name of cookies with `prefix` and makes the cookie conform
+    /// to the prefix's requirements. This means that added cookies:
+    ///
+    ///   1. Have the [`Prefix::PREFIX`] prepended to their name.
+    ///   2. Modify the cookie via [`Prefix::conform()`] so that it conforms to
+    ///      the prefix's requirements.
+    ///
+    /// Any modifications to the child jar will be reflected on the parent jar,
+    /// and any retrievals from the child jar will be made from the parent jar.
+    ///
+    /// **Note:** Cookie prefixes are specified in an HTTP draft! Their meaning
+    /// and definition are subject to change.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    /// use cookie::prefix::{Host, Secure};
+    ///
+    /// // Add some prefixed cookies.
+    /// let mut jar = CookieJar::new();
+    /// jar.prefixed_mut(Host).add(("one", "1"));
+    /// jar.prefixed_mut(Secure).add((2.to_string(), "2"));
+    /// jar.prefixed_mut(Host).add((format!("{:0b}", 3), "0b11"));
+    ///
+    /// // Fetch cookies with either `prefixed()` or `prefixed_mut()`.
+    /// assert_eq!(jar.prefixed(Host).get("one").unwrap().value(), "1");
+    /// assert_eq!(jar.prefixed(Secure).get("2").unwrap().value(), "2");
+    /// assert_eq!(jar.prefixed_mut(Host).get("11").unwrap().value(), "0b11");
+    ///
+    /// // Remove cookies.
+    /// jar.prefixed_mut(Host).remove("one");
+    /// assert!(jar.prefixed(Host).get("one").is_none());
+    /// ```
+    pub fn prefixed_mut<P: Prefix>(&mut self, prefix: P) -> PrefixedJar<P, &mut Self, M> {
+        let _ = prefix;
+        PrefixedJar::new(self)
+    }
+
+    /// Returns a read-only `BackedJar` with `self` as its parent jar that reads
+    /// through to `store` for cookies that aren't in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar, CookieStore};
+    ///
+    /// struct OneCookie;
+    ///
+    /// impl CookieStore for OneCookie {
+    ///     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+    ///         Some(Cookie::new(name.to_string(), "stored"))
+    ///     }
+    ///
+    ///     fn store(&mut self, _: &Cookie<'static>) { }
+    ///     fn remove(&mut self, _: &str) { }
+    /// }
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("name", "value"));
+    ///
+    /// assert_eq!(jar.backed(OneCookie).get("name").unwrap().value(), "value");
+    /// assert_eq!(jar.backed(OneCookie).get("other").unwrap().value(), "stored");
+    /// ```
+    pub fn backed<S: CookieStore>(&self, store: S) -> BackedJar<S, &Self, M> {
+        BackedJar::new(self, store)
+    }
+
+    /// Returns a read/write `BackedJar` with `self` as its parent jar that
+    /// reads through to `store` for cookies that aren't in `self` and writes
+    /// additions and removals through to `store`.
+    ///
+    /// Any modifications to the child jar will be reflected on the parent jar
+    /// and in the store.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use cookie::{Cookie, CookieJar, CookieStore};
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryStore(HashMap<String, Cookie<'static>>);
+    ///
+    /// impl CookieStore for MemoryStore {
+    ///     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+    ///         self.0.get(name).cloned()
+    ///     }
+    ///
+    ///     fn store(&mut self, cookie: &Cookie<'static>) {
+    ///         self.0.insert(cookie.name().to_string(), cookie.clone());
+    ///     }
+    ///
+    ///     fn remove(&mut self, name: &str) {
+    ///         self.0.remove(name);
+    ///     }
+    /// }
+    ///
+    /// let mut store = MemoryStore::default();
+    /// let mut jar = CookieJar::new();
+    /// jar.backed_mut(&mut store).add(("name", "value"));
+    /// assert!(jar.get("name").is_some());
+    /// assert!(store.load("name").is_some());
+    ///
+    /// // A new jar reads the cookie from the store.
+    /// let jar = CookieJar::new();
+    /// assert_eq!(jar.backed(&mut store).get("name").unwrap().value(), "value");
+    /// ```
+    pub fn backed_mut<S: CookieStore>(&mut self, store: S) -> BackedJar<S, &mut Self, M> {
+        BackedJar::new(self, store)
+    }
+}
+
+/// An error returned when a cookie violates a policy of a [`CookieJar`].
+///
+/// Returned by [`CookieJar::try_add()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AddError {
+    /// The cookie's serialized size, `size`, exceeds the jar's maximum cookie
+    /// size, `limit`. See [`CookieJar::set_max_cookie_size()`].
+    TooLarge {
+        /// The serialized size of the cookie in bytes.
+        size: usize,
+        /// The jar's maximum cookie size in bytes.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for AddError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AddError::TooLarge { size, limit } => {
+                write!(f, "cookie is too large: {} bytes exceeds the limit of {} bytes",
+                       size, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AddError { }
+
+/// The associated data for blobs sealed by [`CookieJar::seal_jar()`]. It isn't
+/// valid UTF-8, so it can't be the name of a cookie, keeping blobs distinct
+/// from the values sealed by a `PrivateJar`, which use the cookie's name.
+#[cfg(feature = "private")]
+const SEALED_JAR_AAD: &[u8] = b"\xffcookie-jar";
+
+/// Appends `cookie` to `bytes` for [`CookieJar::seal_jar()`]. Each field of
+/// `cookie` is written in turn: strings are prefixed by their length as a
+/// big-endian `u32`, numbers are big-endian, and optional fields are prefixed
+/// by a tag byte that is `0` when the field is unset.
+#[cfg(feature = "private")]
+fn write_sealed_cookie(bytes: &mut Vec<u8>, cookie: &Cookie<'_>) -> Result<(), SealError> {
+    use std::convert::TryFrom;
+    use crate::{Expiration, SameSite};
+
+    fn len(bytes: &mut Vec<u8>, len: usize) -> Result<(), SealError> {
+        let len = u32::try_from(len).map_err(|_| SealError::TooLong)?;
+        bytes.extend_from_slice(&len.to_be_bytes());
+        Ok(())
+    }
+
+    fn string(bytes: &mut Vec<u8>, string: &str) -> Result<(), SealError> {
+        len(bytes, string.len())?;
+        bytes.extend_from_slice(string.as_bytes());
+        Ok(())
+    }
+
+    fn opt_string(bytes: &mut Vec<u8>, opt: Option<&str>) -> Result<(), SealError> {
+        match opt {
+            Some(value) => {
+                bytes.push(1);
+                string(bytes, value)
+            }
+            None => {
+                bytes.push(0);
+                Ok(())
+            }
+        }
+    }
+
+    fn flag(bytes: &mut Vec<u8>, flag: Option<bool>) {
+        bytes.push(match flag {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+    }
+
+    string(bytes, cookie.name())?;
+    string(bytes, cookie.value())?;
+    match cookie.expires() {
+        None => bytes.push(0),
+        Some(Expiration::Session) => bytes.push(1),
+        Some(Expiration::DateTime(time)) => {
+            bytes.push(2);
+            bytes.extend_from_slice(&time.unix_timestamp_nanos().to_be_bytes());
+            bytes.extend_from_slice(&time.offset().whole_seconds().to_be_bytes());
+        }
+    }
+
+    match cookie.max_age() {
+        None => bytes.push(0),
+        Some(max_age) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&max_age.whole_seconds().to_be_bytes());
+            bytes.extend_from_slice(&max_age.subsec_nanoseconds().to_be_bytes());
+        }
+    }
+
+    // The domain is written as stored, including any leading `.`.
+    let domain = cookie.domain.as_ref().map(|d| d.to_str(cookie.cookie_string.as_ref()));
+    opt_string(bytes, domain)?;
+    opt_string(bytes, cookie.path())?;
+    flag(bytes, cookie.secure());
+    flag(bytes, cookie.http_only());
+    bytes.push(match cookie.same_site() {
+        None => 0,
+        Some(SameSite::Strict) => 1,
+        Some(SameSite::Lax) => 2,
+        Some(SameSite::None) => 3,
+    });
+
+    flag(bytes, cookie.partitioned());
+    len(bytes, cookie.extensions().count())?;
+    for (name, value) in cookie.extensions() {
+        string(bytes, name)?;
+        opt_string(bytes, value)?;
+    }
+
+    Ok(())
+}
+
+/// Reads one cookie written by `write_sealed_cookie()` from the front of
+/// `bytes`, advancing `bytes` past it.
+#[cfg(feature = "private")]
+fn read_sealed_cookie(bytes: &mut &[u8]) -> Result<Cookie<'static>, OpenError> {
+    use time::{Duration, OffsetDateTime, UtcOffset};
+    use crate::{Expiration, SameSite};
+    use OpenError::Malformed;
+
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], OpenError> {
+        if bytes.len() < n {
+            return Err(Malformed);
+        }
+
+        let (taken, rest) = bytes.split_at(n);
+        *bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], OpenError> {
+        let mut array = [0; N];
+        array.copy_from_slice(take(bytes, N)?);
+        Ok(array)
+    }
+
+    fn tag(bytes: &mut &[u8]) -> Result<u8, OpenError> {
+        Ok(take(bytes, 1)?[0])
+    }
+
+    fn len(bytes: &mut &[u8]) -> Result<usize, OpenError> {
+        Ok(u32::from_be_bytes(array(bytes)?) as usize)
+    }
+
+    fn string(bytes: &mut &[u8]) -> Result<String, OpenError> {
+        let len = len(bytes)?;
+        let string = std::str::from_utf8(take(bytes, len)?).map_err(|_| Malformed)?;
+        Ok(string.to_string())
+    }
+
+    fn opt_string(bytes: &mut &[u8]) -> Result<Option<String>, OpenError> {
+        match tag(bytes)? {
+            0 => Ok(None),
+            1 => string(bytes).map(Some),
+            _ => Err(Malformed),
+        }
+    }
+
+    fn flag(bytes: &mut &[u8]) -> Result<Option<bool>, OpenError> {
+        match tag(bytes)? {
+            0 => Ok(None),
+            1 => Ok(Some(false)),
+            2 => Ok(Some(true)),
+            _ => Err(Malformed),
+        }
+    }
+
+    let name = string(bytes)?;
+    let value = string(bytes)?;
+    let mut cookie = Cookie::new(name, value);
+    match tag(bytes)? {
+        0 => {}
+        1 => cookie.set_expires(Expiration::Session),
+        2 => {
+            let nanos = i128::from_be_bytes(array(bytes)?);
+            let offset = i32::from_be_bytes(array(bytes)?);
+            let time = OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| Malformed)?;
+            let offset = UtcOffset::from_whole_seconds(offset).map_err(|_| Malformed)?;
+            cookie.set_expires(Expiration::DateTime(time.to_offset(offset)));
+        }
+        _ => return Err(Malformed),
+    }
+
+    match tag(bytes)? {
+        0 => {}
+        1 => {
+            let seconds = i64::from_be_bytes(array(bytes)?);
+            let nanos = i32::from_be_bytes(array(bytes)?);
+            if !(0..1_000_000_000).contains(&nanos) {
+                return Err(Malformed);
+            }
+
+            cookie.set_max_age(Duration::new(seconds, nanos));
+        }
+        _ => return Err(Malformed),
+    }
+
+    if let Some(domain) = opt_string(bytes)? {
+        cookie.set_domain(domain);
+    }
+
+    if let Some(path) = opt_string(bytes)? {
+        cookie.set_path(path);
+    }
+
+    cookie.set_secure(flag(bytes)?);
+    cookie.set_http_only(flag(bytes)?);
+    cookie.set_same_site(match tag(bytes)? {
+        0 => None,
+        1 => Some(SameSite::Strict),
+        2 => Some(SameSite::Lax),
+        3 => Some(SameSite::None),
+        _ => return Err(Malformed),
+    });
+
+    cookie.set_partitioned(flag(bytes)?);
+    for _ in 0..len(bytes)? {
+        let name = string(bytes)?;
+        let value = opt_string(bytes)?;
+        cookie.add_extension(name, value).map_err(|_| Malformed)?;
+    }
+
+    Ok(cookie)
+}
+
+/// An error returned by [`CookieJar::seal_jar()`].
+#[cfg(feature = "private")]
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum SealError {
+    /// A cookie's name, value, or attribute is longer than `u32::MAX` bytes,
+    /// or it has more than `u32::MAX` extensions.
+    TooLong,
+}
+
+#[cfg(feature = "private")]
+impl fmt::Display for SealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SealError::TooLong => write!(f, "cookie field is too long to be sealed"),
+        }
+    }
+}
+
+#[cfg(feature = "private")]
+impl std::error::Error for SealError { }
+
+/// An error returned by [`CookieJar::open_jar()`].
+#[cfg(feature = "private")]
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum OpenError {
+    /// The blob is not valid base64, was not sealed with the given key, or
+    /// was tampered with.
+    Unauthentic,
+    /// The blob was authenticated but does not contain a serialized jar.
+    Malformed,
+}
+
+#[cfg(feature = "private")]
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            OpenError::Unauthentic => write!(f, "sealed jar failed to authenticate"),
+            OpenError::Malformed => write!(f, "sealed jar is malformed"),
+        }
+    }
+}
+
+#[cfg(feature = "private")]
+impl std::error::Error for OpenError { }
+
+/// A snapshot of the delta state and metadata of a [`CookieJar`].
+///
+/// A value of this type can be obtained via [`CookieJar::checkpoint()`] and
+/// restored via [`CookieJar::restore()`].
+#[derive(Debug, Clone)]
+pub struct Checkpoint<M = ()> {
+    delta_cookies: HashSet<DeltaCookie>,
+    order: AddOrder,
+    meta: HashMap<String, M>,
+}
+
+/// The order in which cookies were added to a [`CookieJar`], used to evict
+/// the least-recently added cookie when the jar exceeds its limit.
+#[derive(Default, Debug, Clone)]
+struct AddOrder {
+    /// The rank of each cookie, by name. More recently added cookies have
+    /// higher ranks.
+    ranks: HashMap<String, i64>,
+    /// The name of each cookie, by rank.
+    names: BTreeMap<i64, String>,
+    /// The lowest and highest ranks handed out so far.
+    bounds: (i64, i64),
+}
+
+impl AddOrder {
+    fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.ranks.contains_key(name)
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.names.values().map(|name| name.as_str())
+    }
+
+    /// Tracks `name` as the least-recently added cookie.
+    fn push_front(&mut self, name: &str) {
+        self.bounds.0 -= 1;
+        self.insert(name, self.bounds.0);
+    }
+
+    /// Tracks `name` as the most-recently added cookie.
+    fn push_back(&mut self, name: &str) {
+        self.bounds.1 += 1;
+        self.insert(name, self.bounds.1);
+    }
+
+    fn insert(&mut self, name: &str, rank: i64) {
+        self.remove(name);
+        self.ranks.insert(name.to_string(), rank);
+        self.names.insert(rank, name.to_string());
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some(rank) = self.ranks.remove(name) {
+            self.names.remove(&rank);
+        }
+    }
+
+    /// Stops tracking and returns the least-recently added cookie's name.
+    fn pop_front(&mut self) -> Option<String> {
+        let rank = *self.names.keys().next()?;
+        let name = self.names.remove(&rank)?;
+        self.ranks.remove(&name);
+        Some(name)
+    }
+}
+
+use std::collections::hash_set::Iter as HashSetIter;
+
+/// Iterator over the changes to a cookie jar.
 pub struct Delta<'a> {
     iter: HashSetIter<'a, DeltaCookie>,
 }
@@ -614,19 +2458,15 @@ impl<'a> Iterator for Iter<'a> {
     type Item = &'a Cookie<'static>;
 
     fn next(&mut self) -> Option<&'a Cookie<'static>> {
-        for cookie in self.delta_cookies.by_ref() {
-            if !cookie.removed {
-                return Some(&*cookie);
-            }
-        }
-
-        None
+        self.delta_cookies.by_ref()
+            .find(|cookie| !cookie.removed)
+            .map(|cookie| &cookie.cookie)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::CookieJar;
+    use super::{AddError, CookieJar};
     use crate::Cookie;
 
     #[test]
@@ -650,6 +2490,874 @@ mod test {
         assert!(c.get("test3").is_none());
     }
 
+    #[test]
+    fn names() {
+        use std::collections::HashSet;
+
+        let mut c = CookieJar::new();
+        assert_eq!(c.names().count(), 0);
+
+        c.add_original(("original", "1"));
+        c.add_original(("replaced", "2"));
+        c.add(("replaced", "3"));
+        c.add(("new", "4"));
+        c.add(("removed", "5"));
+        c.remove("removed");
+        c.remove("original");
+
+        let names: HashSet<_> = c.names().collect();
+        let expected: HashSet<_> = c.iter().map(|c| c.name()).collect();
+        assert_eq!(names, expected);
+        assert_eq!(names, ["replaced", "new"].iter().cloned().collect());
+        assert_eq!(c.names().count(), 2);
+    }
+
+    #[test]
+    fn delta_changed_only() {
+        use crate::SameSite;
+
+        let mut c = CookieJar::new();
+        c.add_original(Cookie::build(("same", "1")).path("/").secure(true));
+        c.add_original(("value", "2"));
+        c.add_original(Cookie::build(("attr", "3")).same_site(SameSite::Lax));
+        c.add_original(("removed", "4"));
+
+        c.add(Cookie::build(("same", "1")).path("/").secure(true));
+        c.add(("value", "two"));
+        c.add(Cookie::build(("attr", "3")).same_site(SameSite::Strict));
+        c.add(("new", "5"));
+        c.remove("removed");
+        assert_eq!(c.delta().count(), 5);
+
+        let mut names: Vec<_> = c.delta_changed_only().map(|c| c.name()).collect();
+        names.sort();
+        assert_eq!(names, ["attr", "new", "removed", "value"]);
+
+        c.add(Cookie::build(("same", "1")).path("/foo").secure(true));
+        assert_eq!(c.delta_changed_only().count(), 5);
+    }
+
+    #[test]
+    fn from_deltas() {
+        let cookies = vec![("a", "1"), ("b", "2"), ("c", "3")];
+        let c: CookieJar = CookieJar::from_deltas(cookies.clone());
+        assert_eq!(c.delta().count(), cookies.len());
+        assert_eq!(c.iter().count(), cookies.len());
+        assert_eq!(c.get("b").map(|c| c.value()), Some("2"));
+
+        let c: CookieJar = CookieJar::from_deltas(Vec::<Cookie<'static>>::new());
+        assert_eq!(c.delta().count(), 0);
+
+        let mut c: CookieJar = CookieJar::from_deltas(vec![("a", "1"), ("a", "2")]);
+        assert_eq!(c.delta().count(), 1);
+        assert_eq!(c.get("a").map(|c| c.value()), Some("2"));
+
+        c.remove("a");
+        assert_eq!(c.delta().count(), 0);
+    }
+
+    #[test]
+    fn limit() {
+        let mut c: CookieJar = CookieJar::with_limit(3);
+        c.add(("a", "1"));
+        c.add(("b", "2"));
+        c.add(("c", "3"));
+        assert_eq!(c.iter().count(), 3);
+
+        c.add(("d", "4"));
+        assert_eq!(c.iter().count(), 3);
+        assert!(c.get("a").is_none());
+        assert!(c.get("b").is_some());
+
+        // Re-adding a cookie makes it the most recently added.
+        c.add(("b", "two"));
+        c.add(("e", "5"));
+        assert!(c.get("c").is_none());
+        assert_eq!(c.get("b").map(|c| c.value()), Some("two"));
+
+        c.add_original(("f", "6"));
+        assert!(c.get("d").is_none());
+        let mut names: Vec<_> = c.names().collect();
+        names.sort();
+        assert_eq!(names, ["b", "e", "f"]);
+
+        // Removal cookies are never evicted.
+        c.remove("f");
+        c.add(("g", "7"));
+        c.add(("h", "8"));
+        assert_eq!(c.iter().count(), 3);
+        assert!(c.delta().any(|c| c.name() == "f"));
+        assert!(c.get("b").is_none());
+
+        c.set_limit(1);
+        assert_eq!(c.names().collect::<Vec<_>>(), ["h"]);
+
+        c.set_limit(None);
+        c.add(("i", "9"));
+        c.add(("j", "10"));
+        assert_eq!(c.iter().count(), 3);
+
+        let mut c = CookieJar::new();
+        c.add_original(("a", "1"));
+        c.add(("b", "2"));
+        c.set_limit(2);
+        c.add(("c", "3"));
+        assert_eq!(c.iter().count(), 2);
+        assert!(c.get("c").is_some());
+
+        let mut c: CookieJar = CookieJar::with_limit(0);
+        c.add(("a", "1"));
+        assert_eq!(c.iter().count(), 0);
+        assert_eq!(c.delta().count(), 0);
+
+        // Evicting an original cookie removes it from the client as well.
+        let mut c = CookieJar::new();
+        c.add_original(("original", "1"));
+        c.set_limit(1);
+        c.add(("new", "2"));
+        assert!(c.get("original").is_none());
+        let mut delta: Vec<_> = c.delta().map(|c| c.name_value()).collect();
+        delta.sort();
+        assert_eq!(delta, [("new", "2"), ("original", "")]);
+
+        // Changes that bypass `add` and `remove` keep the count accurate.
+        let mut c: CookieJar = CookieJar::with_limit(2);
+        c.add_original(("a", "1"));
+        c.add(("b", "2"));
+        c.reset_delta();
+        c.add(("c", "3"));
+        assert_eq!(c.iter().count(), 2);
+        c.add(("d", "4"));
+        let mut names: Vec<_> = c.names().collect();
+        names.sort();
+        assert_eq!(names, ["c", "d"]);
+
+        let checkpoint = c.checkpoint();
+        c.add(("e", "5"));
+        c.restore(checkpoint);
+        c.add(("f", "6"));
+        let mut names: Vec<_> = c.names().collect();
+        names.sort();
+        assert_eq!(names, ["d", "f"]);
+    }
+
+    #[test]
+    fn headers() {
+        let mut c = CookieJar::new();
+        let (request, response) = c.headers();
+        assert!(request.is_empty());
+        assert!(response.is_empty());
+
+        c.add_original(("original", "1"));
+        c.add_original(("gone", "2"));
+        c.add(Cookie::build(("added", "3")).secure(true));
+        c.remove(Cookie::build("gone").path("/"));
+
+        let (request, mut response) = c.headers();
+        let mut pairs: Vec<_> = request.split("; ").collect();
+        pairs.sort();
+        assert_eq!(pairs, ["added=3", "original=1"]);
+
+        response.sort();
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0], "added=3; Secure");
+        assert!(response[1].starts_with("gone=; Path=/; Max-Age=0; Expires="));
+    }
+
+    #[test]
+    fn checkpoint_restore() {
+        let mut c = CookieJar::new();
+        c.add_original(("original", "1"));
+        c.add(("added", "2"));
+
+        let checkpoint = c.checkpoint();
+        c.add(("tentative", "3"));
+        c.add(("added", "changed"));
+        c.remove("original");
+        assert_eq!(c.delta().count(), 3);
+
+        c.restore(checkpoint.clone());
+        assert_eq!(c.delta().count(), 1);
+        assert_eq!(c.get("added").unwrap().value(), "2");
+        assert_eq!(c.get("original").unwrap().value(), "1");
+        assert!(c.get("tentative").is_none());
+
+        // A checkpoint can be restored more than once.
+        c.reset_delta();
+        c.restore(checkpoint);
+        assert_eq!(c.delta().count(), 1);
+        assert_eq!(c.get("added").unwrap().value(), "2");
+    }
+
+    #[test]
+    fn max_cookie_size() {
+        let mut c = CookieJar::new();
+        let big = "x".repeat(4096);
+        assert!(c.try_add(("big", big.clone())).is_ok());
+
+        c.set_max_cookie_size(4096);
+        assert!(c.try_add(("normal", "value")).is_ok());
+        assert_eq!(c.try_add(("huge", big.clone())),
+            Err(AddError::TooLarge { size: 4096 + 5, limit: 4096 }));
+        assert!(c.get("huge").is_none());
+
+        // Attributes count towards the size.
+        let exact = "x".repeat(4096 - "fits=".len());
+        assert!(c.try_add(("fits", exact.clone())).is_ok());
+        assert!(c.try_add(Cookie::build(("fits", exact)).path("/")).is_err());
+        assert_eq!(c.get("fits").unwrap().path(), None);
+
+        // `add` isn't subject to the policy.
+        c.add(("huge", big));
+        assert!(c.get("huge").is_some());
+    }
+
+    #[test]
+    fn clear_session_cookies() {
+        use time::{Duration, OffsetDateTime};
+
+        let mut c = CookieJar::new();
+        let tomorrow = OffsetDateTime::now_utc() + Duration::days(1);
+        c.add_original(("session", "1"));
+        c.add_original(Cookie::build(("max_age", "2")).max_age(Duration::hours(1)));
+        c.add_original(Cookie::build(("expires", "3")).expires(tomorrow));
+        c.add(("new_session", "4"));
+        c.add(Cookie::build(("new_persistent", "5")).max_age(Duration::hours(1)));
+
+        // A persistent cookie replacing an original session cookie is kept.
+        c.add_original(("replaced", "6"));
+        c.add(Cookie::build(("replaced", "7")).max_age(Duration::hours(1)));
+
+        c.clear_session_cookies();
+        let mut names: Vec<_> = c.names().collect();
+        names.sort();
+        assert_eq!(names, ["expires", "max_age", "new_persistent", "replaced"]);
+        assert_eq!(c.get("replaced").unwrap().value(), "7");
+
+        // No removal cookies were generated.
+        assert_eq!(c.delta().count(), 2);
+        assert!(c.delta().all(|c| c.max_age() == Some(Duration::hours(1))));
+    }
+
+    #[test]
+    fn cache_key() {
+        let mut a = CookieJar::new();
+        a.add(("b", "2"));
+        a.add(("a", "1"));
+        a.add(("ignored", "x"));
+        a.add_original(("c", "3"));
+
+        let mut b = CookieJar::new();
+        b.add_original(("ignored", "y"));
+        b.add(("c", "3"));
+        b.add_original(("a", "1"));
+        b.add(("b", "2"));
+
+        let key = a.cache_key(&["c", "a", "b", "missing"]);
+        assert_eq!(key, "1:a=1:1; 1:b=1:2; 1:c=1:3");
+        assert_eq!(key, b.cache_key(&["a", "b", "c", "c"]));
+        assert_eq!(a.cache_key(&["b"]), "1:b=1:2");
+        assert_eq!(a.cache_key(&[]), "");
+
+        b.add(("b", "changed"));
+        assert_ne!(key, b.cache_key(&["a", "b", "c"]));
+        b.remove("b");
+        assert_eq!(b.cache_key(&["a", "b", "c"]), "1:a=1:1; 1:c=1:3");
+
+        // Values containing separators can't collide with separate cookies.
+        let mut c = CookieJar::new();
+        c.add(("a", "1; b=2"));
+        let mut d = CookieJar::new();
+        d.add(("a", "1"));
+        d.add(("b", "2"));
+        assert_ne!(c.cache_key(&["a", "b"]), d.cache_key(&["a", "b"]));
+        assert_eq!(c.cache_key(&["a", "b"]), "1:a=6:1; b=2");
+    }
+
+    #[test]
+    fn to_vec_into_vec() {
+        let mut c = CookieJar::new();
+        assert!(c.to_vec().is_empty());
+
+        c.add_original(("original", "1"));
+        c.add_original(("replaced", "2"));
+        c.add_original(("removed", "3"));
+        c.add(("replaced", "two"));
+        c.add(("new", "4"));
+        c.remove("removed");
+
+        let expected: Vec<_> = c.iter().cloned().collect();
+        assert_eq!(c.to_vec(), expected);
+
+        let mut expected = expected;
+        let mut cookies = c.into_vec();
+        cookies.sort_by(|a, b| a.name().cmp(b.name()));
+        expected.sort_by(|a, b| a.name().cmp(b.name()));
+        assert_eq!(cookies, expected);
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies[2].name_value(), ("replaced", "two"));
+    }
+
+    #[test]
+    fn is_dirty() {
+        let mut c = CookieJar::new();
+        assert!(!c.is_dirty());
+
+        c.add_original(Cookie::new("original", "a"));
+        c.add_original(Cookie::new("other", "b"));
+        assert!(!c.is_dirty());
+
+        c.add(Cookie::new("new", "c"));
+        assert!(c.is_dirty());
+
+        c.reset_delta();
+        assert!(!c.is_dirty());
+
+        c.remove("original");
+        assert!(c.is_dirty());
+    }
+
+    #[test]
+    fn removal_preserves_attributes() {
+        use crate::SameSite;
+        use time::Duration;
+
+        let mut c = CookieJar::new();
+        c.add_original(Cookie::new("name", "value"));
+        c.remove(Cookie::build("name")
+            .path("/")
+            .domain("rust-lang.org")
+            .secure(true)
+            .http_only(true)
+            .same_site(SameSite::None)
+            .partitioned(true));
+
+        let removal = c.delta().next().unwrap();
+        assert_eq!(removal.value(), "");
+        assert_eq!(removal.max_age(), Some(Duration::ZERO));
+        assert_eq!(removal.path(), Some("/"));
+        assert_eq!(removal.domain(), Some("rust-lang.org"));
+        assert_eq!(removal.secure(), Some(true));
+        assert_eq!(removal.http_only(), Some(true));
+        assert_eq!(removal.same_site(), Some(SameSite::None));
+        assert_eq!(removal.partitioned(), Some(true));
+
+        let string = removal.to_string();
+        assert!(string.contains("; Secure"));
+        assert!(string.contains("; SameSite=None"));
+        assert!(string.contains("; Partitioned"));
+
+        // Attributes that aren't provided aren't invented.
+        c.remove(Cookie::build("name").same_site(SameSite::Lax));
+        let removal = c.delta().next().unwrap();
+        assert_eq!(removal.same_site(), Some(SameSite::Lax));
+        assert_eq!(removal.secure(), None);
+        assert_eq!(removal.partitioned(), None);
+        assert_eq!(removal.path(), None);
+    }
+
+    #[test]
+    fn get_all() {
+        let mut c = CookieJar::new();
+        c.add_original(Cookie::new("original", "a"));
+        c.add_original(Cookie::new("replaced", "b"));
+        c.add_original(Cookie::new("removed", "c"));
+        c.add(Cookie::new("replaced", "B"));
+        c.add(Cookie::new("new", "d"));
+        c.remove("removed");
+
+        for name in &["original", "replaced", "removed", "new", "missing"] {
+            let all: Vec<_> = c.get_all(name).collect();
+            let expected: Vec<_> = c.iter().filter(|c| c.name() == *name).collect();
+            assert_eq!(all, expected);
+            assert_eq!(all.first().copied(), c.get(name));
+        }
+
+        assert_eq!(c.get_all("replaced").next().unwrap().value(), "B");
+        assert_eq!(c.get_all("removed").count(), 0);
+
+        c.reset_delta();
+        assert_eq!(c.get_all("replaced").next().unwrap().value(), "b");
+        assert_eq!(c.get_all("removed").next().unwrap().value(), "c");
+        assert_eq!(c.get_all("new").count(), 0);
+    }
+
+    #[test]
+    fn meta() {
+        #[derive(Debug, PartialEq)]
+        enum Source { Header, OAuth, Refresh }
+
+        let mut c = CookieJar::<Source>::default();
+        c.add_original(Cookie::new("original", "a"));
+        c.add(Cookie::new("new", "b"));
+        c.set_meta("original", Source::Header);
+        c.set_meta("new", Source::OAuth);
+        assert_eq!(c.get_meta("original"), Some(&Source::Header));
+        assert_eq!(c.get_meta("new"), Some(&Source::OAuth));
+        assert_eq!(c.get_meta("missing"), None);
+
+        // Metadata isn't part of the delta.
+        assert_eq!(c.delta().count(), 1);
+
+        c.set_meta("new", Source::Refresh);
+        assert_eq!(c.get_meta("new"), Some(&Source::Refresh));
+
+        c.force_remove("original");
+        assert_eq!(c.get_meta("original"), None);
+        assert_eq!(c.get_meta("new"), Some(&Source::Refresh));
+
+        c.force_remove("new");
+        assert_eq!(c.get_meta("new"), None);
+
+        // Metadata comes back with cookies brought back by a restore or reset.
+        let mut c = CookieJar::<&str>::default();
+        c.add_original(("original", "a"));
+        c.add(("new", "b"));
+        c.set_meta("original", "original meta");
+        c.set_meta("new", "new meta");
+
+        let checkpoint = c.checkpoint();
+        c.remove("original");
+        c.remove("new");
+        c.set_meta("later", "later meta");
+        assert!(c.get("original").is_none());
+        assert_eq!(c.get_meta("new"), None);
+
+        c.restore(checkpoint);
+        assert!(c.get("original").is_some() && c.get("new").is_some());
+        assert_eq!(c.get_meta("original"), Some(&"original meta"));
+        assert_eq!(c.get_meta("new"), Some(&"new meta"));
+        assert_eq!(c.get_meta("later"), None);
+
+        c.remove("original");
+        c.reset_delta();
+        assert!(c.get("original").is_some() && c.get("new").is_none());
+        assert_eq!(c.get_meta("original"), Some(&"original meta"));
+        assert_eq!(c.get_meta("new"), None);
+
+        // Eviction removes metadata along with the cookie.
+        let mut c = CookieJar::<&str>::with_limit(1);
+        c.add(Cookie::new("first", "a"));
+        c.set_meta("first", "meta");
+        c.add(Cookie::new("second", "b"));
+        assert!(c.get("first").is_none());
+        assert_eq!(c.get_meta("first"), None);
+
+        // Child jars are available regardless of the metadata type.
+        c.prefixed_mut(crate::prefix::Secure).add(("name", "value"));
+        assert!(c.prefixed(crate::prefix::Secure).get("name").is_some());
+    }
+
+    #[test]
+    fn seed_originals() {
+        let mut c = CookieJar::new();
+        c.add(("added", "1"));
+        c.seed_originals(vec![Cookie::new("a", "1"), Cookie::new("b", "2")]);
+        c.seed_originals(Some(("added", "0")));
+
+        assert_eq!(c.iter().count(), 3);
+        assert_eq!(c.get("a").map(|c| c.value()), Some("1"));
+        assert_eq!(c.get("added").map(|c| c.value()), Some("1"));
+        assert_eq!(c.delta().map(|c| c.name()).collect::<Vec<_>>(), ["added"]);
+
+        let mut c = CookieJar::new();
+        c.seed_originals(vec![("a", "1"), ("b", "2")]);
+        assert_eq!(c.iter().count(), 2);
+        assert_eq!(c.delta().count(), 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "after `CookieJar::remove()`")]
+    fn seed_originals_after_remove() {
+        let mut c = CookieJar::new();
+        c.add_original(("a", "1"));
+        c.remove("a");
+        c.seed_originals(Some(("b", "2")));
+    }
+
+    #[test]
+    fn merge() {
+        let mut a = CookieJar::<&str>::default();
+        a.add_original(("original", "a"));
+        a.add_original(("shadowed", "a"));
+        a.add_original(("removed", "a"));
+        a.add(("shadowed", "a-new"));
+        a.add(("added", "a"));
+
+        let mut b = CookieJar::default();
+        b.add_original(("original", "b"));
+        b.add_original(("shadowed", "b"));
+        b.add_original(("b_removed", "b"));
+        b.add_original(("removed", "b"));
+        b.add(("added", "b"));
+        b.add(("b_added", "b"));
+        b.remove("b_removed");
+        b.remove("removed");
+        b.remove("unknown");
+        b.set_meta("added", "from b");
+
+        a.merge(b);
+        let value = |jar: &CookieJar<&str>, name| jar.get(name).map(|c| c.value().to_string());
+        assert_eq!(value(&a, "original").as_deref(), Some("b"));
+        assert_eq!(value(&a, "shadowed").as_deref(), Some("a-new"));
+        assert_eq!(value(&a, "added").as_deref(), Some("b"));
+        assert_eq!(value(&a, "b_added").as_deref(), Some("b"));
+        assert!(a.get("removed").is_none());
+        assert!(a.get("b_removed").is_none());
+        assert_eq!(a.get_meta("added"), Some(&"from b"));
+        assert_eq!(a.iter().count(), 4);
+
+        let mut delta: Vec<_> = a.delta().map(|c| (c.name(), c.value())).collect();
+        delta.sort();
+        assert_eq!(delta, [
+            ("added", "b"), ("b_added", "b"), ("b_removed", ""),
+            ("removed", ""), ("shadowed", "a-new"),
+        ]);
+
+        // Removing a cookie that isn't an original in either jar is a no-op.
+        let mut a = CookieJar::new();
+        a.add(("x", "1"));
+        let mut b = CookieJar::new();
+        b.add(("x", "2"));
+        b.remove("x");
+        a.merge(b);
+        assert_eq!(a.get("x").map(|c| c.value()), Some("1"));
+        assert_eq!(a.delta().count(), 1);
+
+        let mut a = CookieJar::new();
+        a.add(("x", "1"));
+        let mut b = CookieJar::new();
+        b.add_original(("x", "2"));
+        b.remove("x");
+        a.merge(b);
+        assert!(a.get("x").is_none());
+        assert_eq!(a.delta().count(), 1);
+    }
+
+    #[test]
+    fn delta_lines_are_separate() {
+        use time::macros::datetime;
+
+        let mut c = CookieJar::new();
+        c.add_original(("removed", "1"));
+        c.add(Cookie::build(("a", "1")).expires(datetime!(2015-10-21 7:28 UTC)));
+        c.add(("b", "2"));
+        c.remove("removed");
+
+        // Each delta cookie is its own line, which is its own header value.
+        let lines: Vec<_> = c.delta_lines().collect();
+        let delta: Vec<_> = c.delta().map(|c| c.to_string()).collect();
+        assert_eq!(lines, delta);
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            let cookie = Cookie::parse(line.as_str()).unwrap();
+            assert_eq!(c.delta().filter(|c| c.name() == cookie.name()).count(), 1);
+            assert!(!line.contains('\n'));
+        }
+
+        // Joining the lines yields a single cookie: the first. `Expires` has a
+        // `,`, so there's no way to split the joined value back up.
+        let joined = lines.join(", ");
+        let first = Cookie::parse(lines[0].as_str()).unwrap();
+        assert_eq!(Cookie::parse(joined.as_str()).unwrap().name(), first.name());
+    }
+
+    #[test]
+    fn remove_all() {
+        use time::Duration;
+
+        let mut c = CookieJar::<&str>::default();
+        c.add_original(Cookie::build(("original", "1")).path("/foo").domain("example.com"));
+        c.add_original(("replaced", "2"));
+        c.add_original(("already_removed", "3"));
+        c.add(Cookie::build(("replaced", "2-new")).path("/bar"));
+        c.add(("added", "4"));
+        c.remove("already_removed");
+        c.set_meta("original", "meta");
+
+        c.remove_all();
+        assert_eq!(c.iter().count(), 0);
+        assert_eq!(c.get_meta("original"), Some(&"meta"));
+
+        let mut delta: Vec<_> = c.delta().collect();
+        delta.sort_by_key(|c| c.name());
+        let names: Vec<_> = delta.iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["already_removed", "original", "replaced"]);
+        for cookie in &delta {
+            assert_eq!(cookie.value(), "");
+            assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+        }
+
+        assert_eq!(delta[1].path(), Some("/foo"));
+        assert_eq!(delta[1].domain(), Some("example.com"));
+        assert_eq!(delta[2].path(), Some("/bar"));
+
+        // A jar with only added cookies has an empty delta afterwards.
+        let mut c = CookieJar::new();
+        c.add(("a", "1"));
+        c.add(("b", "2"));
+        c.remove_all();
+        assert_eq!(c.iter().count(), 0);
+        assert_eq!(c.delta().count(), 0);
+    }
+
+    #[test]
+    fn iter_sorted() {
+        let mut c = CookieJar::new();
+        for name in ["d", "b", "e", "a", "c"].iter() {
+            c.add_original((*name, "original"));
+        }
+
+        c.add(("f", "added"));
+        c.add(("a", "replaced"));
+        c.remove("e");
+
+        let cookies: Vec<_> = c.iter_sorted().map(|c| c.name_value()).collect();
+        assert_eq!(cookies, [
+            ("a", "replaced"), ("b", "original"), ("c", "original"),
+            ("d", "original"), ("f", "added"),
+        ]);
+
+        // The order is stable across calls and matches `iter()`'s contents.
+        let again: Vec<_> = c.iter_sorted().map(|c| c.name_value()).collect();
+        assert_eq!(cookies, again);
+        assert_eq!(c.iter_sorted().count(), c.iter().count());
+        assert_eq!(CookieJar::new().iter_sorted().count(), 0);
+    }
+
+    #[test]
+    fn minimize_delta() {
+        let template = |c: &mut Cookie<'static>| {
+            c.set_path("/");
+            c.set_secure(true);
+            c.set_http_only(true);
+        };
+
+        let mut c = CookieJar::new();
+        for (name, value) in [("a", "1"), ("b", "2"), ("c", "3")].iter() {
+            let mut cookie = Cookie::new(*name, *value);
+            template(&mut cookie);
+            c.add_original(cookie);
+        }
+
+        // Applying the template to every cookie changes nothing.
+        for mut cookie in c.to_vec() {
+            template(&mut cookie);
+            c.add(cookie);
+        }
+
+        assert_eq!(c.delta().count(), 3);
+        c.minimize_delta();
+        assert_eq!(c.delta().count(), 0);
+        assert_eq!(c.iter().count(), 3);
+        assert_eq!(c.get("a").unwrap().path(), Some("/"));
+
+        // Changed cookies, new cookies, and removals are kept.
+        let mut changed = c.get("a").cloned().unwrap();
+        changed.set_http_only(false);
+        c.add(changed);
+        c.add(("d", "4"));
+        c.remove("b");
+        let same = c.get("c").cloned().unwrap();
+        c.add(same);
+
+        c.minimize_delta();
+        let mut delta: Vec<_> = c.delta().map(|c| c.name()).collect();
+        delta.sort();
+        assert_eq!(delta, ["a", "b", "d"]);
+        assert!(c.get("b").is_none());
+    }
+
+    #[test]
+    fn extend_originals_from_header() {
+        let mut c = CookieJar::new();
+        c.add_original(("seeded", "0"));
+        c.add(("added", "0"));
+
+        c.extend_originals_from_header("a=1; shared=first; =invalid; b=2");
+        c.extend_originals_from_header("shared=second;c=3; added=original ");
+
+        let mut cookies: Vec<_> = c.iter().map(|c| c.name_value()).collect();
+        cookies.sort();
+        assert_eq!(cookies, [
+            ("a", "1"), ("added", "0"), ("b", "2"), ("c", "3"),
+            ("seeded", "0"), ("shared", "second"),
+        ]);
+
+        let delta: Vec<_> = c.delta().map(|c| c.name_value()).collect();
+        assert_eq!(delta, [("added", "0")]);
+
+        c.remove("added");
+        assert_eq!(c.delta().next().map(|c| c.value()), Some(""));
+
+        let mut c = CookieJar::new();
+        c.extend_originals_from_header("");
+        c.extend_originals_from_header(";;");
+        assert_eq!(c.iter().count(), 0);
+    }
+
+    #[test]
+    fn seeding() {
+        let mut jar = CookieJar::new();
+        jar.add_original(("original", "value"));
+        jar.add(("new", "value"));
+
+        jar.seeding(true);
+        jar.add(("stored", "value"));
+        jar.add(("original", "replaced"));
+        jar.add(("new", "ignored"));
+        assert!(jar.try_add(("tried", "value")).is_ok());
+        assert_eq!(jar.delta().count(), 1);
+        assert_eq!(jar.get("stored").map(|c| c.value()), Some("value"));
+        assert_eq!(jar.get("original").map(|c| c.value()), Some("replaced"));
+        assert_eq!(jar.get("new").map(|c| c.value()), Some("value"));
+        assert!(jar.get("tried").is_some());
+
+        // Removal is unaffected by seeding mode.
+        jar.remove("stored");
+        assert!(jar.get("stored").is_none());
+        assert_eq!(jar.delta().count(), 2);
+
+        jar.seeding(false);
+        assert!(!jar.is_seeding());
+        jar.add(("later", "value"));
+        assert_eq!(jar.delta().count(), 3);
+        assert_eq!(jar.delta().filter(|c| c.name() == "tried").count(), 0);
+    }
+
+    #[test]
+    fn delta_additions_removals() {
+        let mut jar = CookieJar::new();
+        assert_eq!(jar.delta_additions().count(), 0);
+        assert_eq!(jar.delta_removals().count(), 0);
+
+        jar.add_original(("original", "1"));
+        jar.add_original(("replaced", "2"));
+        jar.add_original(("removed", "3"));
+        jar.add(("replaced", "two"));
+        jar.add(("new", "4"));
+        jar.add(("fleeting", "5"));
+        jar.remove("removed");
+        jar.remove("fleeting");
+
+        let mut additions: Vec<_> = jar.delta_additions().map(|c| c.name_value()).collect();
+        additions.sort();
+        assert_eq!(additions, [("new", "4"), ("replaced", "two")]);
+
+        let removals: Vec<_> = jar.delta_removals().map(|c| c.name()).collect();
+        assert_eq!(removals, ["removed"]);
+        assert_eq!(jar.delta().count(), additions.len() + removals.len());
+
+        // Removing an added cookie with an original turns it into a removal.
+        jar.remove("replaced");
+        assert_eq!(jar.delta_additions().count(), 1);
+        assert_eq!(jar.delta_removals().count(), 2);
+    }
+
+    #[test]
+    fn clear_all_removals() {
+        let mut jar = CookieJar::new();
+        assert!(jar.clear_all_removals().is_empty());
+
+        jar.add_original(Cookie::build(("root", "1")).path("/"));
+        jar.add_original(Cookie::build(("docs", "2")).path("/docs").domain("rust-lang.org"));
+        jar.add_original(Cookie::build(("moved", "3")).path("/old"));
+        jar.add_original(Cookie::build(("same", "4")).path("/"));
+        jar.add_original(("gone", "5"));
+        jar.add(Cookie::build(("moved", "three")).path("/new"));
+        jar.add(Cookie::build(("same", "four")).path("/"));
+        jar.add(Cookie::build(("new", "6")).path("/api"));
+        jar.remove("gone");
+
+        let mut removals: Vec<_> = jar.clear_all_removals().into_iter()
+            .map(|c| {
+                assert_eq!(c.value(), "");
+                assert_eq!(c.max_age(), Some(time::Duration::ZERO));
+                (c.name().to_string(), c.path().map(String::from), c.domain().map(String::from))
+            })
+            .collect();
+
+        removals.sort();
+        let path = |p: &str| Some(p.to_string());
+        assert_eq!(removals, [
+            ("docs".to_string(), path("/docs"), Some("rust-lang.org".to_string())),
+            ("moved".to_string(), path("/new"), None),
+            ("moved".to_string(), path("/old"), None),
+            ("new".to_string(), path("/api"), None),
+            ("root".to_string(), path("/"), None),
+            ("same".to_string(), path("/"), None),
+        ]);
+
+        assert_eq!(jar.iter().count(), 5);
+        assert_eq!(jar.delta().count(), 4);
+    }
+
+    #[test]
+    fn contains() {
+        let mut jar = CookieJar::new();
+        assert!(!jar.contains("original"));
+        assert!(!jar.contains_original("original"));
+
+        jar.add_original(("original", "value"));
+        jar.add_original(("replaced", "value"));
+        jar.add(("replaced", "new"));
+        jar.add(("new", "value"));
+
+        for name in ["original", "replaced", "new"] {
+            assert!(jar.contains(name));
+            assert_eq!(jar.contains(name), jar.get(name).is_some());
+        }
+
+        assert!(jar.contains_original("original"));
+        assert!(jar.contains_original("replaced"));
+        assert!(!jar.contains_original("new"));
+
+        jar.remove("original");
+        jar.remove("new");
+        assert!(!jar.contains("original"));
+        assert!(jar.contains_original("original"));
+        assert!(!jar.contains("new"));
+        assert!(!jar.contains_original("new"));
+
+        jar.reset_delta();
+        assert!(jar.contains("original"));
+    }
+
+    #[test]
+    fn expire() {
+        use time::{Duration, macros::datetime};
+
+        let now = datetime!(2025-06-01 12:00 UTC);
+        let mut jar = CookieJar::new();
+        jar.add_original(Cookie::build(("expired", "1")).expires(now - Duration::days(1)));
+        jar.add_original(Cookie::build(("exact", "2")).expires(now));
+        jar.add_original(Cookie::build(("live", "3")).expires(now + Duration::days(1)));
+        jar.add_original(Cookie::build(("zero", "4")).max_age(Duration::ZERO));
+        jar.add_original(("session", "5"));
+        jar.add(Cookie::build(("new_expired", "6")).expires(now - Duration::hours(1)));
+        jar.add(Cookie::build(("new_live", "7")).max_age(Duration::hours(1)));
+        jar.add(Cookie::build(("revived", "8")).expires(now + Duration::days(1)));
+        jar.add_original(Cookie::build(("revived", "0")).expires(now - Duration::days(1)));
+
+        let mut expired = jar.expire(now);
+        expired.sort();
+        assert_eq!(expired, ["exact", "expired", "new_expired", "zero"]);
+
+        let mut names: Vec<_> = jar.iter().map(|c| c.name()).collect();
+        names.sort();
+        assert_eq!(names, ["live", "new_live", "revived", "session"]);
+        assert_eq!(jar.delta().count(), 2);
+        assert!(jar.delta().all(|c| !c.value().is_empty()));
+
+        assert!(jar.expire(now).is_empty());
+
+        let mut expired = jar.expire(now + Duration::days(2));
+        expired.sort();
+        assert_eq!(expired, ["live", "revived"]);
+        assert_eq!(jar.iter().count(), 2);
+    }
+
     #[test]
     fn jar_is_send() {
         fn is_send<T: Send>(_: T) -> bool {
@@ -61,6 +61,24 @@
 //!   A meta-feature that simultaneously enables `signed`, `private`, and
 //!   `key-expansion`.
 //!
+//! * **`http`**
+//!
+//!   Enables integration with the [`http`](https://docs.rs/http) crate.
+//!
+//!   When this feature is enabled, the `CookieJar::from_header_map()` and
+//!   `CookieJar::write_deltas_to_header_map()` methods are available. They
+//!   create a jar from the `Cookie` headers in an `http::HeaderMap` and write
+//!   the jar's delta to a `HeaderMap` as `Set-Cookie` headers, respectively.
+//!
+//! * **`serde`**
+//!
+//!   Enables serialization via [`serde`](https://docs.rs/serde).
+//!
+//!   When this feature is enabled, `Cookie`, `SameSite`, and `Expiration`
+//!   implement `Serialize` and `Deserialize`. A cookie is serialized as a
+//!   struct of its name, value, and set attributes, making it suitable for
+//!   storage in formats like JSON.
+//!
 //! You can enable features via `Cargo.toml`:
 //!
 //! ```toml
@@ -80,6 +98,9 @@ mod jar;
 mod delta;
 mod same_site;
 mod expiration;
+mod attributes;
+mod path;
+mod store;
 
 /// Implementation of [HTTP RFC6265 draft] cookie prefixes.
 ///
@@ -87,9 +108,16 @@ mod expiration;
 /// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-cookie-name-prefixes
 pub mod prefix;
 
+/// Reading and writing cookies in the Netscape `cookies.txt` format used by
+/// curl and wget.
+pub mod netscape;
+
 #[cfg(any(feature = "private", feature = "signed"))] #[macro_use] mod secure;
 #[cfg(any(feature = "private", feature = "signed"))] pub use secure::*;
 
+#[cfg(feature = "http")] mod http;
+#[cfg(feature = "serde")] mod serde;
+
 use std::borrow::Cow;
 use std::fmt;
 use std::str::FromStr;
@@ -97,14 +125,20 @@ use std::str::FromStr;
 #[allow(unused_imports, deprecated)]
 use std::ascii::AsciiExt;
 
-use time::{Duration, OffsetDateTime, UtcOffset, macros::datetime};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use time::format_description::well_known::Rfc3339;
 
 use crate::parse::parse_cookie;
-pub use crate::parse::ParseError;
+pub use crate::parse::{ParseError, ParseConfig, ValidationError, Warning};
+pub use crate::parse::{is_valid_name, is_valid_value};
 pub use crate::builder::CookieBuilder;
-pub use crate::jar::{CookieJar, Delta, Iter};
+pub use crate::jar::{CookieJar, AddError, Checkpoint, Delta, Iter};
+#[cfg(feature = "private")] pub use crate::jar::{OpenError, SealError};
 pub use crate::same_site::*;
 pub use crate::expiration::*;
+pub use crate::attributes::AttributeSet;
+pub use crate::path::default_path_for;
+pub use crate::store::{BackedJar, CookieStore};
 
 #[derive(Debug, Clone)]
 enum CookieStr<'c> {
@@ -151,7 +185,7 @@ impl<'c> CookieStr<'c> {
                     converting indexed str to str! (This is a module invariant.)");
                 &s[i..j]
             },
-            CookieStr::Concrete(ref cstr) => &*cstr,
+            CookieStr::Concrete(ref cstr) => cstr,
         }
     }
 
@@ -177,6 +211,13 @@ impl<'c> CookieStr<'c> {
             Concrete(Cow::Borrowed(c)) => Concrete(Cow::Owned(c.into())),
         }
     }
+
+    fn reborrow(&self) -> CookieStr<'_> {
+        match *self {
+            CookieStr::Indexed(a, b) => CookieStr::Indexed(a, b),
+            CookieStr::Concrete(ref c) => CookieStr::Concrete(Cow::Borrowed(c)),
+        }
+    }
 }
 
 /// Representation of an HTTP cookie.
@@ -224,6 +265,9 @@ pub struct Cookie<'c> {
     expires: Option<Expiration>,
     /// The cookie's maximum age, if any.
     max_age: Option<Duration>,
+    /// The raw value of the parsed `Max-Age` attribute, if any, until the
+    /// maximum age is changed.
+    max_age_raw: Option<CookieStr<'c>>,
     /// The cookie's domain, if any.
     domain: Option<CookieStr<'c>>,
     /// The cookie's path domain, if any.
@@ -236,6 +280,10 @@ pub struct Cookie<'c> {
     same_site: Option<SameSite>,
     /// The draft `Partitioned` attribute.
     partitioned: Option<bool>,
+    /// Unknown attributes, as `(name, value)` pairs, in order.
+    extensions: Vec<(CookieStr<'c>, Option<CookieStr<'c>>)>,
+    /// Whether the name or value were percent-decoded when parsing.
+    decoded: bool,
 }
 
 impl<'c> Cookie<'c> {
@@ -263,12 +311,15 @@ impl<'c> Cookie<'c> {
             value: CookieStr::Concrete(value.into()),
             expires: None,
             max_age: None,
+            max_age_raw: None,
             domain: None,
             path: None,
             secure: None,
             http_only: None,
             same_site: None,
             partitioned: None,
+            extensions: Vec::new(),
+            decoded: false,
         }
     }
 
@@ -295,6 +346,43 @@ impl<'c> Cookie<'c> {
         Cookie::new(name, "")
     }
 
+    /// Creates a ready-to-send "removal" cookie for the cookie named `name`
+    /// with the given `path` and `domain`. The returned cookie has an empty
+    /// value, a max-age of `0`, and an expiration date in the past. See
+    /// [`Cookie::make_removal()`].
+    ///
+    /// For a client to remove a cookie, the removal cookie's path and domain
+    /// must match those the cookie was originally set with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::time::Duration;
+    ///
+    /// let c = Cookie::clearing("name", Some("/"), None);
+    /// assert_eq!(c.name(), "name");
+    /// assert_eq!(c.value(), "");
+    /// assert_eq!(c.path(), Some("/"));
+    /// assert_eq!(c.domain(), None);
+    /// assert_eq!(c.max_age(), Some(Duration::ZERO));
+    /// ```
+    pub fn clearing<N>(name: N, path: Option<&str>, domain: Option<&str>) -> Cookie<'static>
+        where N: Into<Cow<'static, str>>
+    {
+        let mut cookie = Cookie::new(name, "");
+        if let Some(path) = path {
+            cookie.set_path(path.to_string());
+        }
+
+        if let Some(domain) = domain {
+            cookie.set_domain(domain.to_string());
+        }
+
+        cookie.make_removal();
+        cookie
+    }
+
     /// Creates a new [`CookieBuilder`] starting from a `base` cookie.
     ///
     /// Any type that implements `T: Into<Cookie>` can be used as a `base`:
@@ -331,6 +419,12 @@ impl<'c> Cookie<'c> {
     /// Parses a `Cookie` from the given HTTP cookie header value string. Does
     /// not perform any percent-decoding.
     ///
+    /// Whitespace surrounding the name and the value is trimmed while
+    /// whitespace within them is preserved, as RFC 6265 §5.2 specifies for
+    /// user agents. Thus, `" fo o = bar "` parses as a cookie named `fo o` with
+    /// value `bar`. Names are not otherwise validated; use
+    /// [`is_valid_name()`](crate::is_valid_name()) to reject such names.
+    ///
     /// # Example
     ///
     /// ```
@@ -344,7 +438,7 @@ impl<'c> Cookie<'c> {
     pub fn parse<S>(s: S) -> Result<Cookie<'c>, ParseError>
         where S: Into<Cow<'c, str>>
     {
-        parse_cookie(s.into(), false)
+        parse_cookie(s.into(), false, &ParseConfig::default())
     }
 
     /// Parses a `Cookie` from the given HTTP cookie header value string where
@@ -366,7 +460,161 @@ impl<'c> Cookie<'c> {
     pub fn parse_encoded<S>(s: S) -> Result<Cookie<'c>, ParseError>
         where S: Into<Cow<'c, str>>
     {
-        parse_cookie(s.into(), true)
+        parse_cookie(s.into(), true, &ParseConfig::default())
+    }
+
+    /// Parses a `Cookie` from the given HTTP cookie header value string,
+    /// returning an error if any known attribute is malformed. Does not
+    /// perform any percent-decoding.
+    ///
+    /// Unlike [`Cookie::parse()`], which ignores malformed attributes, this
+    /// method fails if `Max-Age` is not an integer, if `Expires` is not an HTTP
+    /// date, if `SameSite` is not one of `Strict`, `Lax`, or `None`, or if any
+    /// attribute value contains a control character. Unknown attributes are
+    /// still accepted as [extensions](Cookie::extensions()).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, ParseError};
+    ///
+    /// let c = Cookie::parse_strict("foo=bar; Max-Age=60; SameSite=Lax").unwrap();
+    /// assert_eq!(c.name_value(), ("foo", "bar"));
+    ///
+    /// let err = Cookie::parse_strict("foo=bar; Max-Age=soon").unwrap_err();
+    /// assert_eq!(err, ParseError::InvalidMaxAge);
+    ///
+    /// // The lenient parser simply ignores the invalid attribute.
+    /// let c = Cookie::parse("foo=bar; Max-Age=soon").unwrap();
+    /// assert_eq!(c.max_age(), None);
+    /// ```
+    pub fn parse_strict<S>(s: S) -> Result<Cookie<'c>, ParseError>
+        where S: Into<Cow<'c, str>>
+    {
+        parse_cookie(s.into(), false, &ParseConfig::default().strict(true))
+    }
+
+    /// Parses a `Cookie` from the given HTTP cookie header value string exactly
+    /// as [`Cookie::parse()`] does, additionally returning a [`Warning`] for
+    /// each non-fatal problem found. Does not perform any percent-decoding.
+    ///
+    /// Currently, a [`Warning::DuplicateAttribute`] is reported, once, for each
+    /// known attribute that appears more than once, as is often the case in
+    /// `Set-Cookie` headers assembled by buggy servers. As with
+    /// [`Cookie::parse()`], the last occurrence of the attribute takes
+    /// precedence. Unknown attributes are never reported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, Warning};
+    ///
+    /// let (c, warnings) = Cookie::parse_with_warnings("foo=bar; Path=/; Path=/x").unwrap();
+    /// assert_eq!(c.path(), Some("/x"));
+    /// assert_eq!(warnings, [Warning::DuplicateAttribute("Path")]);
+    ///
+    /// let (_, warnings) = Cookie::parse_with_warnings("foo=bar; Path=/; Secure").unwrap();
+    /// assert!(warnings.is_empty());
+    /// ```
+    pub fn parse_with_warnings<S>(s: S) -> Result<(Cookie<'c>, Vec<Warning>), ParseError>
+        where S: Into<Cow<'c, str>>
+    {
+        let cookie = parse_cookie(s.into(), false, &ParseConfig::default())?;
+        let string = cookie.cookie_string.as_deref().expect("parsed cookie string");
+        let warnings = crate::parse::duplicate_attributes(string);
+        Ok((cookie, warnings))
+    }
+
+    /// Like [`Cookie::parse_strict()`] but percent-decodes the name/value
+    /// fields as in [`Cookie::parse_encoded()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, ParseError};
+    ///
+    /// let c = Cookie::parse_encoded_strict("foo=bar%20baz; SameSite=None").unwrap();
+    /// assert_eq!(c.name_value(), ("foo", "bar baz"));
+    ///
+    /// let err = Cookie::parse_encoded_strict("foo=bar%20baz; SameSite=Maybe").unwrap_err();
+    /// assert_eq!(err, ParseError::InvalidSameSite);
+    /// ```
+    #[cfg(feature = "percent-encode")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
+    pub fn parse_encoded_strict<S>(s: S) -> Result<Cookie<'c>, ParseError>
+        where S: Into<Cow<'c, str>>
+    {
+        parse_cookie(s.into(), true, &ParseConfig::default().strict(true))
+    }
+
+    /// Parses a `Cookie` from the given HTTP cookie header value string using
+    /// the options in `config`. Does not perform any percent-decoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, ParseConfig};
+    ///
+    /// let c = Cookie::parse("\"a=b\"=c").unwrap();
+    /// assert_eq!(c.name_value(), ("\"a", "b\"=c"));
+    ///
+    /// let config = ParseConfig::default().quoted_names(true);
+    /// let c = Cookie::parse_with_config("\"a=b\"=c", config).unwrap();
+    /// assert_eq!(c.name_value(), ("\"a=b\"", "c"));
+    /// ```
+    pub fn parse_with_config<S>(s: S, config: ParseConfig) -> Result<Cookie<'c>, ParseError>
+        where S: Into<Cow<'c, str>>
+    {
+        parse_cookie(s.into(), false, &config)
+    }
+
+    /// Parses a `Cookie` from a string whose name/value pair and attributes
+    /// are separated by `delimiter` instead of `;`. Does not perform any
+    /// percent-decoding.
+    ///
+    /// This is intended for non-HTTP serializations of cookies, such as those
+    /// that place each attribute on its own line. To parse a `Set-Cookie`
+    /// header value, use [`Cookie::parse()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse_with_delimiter("name=value\nPath=/\nSecure", '\n').unwrap();
+    /// assert_eq!(c.name_value(), ("name", "value"));
+    /// assert_eq!(c.path(), Some("/"));
+    /// assert_eq!(c.secure(), Some(true));
+    /// ```
+    pub fn parse_with_delimiter<S>(s: S, delimiter: char) -> Result<Cookie<'c>, ParseError>
+        where S: Into<Cow<'c, str>>
+    {
+        let config = ParseConfig::default().delimiter(delimiter);
+        parse_cookie(s.into(), false, &config)
+    }
+
+    /// Parses `attrs`, a string of `;`-separated cookie attributes without a
+    /// name/value pair, such as `Secure; Path=/`, and applies the recognized
+    /// attributes to `self`. Attributes in `attrs` replace any existing value;
+    /// all other attributes are left untouched. Unknown attributes are appended
+    /// to the [extensions](Cookie::extensions()) of `self` and invalid
+    /// attributes are ignored, just as in [`Cookie::parse()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    /// use cookie::time::Duration;
+    ///
+    /// let mut c = Cookie::build(("name", "value")).http_only(true).build();
+    /// c.apply_attributes("Secure; Path=/foo; Max-Age=60");
+    /// assert_eq!(c.secure(), Some(true));
+    /// assert_eq!(c.path(), Some("/foo"));
+    /// assert_eq!(c.max_age(), Some(Duration::seconds(60)));
+    /// assert_eq!(c.http_only(), Some(true));
+    /// ```
+    pub fn apply_attributes(&mut self, attrs: &str) {
+        parse::apply_attributes(self, attrs)
     }
 
     /// Parses the HTTP `Cookie` header, a series of cookie names and value
@@ -468,12 +716,56 @@ impl<'c> Cookie<'c> {
             value: self.value.into_owned(),
             expires: self.expires,
             max_age: self.max_age,
+            max_age_raw: self.max_age_raw.map(|s| s.into_owned()),
             domain: self.domain.map(|s| s.into_owned()),
             path: self.path.map(|s| s.into_owned()),
             secure: self.secure,
             http_only: self.http_only,
             same_site: self.same_site,
             partitioned: self.partitioned,
+            extensions: self.extensions.into_iter()
+                .map(|(name, value)| (name.into_owned(), value.map(|v| v.into_owned())))
+                .collect(),
+            decoded: self.decoded,
+        }
+    }
+
+    /// Returns a `Cookie` with the same name, value, and attributes as `self`
+    /// that borrows all of its string data from `self`. This only allocates if
+    /// `self` has [extensions](Cookie::extensions()).
+    ///
+    /// This is useful when a `Cookie<'b>` with a lifetime no longer than a
+    /// borrow of `self` is needed: unlike [`Cookie::into_owned()`] or
+    /// [`Clone`], no string data is copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build((String::from("name"), String::from("value"))).path("/").build();
+    /// let borrowed: Cookie<'_> = c.reborrow();
+    /// assert_eq!(borrowed, c);
+    /// assert_eq!(borrowed.value().as_ptr(), c.value().as_ptr());
+    /// ```
+    pub fn reborrow<'b>(&'b self) -> Cookie<'b> {
+        Cookie {
+            cookie_string: self.cookie_string.as_ref().map(|s| Cow::Borrowed(&**s)),
+            name: self.name.reborrow(),
+            value: self.value.reborrow(),
+            expires: self.expires,
+            max_age: self.max_age,
+            max_age_raw: self.max_age_raw.as_ref().map(|s| s.reborrow()),
+            domain: self.domain.as_ref().map(|s| s.reborrow()),
+            path: self.path.as_ref().map(|s| s.reborrow()),
+            secure: self.secure,
+            http_only: self.http_only,
+            same_site: self.same_site,
+            partitioned: self.partitioned,
+            extensions: self.extensions.iter()
+                .map(|(name, value)| (name.reborrow(), value.as_ref().map(|v| v.reborrow())))
+                .collect(),
+            decoded: self.decoded,
         }
     }
 
@@ -707,6 +999,36 @@ impl<'c> Cookie<'c> {
         self.partitioned
     }
 
+    /// Returns an iterator over the extensions of `self`: the attributes that
+    /// aren't recognized by this library, such as `Priority=High`, as `(name,
+    /// value)` pairs in the order they were parsed or added. The value of a
+    /// valueless extension, such as `SameParty`, is `None`.
+    ///
+    /// This includes the obsolete `Comment` and `CommentURL` attributes defined
+    /// by RFC 2965, which are preserved verbatim, surrounding quotes included.
+    ///
+    /// Extensions are emitted, in order, after all other attributes when
+    /// `self` is displayed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse("name=value; Priority=High; Secure; SameParty").unwrap();
+    /// let extensions: Vec<_> = c.extensions().collect();
+    /// assert_eq!(extensions, [("Priority", Some("High")), ("SameParty", None)]);
+    /// assert_eq!(c.to_string(), "name=value; Secure; Priority=High; SameParty");
+    ///
+    /// let c = Cookie::new("name", "value");
+    /// assert_eq!(c.extensions().count(), 0);
+    /// ```
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, Option<&str>)> + '_ {
+        let string = self.cookie_string.as_ref();
+        self.extensions.iter()
+            .map(move |(name, value)| (name.to_str(string), value.as_ref().map(|v| v.to_str(string))))
+    }
+
     /// Returns the specified max-age of the cookie if one was specified.
     ///
     /// # Example
@@ -784,6 +1106,201 @@ impl<'c> Cookie<'c> {
         }
     }
 
+    /// Returns `true` if the `Domain` of the cookie, as stored, begins with a
+    /// `.`, which [`Cookie::domain()`] and [`Cookie::domain_raw()`] strip.
+    /// Returns `false` if no `Domain` is set.
+    ///
+    /// **The leading `.` is otherwise ignored.** As per RFC 6265, it carries
+    /// no meaning: a cookie with `Domain=.crates.io` matches exactly the same
+    /// hosts as one with `Domain=crates.io`. This method exists for the sake of
+    /// formats, like the Netscape cookie file format, where it is significant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse("name=value; Domain=.crates.io").unwrap();
+    /// assert_eq!(c.domain(), Some("crates.io"));
+    /// assert!(c.domain_has_leading_dot());
+    ///
+    /// let c = Cookie::parse("name=value; Domain=crates.io").unwrap();
+    /// assert!(!c.domain_has_leading_dot());
+    ///
+    /// // Only one `.` is stripped, but a leading `.` is still present.
+    /// let c = Cookie::parse("name=value; Domain=..crates.io").unwrap();
+    /// assert_eq!(c.domain(), Some(".crates.io"));
+    /// assert!(c.domain_has_leading_dot());
+    ///
+    /// let c = Cookie::new("name", "value");
+    /// assert!(!c.domain_has_leading_dot());
+    /// ```
+    pub fn domain_has_leading_dot(&self) -> bool {
+        match self.domain {
+            Some(ref c) => c.to_str(self.cookie_string.as_ref()).starts_with('.'),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if `host` _domain-matches_ the `Domain` of `self` as
+    /// specified by [RFC 6265 §5.1.3], that is, if a client should send `self`
+    /// to `host`. The comparison is case-insensitive and ignores any leading
+    /// `.` in the `Domain`.
+    ///
+    /// `host` matches if it is identical to the `Domain` or if it is a
+    /// subdomain of it, such as `www.example.com` for `example.com`. If `host`
+    /// is an IP address, it must be identical to the `Domain`.
+    ///
+    /// A cookie without a `Domain` is a _host-only_ cookie which must only be
+    /// sent to the exact host it was received from. As a `Cookie` doesn't
+    /// record that host, this method returns `false` for such cookies; callers
+    /// must compare the origin host themselves.
+    ///
+    /// [RFC 6265 §5.1.3]: https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.3
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("name", "value")).domain("example.com").build();
+    /// assert!(c.matches_domain("example.com"));
+    /// assert!(c.matches_domain("www.Example.COM"));
+    /// assert!(!c.matches_domain("badexample.com"));
+    /// assert!(!c.matches_domain("com"));
+    ///
+    /// // A host-only cookie matches no host.
+    /// assert!(!Cookie::new("name", "value").matches_domain("example.com"));
+    /// ```
+    pub fn matches_domain(&self, host: &str) -> bool {
+        let domain = match self.domain() {
+            Some(domain) if !domain.is_empty() => domain,
+            _ => return false,
+        };
+
+        if host.eq_ignore_ascii_case(domain) {
+            return true;
+        }
+
+        let is_ip = host.trim_start_matches('[').trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok();
+
+        let (host, domain) = (host.as_bytes(), domain.as_bytes());
+        !is_ip
+            && host.len() > domain.len()
+            && host[host.len() - domain.len() - 1] == b'.'
+            && host[(host.len() - domain.len())..].eq_ignore_ascii_case(domain)
+    }
+
+    /// Returns `true` if `request_path` path-matches the cookie's path as
+    /// defined in [RFC 6265 §5.1.4]. That is, if the cookie's path is a prefix
+    /// of `request_path` and either the cookie's path ends in `/` or the first
+    /// character of `request_path` following the prefix is `/`. A cookie
+    /// without a `Path` is treated as having the path `/` and thus matches
+    /// every request path.
+    ///
+    /// Path matching is case-sensitive.
+    ///
+    /// [RFC 6265 §5.1.4]: https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.4
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("name", "value")).path("/foo").build();
+    /// assert!(c.matches_path("/foo"));
+    /// assert!(c.matches_path("/foo/bar"));
+    /// assert!(!c.matches_path("/foobar"));
+    /// assert!(!c.matches_path("/"));
+    ///
+    /// // A cookie without a path matches every path.
+    /// assert!(Cookie::new("name", "value").matches_path("/foo/bar"));
+    /// ```
+    pub fn matches_path(&self, request_path: &str) -> bool {
+        let path = self.path().unwrap_or("/");
+        match request_path.strip_prefix(path) {
+            Some(rest) => rest.is_empty() || path.ends_with('/') || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Returns the requirements of the [`"__Host-"` prefix] that `self` fails
+    /// to meet, or an empty vector if `self` meets all of them. The name of the
+    /// cookie is not considered. The requirements are checked in the following
+    /// order, and the corresponding [`HostPrefixViolation`] is included for
+    /// each one failed:
+    ///
+    ///   * [`MissingSecure`]: [`secure`](Cookie::secure()) is not `Some(true)`.
+    ///   * [`PathNotRoot`]: [`path`](Cookie::path()) is not `Some("/")`.
+    ///   * [`HasDomain`]: [`domain`](Cookie::domain()) is `Some(_)`.
+    ///
+    /// [`"__Host-"` prefix]: crate::prefix::Host
+    /// [`HostPrefixViolation`]: crate::prefix::HostPrefixViolation
+    /// [`MissingSecure`]: crate::prefix::HostPrefixViolation::MissingSecure
+    /// [`PathNotRoot`]: crate::prefix::HostPrefixViolation::PathNotRoot
+    /// [`HasDomain`]: crate::prefix::HostPrefixViolation::HasDomain
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    /// use cookie::prefix::HostPrefixViolation::*;
+    ///
+    /// let c = Cookie::build(("__Host-name", "value")).secure(true).path("/").build();
+    /// assert!(c.host_prefix_violations().is_empty());
+    ///
+    /// let c = Cookie::build(("__Host-name", "value")).domain("rust-lang.org").build();
+    /// assert_eq!(c.host_prefix_violations(), [MissingSecure, PathNotRoot, HasDomain]);
+    /// ```
+    pub fn host_prefix_violations(&self) -> Vec<prefix::HostPrefixViolation> {
+        use crate::prefix::HostPrefixViolation::*;
+
+        let mut violations = vec![];
+        if self.secure() != Some(true) {
+            violations.push(MissingSecure);
+        }
+
+        if self.path() != Some("/") {
+            violations.push(PathNotRoot);
+        }
+
+        if self.domain.is_some() {
+            violations.push(HasDomain);
+        }
+
+        violations
+    }
+
+    /// Checks that the name of `self` is a valid RFC 6265 `token` and that its
+    /// value is a valid RFC 6265 `cookie-value`, as by
+    /// [`is_valid_name()`](crate::is_valid_name()) and
+    /// [`is_valid_value()`](crate::is_valid_value()), returning an error
+    /// identifying the first offending byte otherwise.
+    ///
+    /// Cookies are never validated when they are built or parsed; this method
+    /// is opt-in. Percent-encoded cookies, as emitted by [`Cookie::encoded()`],
+    /// may have valid encodings even when `self` is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, ValidationError};
+    ///
+    /// assert!(Cookie::new("name", "value").validate().is_ok());
+    /// assert!(Cookie::new("name", "\"quoted\"").validate().is_ok());
+    ///
+    /// let err = Cookie::new("my name", "value").validate().unwrap_err();
+    /// assert_eq!(err, ValidationError::InvalidName { index: 2, byte: b' ' });
+    ///
+    /// let err = Cookie::new("name", "a;b").validate().unwrap_err();
+    /// assert_eq!(err, ValidationError::InvalidValue { index: 1, byte: b';' });
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        crate::parse::validate(self.name(), self.value())
+    }
+
     /// Returns the [`Expiration`] of the cookie if one was specified.
     ///
     /// # Example
@@ -832,52 +1349,246 @@ impl<'c> Cookie<'c> {
         self.expires.and_then(|e| e.datetime())
     }
 
-    /// Sets the name of `self` to `name`.
+    /// Returns `true` if `self` is a session cookie, that is, if it has
+    /// neither a `Max-Age` nor an `Expires` date-time. A session cookie is
+    /// discarded by the client when the current session ends.
     ///
     /// # Example
     ///
     /// ```
-    /// use cookie::Cookie;
+    /// use cookie::{Cookie, Expiration};
+    /// use cookie::time::Duration;
     ///
-    /// let mut c = Cookie::new("name", "value");
-    /// assert_eq!(c.name(), "name");
+    /// let c = Cookie::new("name", "value");
+    /// assert!(c.is_session());
     ///
-    /// c.set_name("foo");
-    /// assert_eq!(c.name(), "foo");
+    /// let c = Cookie::build(("name", "value")).expires(Expiration::Session);
+    /// assert!(c.inner().is_session());
+    ///
+    /// let c = Cookie::build(("name", "value")).max_age(Duration::hours(1));
+    /// assert!(!c.inner().is_session());
     /// ```
-    pub fn set_name<N: Into<Cow<'c, str>>>(&mut self, name: N) {
-        self.name = CookieStr::Concrete(name.into())
+    #[inline]
+    pub fn is_session(&self) -> bool {
+        self.max_age.is_none() && self.expires_datetime().is_none()
     }
 
-    /// Sets the value of `self` to `value`.
+    /// Returns `true` if `self` is expired at the current time. Equivalent to
+    /// `self.is_expired_at(OffsetDateTime::now_utc())`. See
+    /// [`Cookie::is_expired_at()`] for details.
     ///
     /// # Example
     ///
     /// ```
     /// use cookie::Cookie;
+    /// use cookie::time::Duration;
     ///
-    /// let mut c = Cookie::new("name", "value");
-    /// assert_eq!(c.value(), "value");
+    /// let c = Cookie::new("name", "value");
+    /// assert!(!c.is_expired());
     ///
-    /// c.set_value("bar");
-    /// assert_eq!(c.value(), "bar");
+    /// let c = Cookie::build(("name", "value")).removal().build();
+    /// assert!(c.is_expired());
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// c.set_expires_in(Duration::hours(-1));
+    /// assert!(c.is_expired());
     /// ```
-    pub fn set_value<V: Into<Cow<'c, str>>>(&mut self, value: V) {
-        self.value = CookieStr::Concrete(value.into())
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(OffsetDateTime::now_utc())
     }
 
-    /// Sets the value of `http_only` in `self` to `value`.  If `value` is
-    /// `None`, the field is unset.
+    /// Returns `true` if `self` is expired at `at`.
+    ///
+    /// As specified by RFC 6265, `Max-Age` takes priority over `Expires` when
+    /// both are set. Thus, if `Max-Age` is set, `self` is expired if and only
+    /// if `Max-Age` is zero or negative, regardless of `Expires` and `at`; a
+    /// positive `Max-Age` is relative to when the cookie was received, which
+    /// is unknown, so it never expires the cookie. Otherwise, `self` is expired
+    /// if its `Expires` date-time is at or before `at`. A session cookie, one
+    /// with neither attribute, is never expired.
     ///
     /// # Example
     ///
     /// ```
     /// use cookie::Cookie;
+    /// use cookie::time::{Duration, macros::datetime};
     ///
-    /// let mut c = Cookie::new("name", "value");
-    /// assert_eq!(c.http_only(), None);
+    /// let c = Cookie::build(("name", "value")).expires(datetime!(2020-01-01 0:00 UTC));
+    /// assert!(c.inner().is_expired_at(datetime!(2021-01-01 0:00 UTC)));
+    /// assert!(!c.inner().is_expired_at(datetime!(2019-01-01 0:00 UTC)));
     ///
-    /// c.set_http_only(true);
+    /// // `Max-Age` takes priority over `Expires`.
+    /// let c = c.max_age(Duration::hours(1));
+    /// assert!(!c.inner().is_expired_at(datetime!(2021-01-01 0:00 UTC)));
+    ///
+    /// let c = c.max_age(Duration::ZERO);
+    /// assert!(c.inner().is_expired_at(datetime!(2019-01-01 0:00 UTC)));
+    /// ```
+    pub fn is_expired_at(&self, at: OffsetDateTime) -> bool {
+        match (self.max_age(), self.expires_datetime()) {
+            (Some(max_age), _) => max_age <= Duration::ZERO,
+            (None, Some(expires)) => expires <= at,
+            (None, None) => false,
+        }
+    }
+
+    /// Returns `true` if the `Max-Age` and `Expires` of `self` agree, that is,
+    /// if `Expires` is within one minute of the current time plus `Max-Age`.
+    /// Returns `true` if either attribute is unset.
+    ///
+    /// Clients prefer `Max-Age` over `Expires` when both are set, so a
+    /// disagreement between the two usually indicates a bug. As a special
+    /// case, a zero `Max-Age` agrees with any `Expires` that isn't in the
+    /// future, as both indicate an already-expired cookie; this is the case for
+    /// [removal](Cookie::make_removal()) cookies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    /// use cookie::time::{Duration, OffsetDateTime};
+    ///
+    /// let in_an_hour = OffsetDateTime::now_utc() + Duration::hours(1);
+    /// let c = Cookie::build(("name", "value"))
+    ///     .max_age(Duration::hours(1))
+    ///     .expires(in_an_hour)
+    ///     .build();
+    ///
+    /// assert!(c.expiry_is_consistent());
+    ///
+    /// let c = Cookie::build(("name", "value"))
+    ///     .max_age(Duration::days(30))
+    ///     .expires(in_an_hour)
+    ///     .build();
+    ///
+    /// assert!(!c.expiry_is_consistent());
+    /// ```
+    pub fn expiry_is_consistent(&self) -> bool {
+        const TOLERANCE: Duration = Duration::minutes(1);
+
+        let (max_age, expires) = match (self.max_age(), self.expires_datetime()) {
+            (Some(max_age), Some(expires)) => (max_age, expires),
+            _ => return true,
+        };
+
+        let now = OffsetDateTime::now_utc();
+        if max_age <= Duration::ZERO {
+            return expires <= now + TOLERANCE;
+        }
+
+        let expected = now.checked_add(max_age).unwrap_or(MAX_DATETIME);
+        (expires - expected).abs() <= TOLERANCE
+    }
+
+    /// Sets the name of `self` to `name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// assert_eq!(c.name(), "name");
+    ///
+    /// c.set_name("foo");
+    /// assert_eq!(c.name(), "foo");
+    /// ```
+    pub fn set_name<N: Into<Cow<'c, str>>>(&mut self, name: N) {
+        self.name = CookieStr::Concrete(name.into())
+    }
+
+    /// Sets the value of `self` to `value`.
+    ///
+    /// The value is used as-is. Cookie values should not have leading or
+    /// trailing whitespace: it is not allowed by RFC 6265, and clients and
+    /// [`Cookie::parse()`] trim it. Use [`Cookie::trim_value()`] to remove it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// assert_eq!(c.value(), "value");
+    ///
+    /// c.set_value("bar");
+    /// assert_eq!(c.value(), "bar");
+    /// ```
+    pub fn set_value<V: Into<Cow<'c, str>>>(&mut self, value: V) {
+        self.value = CookieStr::Concrete(value.into())
+    }
+
+    /// Sets the value of `self` to `value` wrapped in double quotes, as in an
+    /// RFC 6265 quoted `cookie-value`. [`Cookie::value()`] returns the quoted
+    /// value while [`Cookie::value_trimmed()`] returns `value`. An empty
+    /// `value` results in the value `""`.
+    ///
+    /// **Warning:** If `value` itself contains a `"`, it cannot be quoted and
+    /// is instead set as-is, exactly as by [`Cookie::set_value()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// c.set_quoted_value("bar");
+    /// assert_eq!(c.value(), "\"bar\"");
+    /// assert_eq!(c.value_trimmed(), "bar");
+    /// assert_eq!(c.to_string(), "name=\"bar\"");
+    ///
+    /// c.set_quoted_value("");
+    /// assert_eq!(c.value(), "\"\"");
+    ///
+    /// // Values containing quotes are left as-is.
+    /// c.set_quoted_value("\"already\"");
+    /// assert_eq!(c.value(), "\"already\"");
+    /// ```
+    pub fn set_quoted_value<V: Into<Cow<'c, str>>>(&mut self, value: V) {
+        let value = value.into();
+        if value.contains('"') {
+            self.set_value(value);
+        } else {
+            self.set_value(format!("\"{}\"", value));
+        }
+    }
+
+    /// Removes leading and trailing ASCII whitespace from the value of `self`.
+    /// The value is left untouched if it has no surrounding whitespace.
+    ///
+    /// Cookie values should not have surrounding whitespace. Values parsed via
+    /// [`Cookie::parse()`] never do, but values set programmatically may.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "  token  ");
+    /// c.trim_value();
+    /// assert_eq!(c.value(), "token");
+    /// ```
+    pub fn trim_value(&mut self) {
+        let value = self.value();
+        let trimmed = value.trim_matches(|c: char| c.is_ascii_whitespace());
+        if trimmed.len() != value.len() {
+            let trimmed = trimmed.to_string();
+            self.set_value(trimmed);
+        }
+    }
+
+    /// Sets the value of `http_only` in `self` to `value`.  If `value` is
+    /// `None`, the field is unset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// assert_eq!(c.http_only(), None);
+    ///
+    /// c.set_http_only(true);
     /// assert_eq!(c.http_only(), Some(true));
     ///
     /// c.set_http_only(false);
@@ -992,8 +1703,48 @@ impl<'c> Cookie<'c> {
         self.partitioned = value.into();
     }
 
+    /// Appends the extension attribute `name`, with the value `value` if it is
+    /// `Some`, to the [extensions](Cookie::extensions()) of `self`.
+    ///
+    /// No attempt is made to check whether `name` is a standard attribute or
+    /// whether an extension with the same name already exists.
+    ///
+    /// Returns [`ValidationError::InvalidExtension`], leaving `self`
+    /// unchanged, if `name` is empty or contains `=`, or if `name` or `value`
+    /// contains `;` or an ASCII control character, any of which would allow
+    /// the extension to inject other attributes or headers when displayed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, ValidationError};
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// c.add_extension("Priority", Some("High")).unwrap();
+    /// c.add_extension("SameParty", None::<&str>).unwrap();
+    /// assert_eq!(c.to_string(), "name=value; Priority=High; SameParty");
+    ///
+    /// let err = c.add_extension("X", Some("1; Domain=evil.com")).unwrap_err();
+    /// assert_eq!(err, ValidationError::InvalidExtension);
+    /// assert_eq!(c.to_string(), "name=value; Priority=High; SameParty");
+    /// ```
+    pub fn add_extension<N, V>(&mut self, name: N, value: Option<V>) -> Result<(), ValidationError>
+        where N: Into<Cow<'c, str>>,
+              V: Into<Cow<'c, str>>
+    {
+        let name = name.into();
+        let value = value.map(|v| v.into());
+        crate::parse::validate_extension(&name, value.as_deref())?;
+
+        let name = CookieStr::Concrete(name);
+        let value = value.map(CookieStr::Concrete);
+        self.extensions.push((name, value));
+        Ok(())
+    }
+
     /// Sets the value of `max_age` in `self` to `value`. If `value` is `None`,
-    /// the field is unset.
+    /// the field is unset. A negative `value` is normalized to a duration of
+    /// `0`, matching the handling of negative `Max-Age` values when parsing.
     ///
     /// # Example
     ///
@@ -1009,13 +1760,17 @@ impl<'c> Cookie<'c> {
     /// c.set_max_age(Duration::hours(10));
     /// assert_eq!(c.max_age(), Some(Duration::hours(10)));
     ///
+    /// c.set_max_age(Duration::seconds(-5));
+    /// assert_eq!(c.max_age(), Some(Duration::ZERO));
+    ///
     /// c.set_max_age(None);
     /// assert!(c.max_age().is_none());
     /// # }
     /// ```
     #[inline]
     pub fn set_max_age<D: Into<Option<Duration>>>(&mut self, value: D) {
-        self.max_age = value.into();
+        self.max_age_raw = None;
+        self.max_age = value.into().map(|d| if d.is_negative() { Duration::ZERO } else { d });
     }
 
     /// Sets the `path` of `self` to `path`.
@@ -1072,6 +1827,34 @@ impl<'c> Cookie<'c> {
         self.domain = Some(CookieStr::Concrete(domain.into()));
     }
 
+    /// Sets the `domain` of `self` to `domain` converted to ASCII lowercase.
+    ///
+    /// Domains are case-insensitive. Unlike [`Cookie::set_domain()`], which
+    /// stores `domain` as given, this method normalizes the case so that the
+    /// stored domain can be compared directly. `domain` is only copied if it
+    /// contains an uppercase ASCII character.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// c.set_domain_normalized("Rust-Lang.ORG");
+    /// assert_eq!(c.domain(), Some("rust-lang.org"));
+    ///
+    /// c.set_domain("Rust-Lang.ORG");
+    /// assert_eq!(c.domain(), Some("Rust-Lang.ORG"));
+    /// ```
+    pub fn set_domain_normalized<D: Into<Cow<'c, str>>>(&mut self, domain: D) {
+        let mut domain = domain.into();
+        if domain.bytes().any(|b| b.is_ascii_uppercase()) {
+            domain.to_mut().make_ascii_lowercase();
+        }
+
+        self.set_domain(domain);
+    }
+
     /// Unsets the `domain` of `self`.
     ///
     /// # Example
@@ -1115,8 +1898,6 @@ impl<'c> Cookie<'c> {
     /// assert_eq!(c.expires(), Some(Expiration::Session));
     /// ```
     pub fn set_expires<T: Into<Expiration>>(&mut self, time: T) {
-        static MAX_DATETIME: OffsetDateTime = datetime!(9999-12-31 23:59:59.999_999 UTC);
-
         // RFC 6265 requires dates not to exceed 9999 years.
         self.expires = Some(time.into()
             .map(|time| std::cmp::min(time, MAX_DATETIME)));
@@ -1142,6 +1923,35 @@ impl<'c> Cookie<'c> {
         self.expires = None;
     }
 
+    /// Sets the expires field of `self` to the date-time `duration` from now.
+    /// Date-times too far in the future are clamped as in
+    /// [`Cookie::set_expires()`]. Date-times before the Unix epoch, which
+    /// can't all be represented as HTTP dates, are clamped to the epoch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    /// use cookie::time::{Duration, OffsetDateTime};
+    ///
+    /// let mut c = Cookie::new("name", "value");
+    /// c.set_expires_in(Duration::hours(1));
+    ///
+    /// let expires = c.expires_datetime().unwrap();
+    /// assert!(expires > OffsetDateTime::now_utc() + Duration::minutes(59));
+    /// assert!(expires <= OffsetDateTime::now_utc() + Duration::hours(1));
+    /// ```
+    pub fn set_expires_in(&mut self, duration: Duration) {
+        let when = OffsetDateTime::now_utc()
+            .checked_add(duration)
+            .unwrap_or_else(|| match duration.is_negative() {
+                true => OffsetDateTime::UNIX_EPOCH,
+                false => PrimitiveDateTime::MAX.assume_utc(),
+            });
+
+        self.set_expires(std::cmp::max(when, OffsetDateTime::UNIX_EPOCH));
+    }
+
     /// Makes `self` a "permanent" cookie by extending its expiration and max
     /// age 20 years into the future.
     ///
@@ -1195,7 +2005,245 @@ impl<'c> Cookie<'c> {
         self.set_expires(OffsetDateTime::now_utc() - Duration::days(365));
     }
 
+    /// Returns the "removal" cookie corresponding to `self` without modifying
+    /// `self`. The returned cookie is an owned clone of `self` with
+    /// [`Cookie::make_removal()`] applied: its path, domain, and other
+    /// attributes are preserved so that it matches the cookie to be removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::time::Duration;
+    ///
+    /// let c = Cookie::build(("foo", "bar")).path("/").domain("rust-lang.org").build();
+    /// let removal = c.to_removal();
+    /// assert_eq!(removal.value(), "");
+    /// assert_eq!(removal.path(), Some("/"));
+    /// assert_eq!(removal.domain(), Some("rust-lang.org"));
+    /// assert_eq!(removal.max_age(), Some(Duration::ZERO));
+    ///
+    /// // `c` is unchanged.
+    /// assert_eq!(c.value(), "bar");
+    /// ```
+    pub fn to_removal(&self) -> Cookie<'static> {
+        let mut removal = self.clone().into_owned();
+        removal.make_removal();
+        removal
+    }
+
+    /// Returns `self` with every attribute, including any
+    /// [extensions](Cookie::extensions()), cleared, leaving only the name and
+    /// value. The resulting cookie is displayed as `name=value`.
+    ///
+    /// Unlike [`Cookie::stripped()`], which only omits attributes when
+    /// displaying, this method removes them from the cookie itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("name", "value")).path("/").secure(true).build();
+    /// let bare = c.bare();
+    /// assert_eq!(bare.name_value(), ("name", "value"));
+    /// assert_eq!(bare.path(), None);
+    /// assert_eq!(bare.secure(), None);
+    /// assert_eq!(bare.to_string(), "name=value");
+    /// ```
+    pub fn bare(self) -> Cookie<'c> {
+        Cookie {
+            cookie_string: self.cookie_string,
+            name: self.name,
+            value: self.value,
+            expires: None,
+            max_age: None,
+            max_age_raw: None,
+            domain: None,
+            path: None,
+            secure: None,
+            http_only: None,
+            same_site: None,
+            partitioned: None,
+            extensions: Vec::new(),
+            decoded: self.decoded,
+        }
+    }
+
+    /// Returns `true` if `self` and `other` have byte-identical names, values,
+    /// and attributes.
+    ///
+    /// Unlike `==`, which follows RFC 6265 in comparing the path and domain
+    /// case-insensitively, this compares the path and domain case-sensitively,
+    /// does not ignore a leading `.` in the domain, and compares `SameSite`
+    /// and [extensions](Cookie::extensions()).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let a = Cookie::build(("name", "value")).domain("rust-lang.org").build();
+    /// let b = Cookie::build(("name", "value")).domain("Rust-Lang.org").build();
+    /// assert!(a == b);
+    /// assert!(!a.eq_exact(&b));
+    /// assert!(a.eq_exact(&a.clone()));
+    /// ```
+    pub fn eq_exact(&self, other: &Cookie<'_>) -> bool {
+        let domain = self.domain.as_ref().map(|d| d.to_str(self.cookie_string.as_ref()));
+        let other_domain = other.domain.as_ref().map(|d| d.to_str(other.cookie_string.as_ref()));
+
+        self.name() == other.name()
+            && self.value() == other.value()
+            && self.expires == other.expires
+            && self.max_age == other.max_age
+            && self.path() == other.path()
+            && domain == other_domain
+            && self.secure == other.secure
+            && self.http_only == other.http_only
+            && self.same_site == other.same_site
+            && self.partitioned == other.partitioned
+            && self.extensions().eq(other.extensions())
+    }
+
+    /// Returns the attributes of `self` formatted exactly as they are when
+    /// `self` is displayed, but without the leading `name=value` pair. The
+    /// string is empty if `self` has no attributes and otherwise begins with
+    /// `; `. This is useful to apply the same set of attributes to several
+    /// cookies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::build(("name", "value")).secure(true).path("/").build();
+    /// assert_eq!(c.attributes_string(), "; Secure; Path=/");
+    ///
+    /// let other = format!("other=value{}", c.attributes_string());
+    /// assert_eq!(other, "other=value; Secure; Path=/");
+    ///
+    /// assert_eq!(Cookie::new("name", "value").attributes_string(), "");
+    /// ```
+    pub fn attributes_string(&self) -> String {
+        struct Attributes<'a, 'c>(&'a Cookie<'c>);
+
+        impl fmt::Display for Attributes<'_, '_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_parameters(f)
+            }
+        }
+
+        Attributes(self).to_string()
+    }
+
+    /// Returns a human-readable description of the name, value, and every
+    /// attribute of `self`, intended for debugging. Unlike the `Display`
+    /// implementation, which omits attributes that are not set, this lists
+    /// every attribute along with its state, making absent attributes and
+    /// explicitly set ones, including `SameSite=None` and `Secure` set to
+    /// `false`, distinguishable.
+    ///
+    /// An attribute that isn't set is described as `unset`. If `Secure` is
+    /// unset but nonetheless emitted because it is implied by `Partitioned` or
+    /// `SameSite=None`, it is described as `unset (implied)`. The format of the
+    /// returned string is not stable and should not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{Cookie, SameSite};
+    ///
+    /// let c = Cookie::new("name", "value");
+    /// assert_eq!(c.debug_attributes(),
+    ///     "name=\"name\"; value=\"value\"; HttpOnly=unset; SameSite=unset; \
+    ///     Partitioned=unset; Secure=unset; Path=unset; Domain=unset; \
+    ///     Max-Age=unset; Expires=unset");
+    ///
+    /// let c = Cookie::parse("name=value; SameSite=None; Path=/").unwrap();
+    /// assert_eq!(c.debug_attributes(),
+    ///     "name=\"name\"; value=\"value\"; HttpOnly=unset; SameSite=None; \
+    ///     Partitioned=unset; Secure=unset (implied); Path=\"/\"; Domain=unset; \
+    ///     Max-Age=unset; Expires=unset");
+    ///
+    /// let c = Cookie::build(("name", "value"))
+    ///     .same_site(SameSite::Lax)
+    ///     .http_only(false)
+    ///     .expires(None)
+    ///     .build();
+    ///
+    /// assert_eq!(c.debug_attributes(),
+    ///     "name=\"name\"; value=\"value\"; HttpOnly=false; SameSite=Lax; \
+    ///     Partitioned=unset; Secure=unset; Path=unset; Domain=unset; \
+    ///     Max-Age=unset; Expires=Session");
+    /// ```
+    pub fn debug_attributes(&self) -> String {
+        fn flag(value: Option<bool>) -> String {
+            value.map_or_else(|| "unset".into(), |v| v.to_string())
+        }
+
+        fn text(value: Option<&str>) -> String {
+            value.map_or_else(|| "unset".into(), |v| format!("{:?}", v))
+        }
+
+        let secure = match self.secure() {
+            None if self.emits_secure() => "unset (implied)".into(),
+            secure => flag(secure),
+        };
+
+        let expires = match self.expires() {
+            Some(Expiration::DateTime(time)) => time.to_offset(UtcOffset::UTC)
+                .format(&crate::parse::FMT1)
+                .unwrap_or_else(|_| time.to_string()),
+            Some(Expiration::Session) => "Session".into(),
+            None => "unset".into(),
+        };
+
+        let mut debug = format!("name={:?}; value={:?}; HttpOnly={}; SameSite={}; \
+            Partitioned={}; Secure={}; Path={}; Domain={}; Max-Age={}; Expires={}",
+            self.name(),
+            self.value(),
+            flag(self.http_only()),
+            self.same_site().map_or_else(|| "unset".into(), |s| s.to_string()),
+            flag(self.partitioned()),
+            secure,
+            text(self.path()),
+            text(self.domain()),
+            self.max_age().map_or_else(|| "unset".into(), |d| d.whole_seconds().to_string()),
+            expires);
+
+        for (name, value) in self.extensions() {
+            match value {
+                Some(value) => debug.push_str(&format!("; {}={:?}", name, value)),
+                None => debug.push_str(&format!("; {}=present", name)),
+            }
+        }
+
+        debug
+    }
+
+    /// Whether `Secure` is emitted when `self` is displayed: either it was set
+    /// explicitly, or it is implied by `Partitioned` or `SameSite=None`.
+    fn emits_secure(&self) -> bool {
+        self.secure() == Some(true)
+            || self.partitioned() == Some(true)
+            || self.secure().is_none() && self.same_site() == Some(SameSite::None)
+    }
+
     fn fmt_parameters(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_parameters_with(f, false, false)
+    }
+
+    /// Formats the parameters of `self`, omitting `Secure` if `insecure` and
+    /// formatting `Expires` as an RFC 3339 date-time if `iso_expires`. The
+    /// `encoded`, `stripped`, and `verbatim` options of [`Display`] don't
+    /// affect the parameters and are handled by its `Display` implementation.
+    fn fmt_parameters_with(
+        &self,
+        f: &mut fmt::Formatter,
+        insecure: bool,
+        iso_expires: bool,
+    ) -> fmt::Result {
         if let Some(true) = self.http_only() {
             write!(f, "; HttpOnly")?;
         }
@@ -1208,10 +2256,7 @@ impl<'c> Cookie<'c> {
             write!(f, "; Partitioned")?;
         }
 
-        if self.secure() == Some(true)
-            || self.partitioned() == Some(true)
-            || self.secure().is_none() && self.same_site() == Some(SameSite::None)
-        {
+        if self.emits_secure() && !insecure {
             write!(f, "; Secure")?;
         }
 
@@ -1229,7 +2274,19 @@ impl<'c> Cookie<'c> {
 
         if let Some(time) = self.expires_datetime() {
             let time = time.to_offset(UtcOffset::UTC);
-            write!(f, "; Expires={}", time.format(&crate::parse::FMT1).map_err(|_| fmt::Error)?)?;
+            let time = match iso_expires {
+                true => time.format(&Rfc3339),
+                false => time.format(&crate::parse::FMT1),
+            };
+
+            write!(f, "; Expires={}", time.map_err(|_| fmt::Error)?)?;
+        }
+
+        for (name, value) in self.extensions() {
+            match value {
+                Some(value) => write!(f, "; {}={}", name, value)?,
+                None => write!(f, "; {}", name)?,
+            }
         }
 
         Ok(())
@@ -1237,7 +2294,8 @@ impl<'c> Cookie<'c> {
 
     /// Returns the name of `self` as a string slice of the raw string `self`
     /// was originally parsed from. If `self` was not originally parsed from a
-    /// raw string, returns `None`.
+    /// raw string, returns `None`. As the parser trims the name, the returned
+    /// slice excludes any whitespace surrounding the name in the raw string.
     ///
     /// This method differs from [`Cookie::name()`] in that it returns a string
     /// with the same lifetime as the originally parsed string. This lifetime
@@ -1369,29 +2427,218 @@ impl<'c> Cookie<'c> {
         }
     }
 
-    /// Wraps `self` in an encoded [`Display`]: a cost-free wrapper around
-    /// `Cookie` whose [`fmt::Display`] implementation percent-encodes the name
-    /// and value of the wrapped `Cookie`.
+    /// Returns the value of the `Max-Age` attribute of `self` as a string slice
+    /// of the raw string `self` was originally parsed from, exactly as it
+    /// appears there. If `self` was not originally parsed from a raw string, or
+    /// if the raw string doesn't contain a `Max-Age` attribute with a value, or
+    /// if the max-age has changed since parsing, returns `None`. If the raw
+    /// string contains multiple `Max-Age` attributes, the last one is returned.
     ///
-    /// The returned structure can be chained with [`Display::stripped()`] to
-    /// display only the name and value.
+    /// The parser interprets `Max-Age` leniently: negative values become `0`,
+    /// values too large for a [`Duration`] are clamped, and non-numeric values
+    /// are ignored. The raw value allows callers to detect or interpret such
+    /// values themselves.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```
     /// use cookie::Cookie;
+    /// use cookie::time::Duration;
     ///
-    /// let mut c = Cookie::build(("my name", "this; value?")).secure(true).build();
-    /// assert_eq!(&c.encoded().to_string(), "my%20name=this%3B%20value%3F; Secure");
-    /// assert_eq!(&c.encoded().stripped().to_string(), "my%20name=this%3B%20value%3F");
+    /// let c = Cookie::parse("foo=bar; Max-Age=99999999999999999999").unwrap();
+    /// assert_eq!(c.max_age(), Some(Duration::seconds(i64::MAX)));
+    /// assert_eq!(c.max_age_raw(), Some("99999999999999999999"));
+    ///
+    /// let c = Cookie::parse("foo=bar; Max-Age=soon").unwrap();
+    /// assert_eq!(c.max_age(), None);
+    /// assert_eq!(c.max_age_raw(), Some("soon"));
+    ///
+    /// let c = Cookie::parse("foo=bar").unwrap();
+    /// assert_eq!(c.max_age_raw(), None);
     /// ```
-    #[cfg(feature = "percent-encode")]
-    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
-    #[inline(always)]
+    #[inline]
+    pub fn max_age_raw(&self) -> Option<&'c str> {
+        match (self.max_age_raw.as_ref(), self.cookie_string.as_ref()) {
+            (Some(max_age), Some(string)) => max_age.to_raw_str(string),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the name or value of `self` were transformed by
+    /// percent-decoding when `self` was parsed, that is, if they differ from
+    /// their raw, encoded form in the parsed string. Returns `false` if `self`
+    /// wasn't parsed with [`Cookie::parse_encoded()`] or
+    /// [`Cookie::split_parse_encoded()`] or if there was nothing to decode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse("foo=bar%20baz").unwrap();
+    /// assert!(!c.was_decoded());
+    ///
+    /// # #[cfg(feature = "percent-encode")] {
+    /// let c = Cookie::parse_encoded("foo=bar%20baz").unwrap();
+    /// assert_eq!(c.value(), "bar baz");
+    /// assert!(c.was_decoded());
+    ///
+    /// let c = Cookie::parse_encoded("foo=bar").unwrap();
+    /// assert!(!c.was_decoded());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn was_decoded(&self) -> bool {
+        self.decoded
+    }
+
+    /// Returns the exact string `self` was parsed from if `self` hasn't been
+    /// modified since it was parsed. Otherwise, or if `self` wasn't parsed,
+    /// returns `None`.
+    ///
+    /// Unlike displaying `self`, which emits attributes in a canonical order
+    /// and format, this preserves the original bytes, as needed by proxies
+    /// that forward headers verbatim. A return value of `None` for a parsed
+    /// cookie indicates that some part of it was modified.
+    ///
+    /// Modification is detected by comparing `self` to the result of parsing
+    /// the original string again with the default [`ParseConfig`]. As such,
+    /// this always returns `None` for a cookie parsed with a non-default
+    /// configuration that affected the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let raw = "name=value;secure; path=/;Max-Age=60";
+    /// let mut c = Cookie::parse(raw).unwrap();
+    /// assert_eq!(c.reemit_original(), Some(raw));
+    /// assert_eq!(c.to_string(), "name=value; Secure; Path=/; Max-Age=60");
+    ///
+    /// c.set_path("/app");
+    /// assert_eq!(c.reemit_original(), None);
+    ///
+    /// assert_eq!(Cookie::new("name", "value").reemit_original(), None);
+    /// ```
+    pub fn reemit_original(&self) -> Option<&str> {
+        let string = self.cookie_string.as_ref()?;
+        let reparsed = parse_cookie(&**string, self.decoded, &ParseConfig::default()).ok()?;
+        match self.eq_exact(&reparsed) {
+            true => Some(string),
+            false => None,
+        }
+    }
+
+    /// Decodes the value of `self` as standard, padded base64, returning the
+    /// decoded bytes or `None` if the value isn't valid base64.
+    ///
+    /// The values of cookies in signed and private jars are base64 encoded,
+    /// making this useful for tooling that inspects such cookies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::new("name", "aGVsbG8=");
+    /// assert_eq!(c.value_base64_decoded(), Some(b"hello".to_vec()));
+    ///
+    /// let c = Cookie::new("name", "not base64!");
+    /// assert_eq!(c.value_base64_decoded(), None);
+    /// ```
+    #[cfg(any(feature = "private", feature = "signed"))]
+    #[cfg_attr(all(nightly, doc), doc(cfg(any(feature = "private", feature = "signed"))))]
+    pub fn value_base64_decoded(&self) -> Option<Vec<u8>> {
+        crate::secure::base64::decode(self.value()).ok()
+    }
+
+    /// Returns the set of standard attributes present in `self`.
+    ///
+    /// An attribute with a value, such as `Path`, is present if it is set. A
+    /// flag attribute, such as `Secure`, is present if it is set to `true`.
+    /// `Expires` is present only if it is set to a date-time. For a parsed
+    /// cookie, these are exactly the attributes the parser recognized and
+    /// accepted; attributes that failed to parse are absent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cookie::{AttributeSet, Cookie};
+    ///
+    /// let c = Cookie::parse("foo=bar; Secure; Path=/; Max-Age=oops").unwrap();
+    /// assert_eq!(c.present_attributes(), AttributeSet::SECURE | AttributeSet::PATH);
+    /// ```
+    pub fn present_attributes(&self) -> AttributeSet {
+        let mut set = AttributeSet::empty();
+        let attributes = [
+            (self.secure() == Some(true), AttributeSet::SECURE),
+            (self.http_only() == Some(true), AttributeSet::HTTP_ONLY),
+            (self.same_site().is_some(), AttributeSet::SAME_SITE),
+            (self.partitioned() == Some(true), AttributeSet::PARTITIONED),
+            (self.path().is_some(), AttributeSet::PATH),
+            (self.domain().is_some(), AttributeSet::DOMAIN),
+            (self.max_age().is_some(), AttributeSet::MAX_AGE),
+            (self.expires_datetime().is_some(), AttributeSet::EXPIRES),
+        ];
+
+        for (present, attribute) in attributes.iter() {
+            if *present {
+                set.insert(*attribute);
+            }
+        }
+
+        set
+    }
+
+    /// Wraps `self` in an encoded [`Display`]: a cost-free wrapper around
+    /// `Cookie` whose [`fmt::Display`] implementation percent-encodes the name
+    /// and value of the wrapped `Cookie`.
+    ///
+    /// The returned structure can be chained with [`Display::stripped()`] to
+    /// display only the name and value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::build(("my name", "this; value?")).secure(true).build();
+    /// assert_eq!(&c.encoded().to_string(), "my%20name=this%3B%20value%3F; Secure");
+    /// assert_eq!(&c.encoded().stripped().to_string(), "my%20name=this%3B%20value%3F");
+    /// ```
+    #[cfg(feature = "percent-encode")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
+    #[inline(always)]
     pub fn encoded<'a>(&'a self) -> Display<'a, 'c> {
         Display::new_encoded(self)
     }
 
+    /// Like [`Cookie::encoded()`] but percent-encodes the name and value with
+    /// the encoding set `set` instead of the default,
+    /// [`encoding::DEFAULT_ENCODE_SET`]. The set can be built by extending the
+    /// default set, to encode additional bytes, or from scratch, to encode
+    /// fewer. Bytes outside of ASCII are always encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::encoding::{AsciiSet, DEFAULT_ENCODE_SET};
+    ///
+    /// const DOTS: &AsciiSet = &DEFAULT_ENCODE_SET.add(b'.');
+    ///
+    /// let c = Cookie::build(("a.b", "1.5 x")).path("/").build();
+    /// assert_eq!(c.encoded().to_string(), "a.b=1.5%20x; Path=/");
+    /// assert_eq!(c.encoded_with(DOTS).to_string(), "a%2Eb=1%2E5%20x; Path=/");
+    /// ```
+    #[cfg(feature = "percent-encode")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
+    #[inline(always)]
+    pub fn encoded_with<'a>(&'a self, set: &'static encoding::AsciiSet) -> Display<'a, 'c> {
+        Display::new(self).encoded_with(set)
+    }
+
     /// Wraps `self` in a stripped `Display`]: a cost-free wrapper around
     /// `Cookie` whose [`fmt::Display`] implementation prints only the `name`
     /// and `value` of the wrapped `Cookie`.
@@ -1415,6 +2662,146 @@ assert_eq!(&c.stripped().encoded().to_string(), "key%3F=value");
     pub fn stripped<'a>(&'a self) -> Display<'a, 'c> {
         Display::new_stripped(self)
     }
+
+    /// Wraps `self` in an insecure [`Display`]: a wrapper around `Cookie`
+    /// whose [`fmt::Display`] implementation never emits the `Secure`
+    /// attribute. See [`Display::insecure_dev()`] for details.
+    ///
+    /// **Warning:** This is intended _only_ for local development over plain
+    /// HTTP. Never use it to emit cookies in production.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, SameSite};
+    ///
+    /// let c = Cookie::build(("key", "value")).same_site(SameSite::None).build();
+    /// assert_eq!(&c.to_string(), "key=value; SameSite=None; Secure");
+    /// assert_eq!(&c.insecure_dev().to_string(), "key=value; SameSite=None");
+    /// ```
+    #[inline(always)]
+    pub fn insecure_dev<'a>(&'a self) -> Display<'a, 'c> {
+        Display::new_insecure_dev(self)
+    }
+
+    /// Wraps `self` in a verbatim [`Display`]: a wrapper around `Cookie` whose
+    /// [`fmt::Display`] implementation emits the exact string `self` was
+    /// parsed from if `self` hasn't been modified since. See
+    /// [`Display::verbatim()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse("a=b;SameSite=None").unwrap();
+    /// assert_eq!(&c.to_string(), "a=b; SameSite=None; Secure");
+    /// assert_eq!(&c.verbatim().to_string(), "a=b;SameSite=None");
+    /// ```
+    #[inline(always)]
+    pub fn verbatim<'a>(&'a self) -> Display<'a, 'c> {
+        Display::new_verbatim(self)
+    }
+
+    /// Wraps `self` in a [`Display`] whose [`fmt::Display`] implementation
+    /// emits the `Expires` attribute as a non-standard RFC 3339 (ISO 8601)
+    /// date-time. See [`Display::iso_expires()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let c = Cookie::parse("a=b; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+    /// assert_eq!(&c.iso_expires().to_string(), "a=b; Expires=2015-10-21T07:28:00Z");
+    ///
+    /// let c = Cookie::parse("a=b; Expires=2015-10-21T07:28:00Z").unwrap();
+    /// assert_eq!(&c.to_string(), "a=b; Expires=Wed, 21 Oct 2015 07:28:00 GMT");
+    /// ```
+    #[inline(always)]
+    pub fn iso_expires<'a>(&'a self) -> Display<'a, 'c> {
+        Display::new_iso_expires(self)
+    }
+
+    /// Returns a value whose [`fmt::Display`] implementation renders `self`'s
+    /// name and value followed only by the attributes of `self` that differ
+    /// from those of `base`.
+    ///
+    /// Attributes that are set in `base` but unset in `self` have no
+    /// `Set-Cookie` representation and are not rendered. As such, this is
+    /// primarily useful for debugging and for emitting minimal updates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let base = Cookie::build(("name", "value")).path("/").http_only(true).build();
+    /// let c = Cookie::build(("name", "value")).path("/").http_only(true).secure(true).build();
+    /// assert_eq!(c.diff_display(&base).to_string(), "name=value; Secure");
+    ///
+    /// let c = Cookie::build(("name", "other")).path("/app").http_only(true).build();
+    /// assert_eq!(c.diff_display(&base).to_string(), "name=other; Path=/app");
+    /// ```
+    pub fn diff_display<'a>(&'a self, base: &'a Cookie<'a>) -> impl fmt::Display + 'a {
+        DiffDisplay { cookie: self, base }
+    }
+}
+
+/// The [`fmt::Display`] implementation returned by [`Cookie::diff_display()`].
+struct DiffDisplay<'a> {
+    cookie: &'a Cookie<'a>,
+    base: &'a Cookie<'a>,
+}
+
+impl fmt::Display for DiffDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (c, base) = (self.cookie, self.base);
+        write!(f, "{}={}", c.name(), c.value())?;
+
+        if c.http_only() == Some(true) && base.http_only() != Some(true) {
+            write!(f, "; HttpOnly")?;
+        }
+
+        if let Some(same_site) = c.same_site().filter(|s| base.same_site() != Some(*s)) {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+
+        if c.partitioned() == Some(true) && base.partitioned() != Some(true) {
+            write!(f, "; Partitioned")?;
+        }
+
+        if c.emits_secure() && !base.emits_secure() {
+            write!(f, "; Secure")?;
+        }
+
+        if let Some(path) = c.path().filter(|p| base.path() != Some(*p)) {
+            write!(f, "; Path={}", path)?;
+        }
+
+        if let Some(domain) = c.domain().filter(|d| base.domain() != Some(*d)) {
+            write!(f, "; Domain={}", domain)?;
+        }
+
+        if let Some(max_age) = c.max_age().filter(|m| base.max_age() != Some(*m)) {
+            write!(f, "; Max-Age={}", max_age.whole_seconds())?;
+        }
+
+        let expires = c.expires_datetime().filter(|t| base.expires_datetime() != Some(*t));
+        if let Some(time) = expires {
+            let time = time.to_offset(UtcOffset::UTC);
+            write!(f, "; Expires={}", time.format(&crate::parse::FMT1).map_err(|_| fmt::Error)?)?;
+        }
+
+        for extension in c.extensions().filter(|e| !base.extensions().any(|b| b == *e)) {
+            match extension {
+                (name, Some(value)) => write!(f, "; {}={}", name, value)?,
+                (name, None) => write!(f, "; {}", name)?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// An iterator over cookie parse `Result`s: `Result<Cookie, ParseError>`.
@@ -1445,9 +2832,10 @@ impl<'c> Iterator for SplitCookies<'c> {
                 continue;
             }
 
+            let config = ParseConfig::default();
             return Some(match self.string {
-                Cow::Borrowed(s) => parse_cookie(s[i..j].trim(), self.decode),
-                Cow::Owned(ref s) => parse_cookie(s[i..j].trim().to_owned(), self.decode),
+                Cow::Borrowed(s) => parse_cookie(s[i..j].trim(), self.decode, &config),
+                Cow::Owned(ref s) => parse_cookie(s[i..j].trim().to_owned(), self.decode, &config),
             })
         }
 
@@ -1455,9 +2843,13 @@ impl<'c> Iterator for SplitCookies<'c> {
     }
 }
 
+/// Percent-encoding sets for [`Cookie::encoded_with()`].
 #[cfg(feature = "percent-encode")]
-mod encoding {
-    use percent_encoding::{AsciiSet, CONTROLS};
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
+pub mod encoding {
+    use percent_encoding::CONTROLS;
+
+    pub use percent_encoding::AsciiSet;
 
     /// https://url.spec.whatwg.org/#fragment-percent-encode-set
     const FRAGMENT: &AsciiSet = &CONTROLS
@@ -1494,19 +2886,52 @@ mod encoding {
         .add(b')')
         .add(b',');
 
-    /// Percent-encode a cookie name or value with the proper encoding set.
-    pub fn encode(string: &str) -> impl std::fmt::Display + '_ {
-        percent_encoding::percent_encode(string.as_bytes(), COOKIE)
+    /// The set of bytes percent-encoded by [`Cookie::encoded()`]: control
+    /// characters, bytes outside of ASCII, and those ASCII characters that are
+    /// not permitted in a cookie name or value or that could be misinterpreted
+    /// when decoding.
+    ///
+    /// The set can be extended to build a custom set for
+    /// [`Cookie::encoded_with()`].
+    ///
+    /// [`Cookie::encoded()`]: crate::Cookie::encoded()
+    /// [`Cookie::encoded_with()`]: crate::Cookie::encoded_with()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::encoding::{AsciiSet, DEFAULT_ENCODE_SET};
+    ///
+    /// const EXTENDED: &AsciiSet = &DEFAULT_ENCODE_SET.add(b'.');
+    /// ```
+    pub const DEFAULT_ENCODE_SET: &AsciiSet = COOKIE;
+
+    /// Percent-encode a cookie name or value with the encoding set `set`.
+    pub(crate) fn encode<'a>(
+        string: &'a str,
+        set: &'static AsciiSet
+    ) -> impl std::fmt::Display + 'a {
+        percent_encoding::percent_encode(string.as_bytes(), set)
     }
 }
 
-/// Wrapper around `Cookie` whose `Display` implementation either
-/// percent-encodes the cookie's name and value, skips displaying the cookie's
-/// parameters (only displaying it's name and value), or both.
+/// Wrapper around `Cookie` whose `Display` implementation displays the cookie
+/// with any combination of the following options:
 ///
-/// A value of this type can be obtained via [`Cookie::encoded()`] and
-/// [`Cookie::stripped()`], or an arbitrary chaining of the two methods. This
-/// type should only be used for its `Display` implementation.
+///   * [encoded](Display::encoded()): percent-encodes the name and value.
+///   * [stripped](Display::stripped()): skips the cookie's parameters, only
+///     displaying its name and value.
+///   * [insecure](Display::insecure_dev()): never displays `Secure`.
+///   * [ISO expires](Display::iso_expires()): displays `Expires` as an RFC
+///     3339 date-time.
+///   * [verbatim](Display::verbatim()): displays the string an unmodified
+///     cookie was parsed from, unless any other option is set.
+///
+/// A value of this type can be obtained via [`Cookie::encoded()`],
+/// [`Cookie::stripped()`], [`Cookie::insecure_dev()`],
+/// [`Cookie::iso_expires()`], and [`Cookie::verbatim()`], or an arbitrary
+/// chaining of the methods. This type should only be used for its `Display`
+/// implementation.
 ///
 /// # Example
 ///
@@ -1525,16 +2950,25 @@ assert_eq!(&c.encoded().stripped().to_string(), "my%20name=this%3B%20value%25%3F
 pub struct Display<'a, 'c: 'a> {
     cookie: &'a Cookie<'c>,
     #[cfg(feature = "percent-encode")]
-    encode: bool,
+    encode: Option<&'static encoding::AsciiSet>,
     strip: bool,
+    insecure: bool,
+    verbatim: bool,
+    iso_expires: bool,
 }
 
 impl<'a, 'c: 'a> fmt::Display for Display<'a, 'c> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.verbatim && !self.has_other_options() {
+            if let Some(original) = self.cookie.reemit_original() {
+                return f.write_str(original);
+            }
+        }
+
         #[cfg(feature = "percent-encode")] {
-            if self.encode {
-                let name = encoding::encode(self.cookie.name());
-                let value = encoding::encode(self.cookie.value());
+            if let Some(set) = self.encode {
+                let name = encoding::encode(self.cookie.name(), set);
+                let value = encoding::encode(self.cookie.value(), set);
                 write!(f, "{}={}", name, value)?;
             } else {
                 write!(f, "{}={}", self.cookie.name(), self.cookie.value())?;
@@ -1547,27 +2981,66 @@ impl<'a, 'c: 'a> fmt::Display for Display<'a, 'c> {
 
         match self.strip {
             true => Ok(()),
-            false => self.cookie.fmt_parameters(f)
+            false => self.cookie.fmt_parameters_with(f, self.insecure, self.iso_expires)
         }
     }
 }
 
 impl<'a, 'c> Display<'a, 'c> {
+    fn new(cookie: &'a Cookie<'c>) -> Self {
+        Display {
+            cookie, strip: false, insecure: false, verbatim: false, iso_expires: false,
+            #[cfg(feature = "percent-encode")] encode: None
+        }
+    }
+
     #[cfg(feature = "percent-encode")]
     fn new_encoded(cookie: &'a Cookie<'c>) -> Self {
-        Display { cookie, strip: false, encode: true }
+        Display::new(cookie).encoded()
     }
 
     fn new_stripped(cookie: &'a Cookie<'c>) -> Self {
-        Display { cookie, strip: true, #[cfg(feature = "percent-encode")] encode: false }
+        Display::new(cookie).stripped()
+    }
+
+    fn new_insecure_dev(cookie: &'a Cookie<'c>) -> Self {
+        Display::new(cookie).insecure_dev()
+    }
+
+    fn new_verbatim(cookie: &'a Cookie<'c>) -> Self {
+        Display::new(cookie).verbatim()
+    }
+
+    fn new_iso_expires(cookie: &'a Cookie<'c>) -> Self {
+        Display::new(cookie).iso_expires()
+    }
+
+    /// Returns `true` if any option other than `verbatim` is set.
+    fn has_other_options(&self) -> bool {
+        #[cfg(feature = "percent-encode")] {
+            if self.encode.is_some() {
+                return true;
+            }
+        }
+
+        self.strip || self.insecure || self.iso_expires
     }
 
     /// Percent-encode the name and value pair.
     #[inline]
     #[cfg(feature = "percent-encode")]
     #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
-    pub fn encoded(mut self) -> Self {
-        self.encode = true;
+    pub fn encoded(self) -> Self {
+        self.encoded_with(encoding::DEFAULT_ENCODE_SET)
+    }
+
+    /// Percent-encode the name and value pair with the encoding set `set`
+    /// instead of [`DEFAULT_ENCODE_SET`](encoding::DEFAULT_ENCODE_SET).
+    #[inline]
+    #[cfg(feature = "percent-encode")]
+    #[cfg_attr(all(nightly, doc), doc(cfg(feature = "percent-encode")))]
+    pub fn encoded_with(mut self, set: &'static encoding::AsciiSet) -> Self {
+        self.encode = Some(set);
         self
     }
 
@@ -1577,6 +3050,97 @@ impl<'a, 'c> Display<'a, 'c> {
         self.strip = true;
         self
     }
+
+    /// Never display the `Secure` attribute, whether it was set explicitly or
+    /// is implied by `SameSite=None` or `Partitioned`.
+    ///
+    /// Browsers refuse to set `Secure` cookies over plain HTTP, which hinders
+    /// local development without TLS. This option lets such cookies be set.
+    ///
+    /// **Warning:** Cookies displayed this way are sent over unencrypted
+    /// connections and, with `SameSite=None`, rejected by modern browsers
+    /// outright when not on `localhost`. This option is intended _only_ for
+    /// local development. Never use it to emit cookies in production.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, SameSite};
+    ///
+    /// let c = Cookie::build(("key", "value")).partitioned(true).build();
+    /// assert_eq!(&c.stripped().to_string(), "key=value");
+    /// assert_eq!(&c.insecure_dev().to_string(), "key=value; Partitioned");
+    #[cfg_attr(feature = "percent-encode", doc = r##"
+// Note: `encoded()` is only available when `percent-encode` is enabled.
+assert_eq!(&c.encoded().insecure_dev().to_string(), "key=value; Partitioned");
+    #"##)]
+    /// ```
+    #[inline]
+    pub fn insecure_dev(mut self) -> Self {
+        self.insecure = true;
+        self
+    }
+
+    /// Display the exact string the cookie was parsed from, as returned by
+    /// [`Cookie::reemit_original()`], if the cookie hasn't been modified since
+    /// it was parsed. If the cookie wasn't parsed, has been modified, or any
+    /// other option, such as [`Display::stripped()`], is also set, this option
+    /// has no effect and the cookie is displayed as usual.
+    ///
+    /// Formatting with this option costs as much as parsing the cookie again:
+    /// every time, the original string is re-parsed to determine whether the
+    /// cookie has been modified. See [`Cookie::reemit_original()`].
+    ///
+    /// This allows representing cookies exactly as a server sent them, even
+    /// when they're malformed, such as a `SameSite=None` cookie without the
+    /// `Secure` attribute, which is otherwise always emitted with `Secure`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    ///
+    /// let mut c = Cookie::parse("a=b; SameSite=None").unwrap();
+    /// assert_eq!(&c.verbatim().to_string(), "a=b; SameSite=None");
+    ///
+    /// // Other options disable verbatim display.
+    /// assert_eq!(&c.stripped().verbatim().to_string(), "a=b");
+    /// assert_eq!(&c.verbatim().insecure_dev().to_string(), "a=b; SameSite=None");
+    ///
+    /// // Once modified, the cookie is displayed as usual.
+    /// c.set_value("c");
+    /// assert_eq!(&c.verbatim().to_string(), "a=c; SameSite=None; Secure");
+    /// ```
+    #[inline]
+    pub fn verbatim(mut self) -> Self {
+        self.verbatim = true;
+        self
+    }
+
+    /// Display the `Expires` attribute as an RFC 3339 (ISO 8601) date-time in
+    /// UTC, such as `2015-10-21T07:28:00Z`, instead of as an HTTP date.
+    ///
+    /// **Warning:** This format is non-standard: RFC 6265 requires an HTTP
+    /// date, and browsers will not understand any other. This option is
+    /// intended _only_ for interoperability with other tools. Cookies
+    /// displayed this way can be parsed by [`Cookie::parse()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Cookie;
+    /// use cookie::time::macros::datetime;
+    ///
+    /// let c = Cookie::build(("a", "b")).expires(datetime!(2015-10-21 7:28 UTC)).build();
+    /// assert_eq!(&c.to_string(), "a=b; Expires=Wed, 21 Oct 2015 07:28:00 GMT");
+    /// assert_eq!(&c.iso_expires().to_string(), "a=b; Expires=2015-10-21T07:28:00Z");
+    /// assert_eq!(&c.stripped().iso_expires().to_string(), "a=b");
+    /// ```
+    #[inline]
+    pub fn iso_expires(mut self) -> Self {
+        self.iso_expires = true;
+        self
+    }
 }
 
 impl<'c> fmt::Display for Cookie<'c> {
@@ -1684,7 +3248,7 @@ impl<'a> AsMut<Cookie<'a>> for Cookie<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Cookie, SameSite, parse::parse_date};
+    use crate::{AttributeSet, Cookie, Expiration, SameSite, parse::parse_date};
     use time::{Duration, OffsetDateTime};
 
     #[test]
@@ -1749,6 +3313,873 @@ mod tests {
         assert_eq!(&c.to_string(), "foo=bar; SameSite=None; Secure");
     }
 
+    #[test]
+    fn negative_max_age() {
+        let mut cookie = Cookie::new("foo", "bar");
+        cookie.set_max_age(Duration::seconds(-10));
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+        assert_eq!(&cookie.to_string(), "foo=bar; Max-Age=0");
+
+        let cookie = Cookie::build(("foo", "bar")).max_age(Duration::MIN);
+        assert_eq!(cookie.inner().max_age(), Some(Duration::ZERO));
+        assert_eq!(&cookie.to_string(), "foo=bar; Max-Age=0");
+    }
+
+    #[test]
+    fn clearing() {
+        let cookie = Cookie::clearing("foo", Some("/a"), Some("rust-lang.org"));
+        assert_eq!(cookie.name_value(), ("foo", ""));
+        assert_eq!(cookie.path(), Some("/a"));
+        assert_eq!(cookie.domain(), Some("rust-lang.org"));
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+        assert!(cookie.expires_datetime().unwrap() < OffsetDateTime::now_utc());
+
+        let string = cookie.to_string();
+        assert!(string.starts_with("foo=; Path=/a; Domain=rust-lang.org; Max-Age=0; Expires="));
+
+        let cookie = Cookie::clearing(String::from("foo"), None, None);
+        assert_eq!(cookie.path(), None);
+        assert_eq!(cookie.domain(), None);
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn is_session() {
+        let cookie = Cookie::new("foo", "bar");
+        assert!(cookie.is_session());
+
+        let cookie = Cookie::build(("foo", "bar")).max_age(Duration::hours(1));
+        assert!(!cookie.inner().is_session());
+
+        let cookie = Cookie::build(("foo", "bar")).expires(OffsetDateTime::now_utc());
+        assert!(!cookie.inner().is_session());
+
+        let cookie = Cookie::build(("foo", "bar")).expires(Expiration::Session);
+        assert!(cookie.inner().is_session());
+
+        let cookie = Cookie::build(("foo", "bar"))
+            .expires(Expiration::Session)
+            .max_age(Duration::ZERO);
+        assert!(!cookie.inner().is_session());
+
+        let cookie = Cookie::parse("foo=bar; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert!(!cookie.is_session());
+    }
+
+    #[test]
+    fn expires_in() {
+        let before = OffsetDateTime::now_utc();
+        let cookie = Cookie::build(("foo", "bar")).expires_in(Duration::hours(1)).build();
+        let after = OffsetDateTime::now_utc();
+
+        let expires = cookie.expires_datetime().unwrap();
+        assert!(expires >= before + Duration::hours(1));
+        assert!(expires <= after + Duration::hours(1));
+        assert!(cookie.max_age().is_none());
+
+        let mut cookie = Cookie::new("foo", "bar");
+        cookie.set_expires_in(Duration::MAX);
+        assert_eq!(cookie.expires_datetime().unwrap().year(), 9999);
+
+        cookie.set_expires_in(Duration::MIN);
+        assert_eq!(cookie.expires_datetime(), Some(OffsetDateTime::UNIX_EPOCH));
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Thu, 01 Jan 1970 00:00:00 GMT");
+
+        cookie.set_expires_in(Duration::weeks(-52 * 3000));
+        assert_eq!(cookie.expires_datetime(), Some(OffsetDateTime::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn to_removal() {
+        let cookie = Cookie::parse("foo=bar; Path=/a; Domain=rust-lang.org; Secure").unwrap();
+        let removal = cookie.to_removal();
+        assert_eq!(removal.name_value(), ("foo", ""));
+        assert_eq!(removal.path(), Some("/a"));
+        assert_eq!(removal.domain(), Some("rust-lang.org"));
+        assert_eq!(removal.secure(), Some(true));
+        assert_eq!(removal.max_age(), Some(Duration::ZERO));
+        assert!(removal.expires_datetime().unwrap() < OffsetDateTime::now_utc());
+        assert!(removal.to_string()
+            .starts_with("foo=; Secure; Path=/a; Domain=rust-lang.org; Max-Age=0; Expires="));
+
+        assert_eq!(cookie.value(), "bar");
+        assert_eq!(cookie.max_age(), None);
+    }
+
+    #[test]
+    fn diff_display() {
+        let base = Cookie::build(("foo", "bar")).path("/").http_only(true).build();
+        let c = Cookie::build(("foo", "bar")).path("/").http_only(true).secure(true).build();
+        assert_eq!(c.diff_display(&base).to_string(), "foo=bar; Secure");
+        assert_eq!(base.diff_display(&c).to_string(), "foo=bar");
+        assert_eq!(c.diff_display(&c).to_string(), "foo=bar");
+
+        let c = Cookie::build(("foo", "bar")).same_site(SameSite::None).build();
+        assert_eq!(c.diff_display(&base).to_string(), "foo=bar; SameSite=None; Secure");
+    }
+
+    #[test]
+    fn build_same_site_none_sets_secure() {
+        let c = Cookie::build(("foo", "bar")).same_site(SameSite::None).build();
+        assert_eq!(c.secure(), Some(true));
+        assert_eq!(&c.to_string(), "foo=bar; SameSite=None; Secure");
+
+        let c: Cookie<'_> = Cookie::build(("foo", "bar")).same_site(SameSite::None).into();
+        assert_eq!(c.secure(), Some(true));
+
+        let c = Cookie::build(("foo", "bar")).same_site(SameSite::None).secure(false).build();
+        assert_eq!(c.secure(), Some(false));
+        assert_eq!(&c.to_string(), "foo=bar; SameSite=None");
+
+        let c = Cookie::build(("foo", "bar")).same_site(SameSite::Lax).build();
+        assert_eq!(c.secure(), None);
+    }
+
+    #[test]
+    fn format_insecure_dev() {
+        let c = Cookie::build(("foo", "bar")).same_site(SameSite::None).build();
+        assert_eq!(&c.insecure_dev().to_string(), "foo=bar; SameSite=None");
+
+        let mut c = Cookie::new("foo", "bar");
+        c.set_same_site(SameSite::None);
+        assert_eq!(&c.to_string(), "foo=bar; SameSite=None; Secure");
+        assert_eq!(&c.insecure_dev().to_string(), "foo=bar; SameSite=None");
+
+        c.set_partitioned(true);
+        c.set_path("/");
+        assert_eq!(&c.insecure_dev().to_string(), "foo=bar; SameSite=None; Partitioned; Path=/");
+        assert_eq!(&c.insecure_dev().stripped().to_string(), "foo=bar");
+    }
+
+    #[test]
+    fn present_attributes() {
+        let c = Cookie::parse("foo=bar").unwrap();
+        assert!(c.present_attributes().is_empty());
+
+        let c = Cookie::parse("foo=bar; Secure; HttpOnly; SameSite=Lax; Path=/; \
+            Domain=rust-lang.org; Max-Age=10; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+
+        let attrs = c.present_attributes();
+        assert_eq!(attrs.len(), 7);
+        assert!(attrs.contains(AttributeSet::SECURE | AttributeSet::HTTP_ONLY));
+        assert!(attrs.contains(AttributeSet::SAME_SITE | AttributeSet::PATH));
+        assert!(attrs.contains(AttributeSet::DOMAIN | AttributeSet::MAX_AGE));
+        assert!(attrs.contains(AttributeSet::EXPIRES));
+        assert!(!attrs.contains(AttributeSet::PARTITIONED));
+
+        // Unrecognized or invalid attributes are not present.
+        let c = Cookie::parse("foo=bar; SameSite=Bogus; Expires=never; Partitioned").unwrap();
+        assert_eq!(c.present_attributes(), AttributeSet::PARTITIONED);
+    }
+
+    #[test]
+    fn reborrow() {
+        let header = String::from("foo=bar; Path=/; Domain=.crates.io; Secure");
+        let c = Cookie::parse(header).unwrap();
+        let borrowed = c.reborrow();
+        assert!(c.eq_exact(&borrowed));
+        assert_eq!(borrowed.to_string(), c.to_string());
+        assert_eq!(borrowed.name().as_ptr(), c.name().as_ptr());
+        assert_eq!(borrowed.value().as_ptr(), c.value().as_ptr());
+        assert_eq!(borrowed.path().unwrap().as_ptr(), c.path().unwrap().as_ptr());
+        assert_eq!(borrowed.domain().unwrap().as_ptr(), c.domain().unwrap().as_ptr());
+
+        let mut c = Cookie::new(String::from("foo"), String::from("bar"));
+        c.set_path(String::from("/"));
+        let borrowed = c.reborrow();
+        assert!(c.eq_exact(&borrowed));
+        assert_eq!(borrowed.value().as_ptr(), c.value().as_ptr());
+        assert_eq!(borrowed.path().unwrap().as_ptr(), c.path().unwrap().as_ptr());
+    }
+
+    #[test]
+    fn format_single_digit_day() {
+        let time_str = "Fri, 05 Jan 2018 07:08:09 GMT";
+        let expires = parse_date(time_str, &crate::parse::FMT1).unwrap();
+        let cookie = Cookie::build(("foo", "bar")).expires(expires);
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Fri, 05 Jan 2018 07:08:09 GMT");
+
+        let expires = OffsetDateTime::from_unix_timestamp(2046301323).unwrap();
+        let cookie = Cookie::build(("foo", "bar")).expires(expires);
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Sun, 05 Nov 2034 01:02:03 GMT");
+    }
+
+    #[test]
+    #[cfg(any(feature = "private", feature = "signed"))]
+    fn value_base64_decoded() {
+        let c = Cookie::new("foo", "");
+        assert_eq!(c.value_base64_decoded(), Some(vec![]));
+
+        let c = Cookie::new("foo", "AAEC/w==");
+        assert_eq!(c.value_base64_decoded(), Some(vec![0, 1, 2, 255]));
+
+        // Missing padding, URL-safe alphabet, and garbage are rejected.
+        for value in &["AAEC/w", "AAEC_w==", "%%%", "AAEC/w== "] {
+            assert_eq!(Cookie::new("foo", *value).value_base64_decoded(), None);
+        }
+    }
+
+    #[test]
+    fn reemit_original() {
+        let raw = "foo=bar ;  HttpOnly;Domain=.crates.io; expires=Wed, 21 Oct 2015 07:28:00 GMT";
+        let c = Cookie::parse(raw).unwrap();
+        assert_eq!(c.reemit_original(), Some(raw));
+        assert_eq!(c.into_owned().reemit_original(), Some(raw));
+
+        let mutations: &[fn(&mut Cookie<'_>)] = &[
+            |c| c.set_name("baz"),
+            |c| c.set_value("baz"),
+            |c| c.set_http_only(None),
+            |c| c.set_secure(true),
+            |c| c.set_domain("crates.io"),
+            |c| c.set_path("/"),
+            |c| c.set_same_site(SameSite::Lax),
+            |c| c.set_max_age(Duration::seconds(1)),
+            |c| c.set_expires(None),
+            |c| c.set_partitioned(true),
+        ];
+
+        for mutate in mutations {
+            let mut c = Cookie::parse(raw).unwrap();
+            mutate(&mut c);
+            assert_eq!(c.reemit_original(), None);
+        }
+
+        // Setting a field to the value it already has isn't a modification.
+        let mut c = Cookie::parse(raw).unwrap();
+        c.set_http_only(true);
+        c.set_value("bar");
+        assert_eq!(c.reemit_original(), Some(raw));
+
+        #[cfg(feature = "percent-encode")] {
+            let raw = "foo%20bar=baz%20qux; Path=/";
+            let mut c = Cookie::parse_encoded(raw).unwrap();
+            assert_eq!(c.reemit_original(), Some(raw));
+            c.set_value("baz%20qux");
+            assert_eq!(c.reemit_original(), None);
+        }
+    }
+
+    #[test]
+    fn expiry_is_consistent() {
+        let now = OffsetDateTime::now_utc();
+        let cookie = |max_age: Option<Duration>, expires: Option<OffsetDateTime>| {
+            let mut c = Cookie::new("foo", "bar");
+            c.set_max_age(max_age);
+            c.set_expires(expires);
+            c
+        };
+
+        assert!(cookie(None, None).expiry_is_consistent());
+        assert!(cookie(Some(Duration::hours(1)), None).expiry_is_consistent());
+        assert!(cookie(None, Some(now)).expiry_is_consistent());
+
+        let hour = Duration::hours(1);
+        assert!(cookie(Some(hour), Some(now + hour)).expiry_is_consistent());
+        assert!(cookie(Some(hour), Some(now + hour - Duration::seconds(30))).expiry_is_consistent());
+        assert!(cookie(Some(hour), Some(now + hour + Duration::seconds(30))).expiry_is_consistent());
+        assert!(!cookie(Some(hour), Some(now + hour * 2)).expiry_is_consistent());
+        assert!(!cookie(Some(hour), Some(now)).expiry_is_consistent());
+        assert!(!cookie(Some(hour), Some(now - hour)).expiry_is_consistent());
+
+        assert!(cookie(Some(Duration::ZERO), Some(now - Duration::days(365))).expiry_is_consistent());
+        assert!(!cookie(Some(Duration::ZERO), Some(now + hour)).expiry_is_consistent());
+
+        let mut removal = Cookie::new("foo", "bar");
+        removal.make_removal();
+        assert!(removal.expiry_is_consistent());
+
+        let huge = Duration::MAX;
+        assert!(!cookie(Some(huge), Some(now)).expiry_is_consistent());
+    }
+
+    #[test]
+    fn build_with_options() {
+        struct Config {
+            path: Option<&'static str>,
+            domain: Option<String>,
+            max_age: Option<Duration>,
+            same_site: Option<SameSite>,
+            secure: Option<bool>,
+        }
+
+        let build = |config: Config| {
+            Cookie::build(("foo", "bar"))
+                .path_opt(config.path)
+                .domain_opt(config.domain)
+                .max_age_opt(config.max_age)
+                .same_site_opt(config.same_site)
+                .secure_opt(config.secure)
+                .build()
+        };
+
+        let c = build(Config {
+            path: Some("/"),
+            domain: None,
+            max_age: Some(Duration::seconds(10)),
+            same_site: None,
+            secure: Some(true),
+        });
+
+        assert_eq!(&c.to_string(), "foo=bar; Secure; Path=/; Max-Age=10");
+
+        let c = build(Config {
+            path: None,
+            domain: Some("rust-lang.org".into()),
+            max_age: None,
+            same_site: Some(SameSite::Lax),
+            secure: None,
+        });
+
+        assert_eq!(&c.to_string(), "foo=bar; SameSite=Lax; Domain=rust-lang.org");
+
+        // `None` leaves previously set values untouched.
+        let c = Cookie::build(("foo", "bar")).path("/").path_opt(None::<&str>).build();
+        assert_eq!(c.path(), Some("/"));
+    }
+
+    #[test]
+    fn format_verbatim() {
+        let c = Cookie::parse("a=b; SameSite=None").unwrap();
+        assert_eq!(c.secure(), None);
+        assert_eq!(&c.to_string(), "a=b; SameSite=None; Secure");
+        assert_eq!(&c.verbatim().to_string(), "a=b; SameSite=None");
+        assert_eq!(&c.into_owned().verbatim().to_string(), "a=b; SameSite=None");
+
+        let raw = "a=b ;  partitioned; PATH=/;foo=bar";
+        let mut c = Cookie::parse(raw).unwrap();
+        assert_eq!(&c.verbatim().to_string(), raw);
+        c.set_path("/app");
+        assert_eq!(&c.verbatim().to_string(), "a=b; Partitioned; Secure; Path=/app; foo=bar");
+
+        let c = Cookie::new("a", "b");
+        assert_eq!(&c.verbatim().to_string(), "a=b");
+
+        // Verbatim display only applies when no other option is set.
+        let c = Cookie::parse("a b=c;SameSite=None; Path=/").unwrap();
+        assert_eq!(&c.verbatim().to_string(), "a b=c;SameSite=None; Path=/");
+        assert_eq!(&c.stripped().verbatim().to_string(), "a b=c");
+        assert_eq!(&c.verbatim().stripped().to_string(), "a b=c");
+        assert_eq!(&c.verbatim().insecure_dev().to_string(), "a b=c; SameSite=None; Path=/");
+        assert_eq!(&c.verbatim().iso_expires().to_string(), "a b=c; SameSite=None; Secure; Path=/");
+
+        #[cfg(feature = "percent-encode")] {
+            assert_eq!(&c.encoded().verbatim().to_string(), "a%20b=c; SameSite=None; Secure; Path=/");
+            assert_eq!(&c.verbatim().encoded().stripped().to_string(), "a%20b=c");
+        }
+    }
+
+    #[test]
+    fn eq_exact() {
+        let a = Cookie::build(("foo", "bar")).domain("example.com").path("/a").build();
+        let b = Cookie::build(("foo", "bar")).domain("EXAMPLE.com").path("/a").build();
+        assert_eq!(a, b);
+        assert!(!a.eq_exact(&b));
+
+        let b = Cookie::build(("foo", "bar")).domain("example.com").path("/A").build();
+        assert_eq!(a, b);
+        assert!(!a.eq_exact(&b));
+
+        let b = Cookie::build(("foo", "bar")).domain(".example.com").path("/a").build();
+        assert_eq!(a, b);
+        assert!(!a.eq_exact(&b));
+
+        let b = Cookie::build(a.clone()).same_site(SameSite::Lax).build();
+        assert_eq!(a, b);
+        assert!(!a.eq_exact(&b));
+
+        let b = Cookie::parse("foo=bar; Domain=example.com; Path=/a").unwrap();
+        assert_eq!(a, b);
+        assert!(a.eq_exact(&b));
+        assert!(b.eq_exact(&a));
+    }
+
+    #[test]
+    fn domain_has_leading_dot() {
+        let mut c = Cookie::new("name", "value");
+        assert!(!c.domain_has_leading_dot());
+
+        c.set_domain(".rust-lang.org");
+        assert!(c.domain_has_leading_dot());
+        assert_eq!(c.domain(), Some("rust-lang.org"));
+
+        c.set_domain("rust-lang.org");
+        assert!(!c.domain_has_leading_dot());
+
+        c.set_domain(".");
+        assert!(c.domain_has_leading_dot());
+        assert_eq!(c.domain(), Some(""));
+
+        let c = Cookie::parse("a=b; Domain=..rust-lang.org").unwrap();
+        assert!(c.domain_has_leading_dot());
+        assert!(c.clone().into_owned().domain_has_leading_dot());
+
+        let c = Cookie::parse("a=b; Domain=rust-lang.org.").unwrap();
+        assert!(!c.domain_has_leading_dot());
+
+        let c = Cookie::parse("a=b; Domain=").unwrap();
+        assert!(!c.domain_has_leading_dot());
+    }
+
+    #[test]
+    fn bare() {
+        let c = Cookie::build(("foo", "bar"))
+            .domain("rust-lang.org")
+            .path("/")
+            .secure(true)
+            .http_only(true)
+            .same_site(SameSite::None)
+            .partitioned(true)
+            .max_age(Duration::hours(1))
+            .expires(OffsetDateTime::now_utc())
+            .extension("Priority", Some("High")).unwrap()
+            .build();
+
+        let bare = c.clone().bare();
+        assert_eq!(bare.to_string(), "foo=bar");
+        assert!(bare.present_attributes().is_empty());
+        assert_eq!(bare.extensions().count(), 0);
+        assert_eq!(bare.expires(), None);
+
+        let parsed = Cookie::parse("foo=bar; SameSite=None; Path=/; Foo").unwrap();
+        assert_eq!(parsed.bare().to_string(), "foo=bar");
+        assert_eq!(Cookie::new("foo", "bar").bare().to_string(), "foo=bar");
+
+        // The name and value of a parsed cookie still borrow from the string.
+        let string = String::from("foo=bar; Secure");
+        let bare = Cookie::parse(&*string).unwrap().bare();
+        assert_eq!(bare.name_raw(), Some("foo"));
+        assert_eq!(bare.value_raw(), Some("bar"));
+    }
+
+    #[test]
+    fn quoted_value() {
+        for value in &["", "bar", "a b", "a=b"] {
+            let c = Cookie::build("foo").quoted_value(*value).build();
+            assert_eq!(c.value(), format!("\"{}\"", value));
+            assert_eq!(c.value_trimmed(), *value);
+
+            let parsed = Cookie::parse(c.to_string()).unwrap();
+            assert_eq!(parsed.value(), c.value());
+            assert_eq!(parsed.value_trimmed(), *value);
+        }
+
+        let mut c = Cookie::new("foo", "bar");
+        c.set_quoted_value("\"bar");
+        assert_eq!(c.value(), "\"bar");
+        c.set_quoted_value("a\"b");
+        assert_eq!(c.value(), "a\"b");
+        c.set_quoted_value(String::from("owned"));
+        assert_eq!(c.to_string(), "foo=\"owned\"");
+    }
+
+    #[test]
+    fn iso_expires_roundtrip() {
+        let expires = parse_date("Wed, 21 Oct 2015 07:28:00 GMT", &crate::parse::FMT1).unwrap();
+        let c = Cookie::build(("foo", "bar")).path("/").expires(expires).build();
+        let iso = c.iso_expires().to_string();
+        assert_eq!(iso, "foo=bar; Path=/; Expires=2015-10-21T07:28:00Z");
+
+        let parsed = Cookie::parse(&*iso).unwrap();
+        assert_eq!(parsed.expires_datetime(), Some(expires));
+        assert_eq!(parsed.path(), Some("/"));
+        assert_eq!(parsed.to_string(), c.to_string());
+        assert_eq!(parsed.iso_expires().to_string(), iso);
+
+        // Non-UTC offsets and fractional seconds are accepted when parsing.
+        let c = Cookie::parse("foo=bar; Expires=2015-10-21T09:28:00.5+02:00").unwrap();
+        assert_eq!(c.expires_datetime(), Some(expires + Duration::milliseconds(500)));
+        assert_eq!(c.iso_expires().to_string(), "foo=bar; Expires=2015-10-21T07:28:00.5Z");
+
+        // Session cookies have no `Expires` to display.
+        let c = Cookie::build(("foo", "bar")).expires(Expiration::Session).build();
+        assert_eq!(c.iso_expires().to_string(), "foo=bar");
+    }
+
+    #[test]
+    fn set_domain_normalized() {
+        let mut cookie = Cookie::new("foo", "bar");
+        cookie.set_domain_normalized("EXAMPLE.COM");
+        assert_eq!(cookie.domain(), Some("example.com"));
+
+        cookie.set_domain_normalized(".Sub.Example.com");
+        assert_eq!(cookie.domain(), Some("sub.example.com"));
+        assert!(cookie.domain_has_leading_dot());
+
+        cookie.set_domain("EXAMPLE.COM");
+        assert_eq!(cookie.domain(), Some("EXAMPLE.COM"));
+    }
+
+    #[test]
+    fn expiration_constructors() {
+        let time = time::macros::datetime!(2015-10-21 7:28:00 UTC);
+        let cookie = Cookie::build(("foo", "bar")).expires(Expiration::at(time));
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Wed, 21 Oct 2015 07:28:00 GMT");
+
+        let cookie = Cookie::build(("foo", "bar")).expires(Expiration::session());
+        assert_eq!(cookie.inner().expires(), Some(Expiration::Session));
+        assert_eq!(&cookie.to_string(), "foo=bar");
+
+        let cookie = Cookie::build(("foo", "bar")).expires(Expiration::never());
+        assert_eq!(cookie.inner().expires(), Some(Expiration::never()));
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+    #[test]
+    fn is_expired() {
+        use time::macros::datetime;
+
+        let at = datetime!(2020-06-01 12:00 UTC);
+        let session = Cookie::new("foo", "bar");
+        assert!(!session.is_expired_at(at));
+        assert!(!session.is_expired());
+
+        let session = Cookie::build(("foo", "bar")).expires(Expiration::Session).build();
+        assert!(!session.is_expired_at(at));
+
+        let mut cookie = Cookie::new("foo", "bar");
+        cookie.set_expires(at);
+        assert!(cookie.is_expired_at(at));
+        assert!(cookie.is_expired_at(at + Duration::seconds(1)));
+        assert!(!cookie.is_expired_at(at - Duration::seconds(1)));
+        assert!(cookie.is_expired());
+
+        cookie.set_max_age(Duration::minutes(5));
+        assert!(!cookie.is_expired_at(at + Duration::days(365)));
+        assert!(!cookie.is_expired());
+
+        cookie.set_max_age(Duration::ZERO);
+        assert!(cookie.is_expired_at(at - Duration::days(365)));
+
+        cookie.set_max_age(Duration::seconds(-1));
+        assert!(cookie.is_expired_at(at));
+
+        cookie.unset_expires();
+        cookie.set_max_age(Duration::ZERO);
+        assert!(cookie.is_expired());
+
+        let removal = Cookie::parse("foo=; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT");
+        assert!(removal.unwrap().is_expired());
+
+        let far = Cookie::parse("foo=bar; Expires=Fri, 31 Dec 9999 23:59:59 GMT").unwrap();
+        assert!(!far.is_expired());
+    }
+
+    #[test]
+    fn trim_value() {
+        let mut cookie = Cookie::new("foo", "  token  ");
+        cookie.trim_value();
+        assert_eq!(cookie.value(), "token");
+        assert_eq!(&cookie.to_string(), "foo=token");
+
+        let mut cookie = Cookie::new("foo", "\t a b \r\n");
+        cookie.trim_value();
+        assert_eq!(cookie.value(), "a b");
+
+        let mut cookie = Cookie::new("foo", "   ");
+        cookie.trim_value();
+        assert_eq!(cookie.value(), "");
+
+        let mut cookie = Cookie::parse("foo=bar; Path=/").unwrap();
+        cookie.trim_value();
+        assert_eq!(cookie.value(), "bar");
+    }
+
+    #[test]
+    fn builder_same_site_option() {
+        let builder = Cookie::build(("foo", "bar")).same_site(SameSite::None);
+        assert_eq!(builder.inner().same_site(), Some(SameSite::None));
+        assert_eq!(&builder.to_string(), "foo=bar; SameSite=None; Secure");
+
+        let builder = builder.same_site(Some(SameSite::Strict));
+        assert_eq!(builder.inner().same_site(), Some(SameSite::Strict));
+
+        let cookie = builder.same_site(None).build();
+        assert_eq!(cookie.same_site(), None);
+        assert_eq!(&cookie.to_string(), "foo=bar");
+
+        let unset: Option<SameSite> = None;
+        let cookie = Cookie::build(("foo", "bar")).same_site(unset).build();
+        assert_eq!(cookie.same_site(), None);
+    }
+
+    #[test]
+    fn matches_domain() {
+        let cookie = Cookie::build(("foo", "bar")).domain("example.com").build();
+        assert!(cookie.matches_domain("example.com"));
+        assert!(cookie.matches_domain("EXAMPLE.com"));
+        assert!(cookie.matches_domain("www.example.com"));
+        assert!(cookie.matches_domain("a.b.example.com"));
+        assert!(!cookie.matches_domain("wwwexample.com"));
+        assert!(!cookie.matches_domain("example.org"));
+        assert!(!cookie.matches_domain("com"));
+        assert!(!cookie.matches_domain(".com"));
+        assert!(!cookie.matches_domain(""));
+        assert!(!cookie.matches_domain("é.example.co"));
+
+        // A leading `.` is ignored.
+        let cookie = Cookie::build(("foo", "bar")).domain(".Example.com").build();
+        assert!(cookie.matches_domain("example.com"));
+        assert!(cookie.matches_domain("www.example.com"));
+        assert!(!cookie.matches_domain(".www.example.co"));
+
+        let cookie = Cookie::build(("foo", "bar")).domain("www.example.com").build();
+        assert!(!cookie.matches_domain("example.com"));
+
+        // Hosts that are IP addresses only match exactly.
+        let cookie = Cookie::build(("foo", "bar")).domain("0.0.1").build();
+        assert!(!cookie.matches_domain("127.0.0.1"));
+        assert!(!cookie.matches_domain("10.0.0.1"));
+
+        let cookie = Cookie::build(("foo", "bar")).domain("127.0.0.1").build();
+        assert!(cookie.matches_domain("127.0.0.1"));
+        assert!(!cookie.matches_domain("127.0.0.2"));
+
+        let cookie = Cookie::build(("foo", "bar")).domain("[::1]").build();
+        assert!(cookie.matches_domain("[::1]"));
+
+        let cookie = Cookie::build(("foo", "bar")).domain("1]").build();
+        assert!(!cookie.matches_domain("[::1]"));
+
+        // Host-only and empty-domain cookies match nothing.
+        assert!(!Cookie::new("foo", "bar").matches_domain("example.com"));
+        let cookie = Cookie::build(("foo", "bar")).domain("").build();
+        assert!(!cookie.matches_domain(""));
+        assert!(!cookie.matches_domain("example.com"));
+    }
+
+    #[test]
+    fn host_prefix_violations() {
+        use crate::prefix::HostPrefixViolation::*;
+
+        let cookie = Cookie::build(("__Host-name", "value"))
+            .secure(false)
+            .path("/foo")
+            .domain("example.com")
+            .build();
+
+        assert_eq!(cookie.host_prefix_violations(), [MissingSecure, PathNotRoot, HasDomain]);
+
+        let cookie = Cookie::build(("__Host-name", "value")).secure(true).build();
+        assert_eq!(cookie.host_prefix_violations(), [PathNotRoot]);
+
+        let cookie = Cookie::build(("__Host-name", "value")).path("/").domain("a.com").build();
+        assert_eq!(cookie.host_prefix_violations(), [MissingSecure, HasDomain]);
+
+        let cookie = Cookie::build(("__Host-name", "value")).secure(true).path("/").build();
+        assert!(cookie.host_prefix_violations().is_empty());
+        assert_eq!(PathNotRoot.to_string(), "Path is not /");
+
+        // Conforming to the prefix clears all violations.
+        use crate::prefix::{Host, Prefix};
+        let cookie = Host::conform(Cookie::build(("name", "value")).domain("a.com").build());
+        assert!(cookie.host_prefix_violations().is_empty());
+    }
+
+    #[test]
+    fn matches_path() {
+        let cookie = Cookie::build(("name", "value")).path("/foo").build();
+        assert!(cookie.matches_path("/foo"));
+        assert!(cookie.matches_path("/foo/"));
+        assert!(cookie.matches_path("/foo/bar"));
+        assert!(!cookie.matches_path("/foobar"));
+        assert!(!cookie.matches_path("/fo"));
+        assert!(!cookie.matches_path("/Foo"));
+        assert!(!cookie.matches_path("/"));
+
+        let cookie = Cookie::build(("name", "value")).path("/foo/").build();
+        assert!(cookie.matches_path("/foo/"));
+        assert!(cookie.matches_path("/foo/bar"));
+        assert!(!cookie.matches_path("/foo"));
+
+        for cookie in [Cookie::new("name", "value"), Cookie::build(("a", "b")).path("/").build()] {
+            assert!(cookie.matches_path("/"));
+            assert!(cookie.matches_path("/foo"));
+            assert!(cookie.matches_path("/foo/bar/"));
+        }
+    }
+
+    #[test]
+    fn attributes_string() {
+        let cookie = Cookie::build(("name", "value"))
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .secure(true)
+            .path("/foo")
+            .domain("www.rust-lang.org")
+            .max_age(Duration::seconds(10))
+            .expires(time::macros::datetime!(2015-10-21 7:28:00 UTC))
+            .build();
+
+        let string = cookie.to_string();
+        let suffix = string.strip_prefix("name=value").unwrap();
+        assert_eq!(cookie.attributes_string(), suffix);
+
+        let cookie = Cookie::parse("a=b; SameSite=None; Foo=bar; Baz").unwrap();
+        assert_eq!(cookie.attributes_string(), "; SameSite=None; Secure; Foo=bar; Baz");
+        assert!(cookie.to_string().ends_with(&cookie.attributes_string()));
+
+        assert_eq!(Cookie::new("a", "b").attributes_string(), "");
+    }
+
+    #[test]
+    fn expiration_system_time() {
+        use std::convert::TryFrom;
+        use std::time::{SystemTime, UNIX_EPOCH};
+        use crate::ExpirationError;
+
+        let expires = Expiration::from_system_time(UNIX_EPOCH);
+        assert_eq!(expires, Expiration::DateTime(OffsetDateTime::UNIX_EPOCH));
+        assert_eq!(SystemTime::try_from(expires), Ok(UNIX_EPOCH));
+
+        let now = SystemTime::now();
+        let expires = Expiration::from_system_time(now);
+        assert_eq!(SystemTime::try_from(expires), Ok(now));
+
+        let before = UNIX_EPOCH - std::time::Duration::from_secs(86400);
+        let expires = Expiration::from_system_time(before);
+        assert_eq!(expires.datetime().unwrap().unix_timestamp(), -86400);
+        assert_eq!(SystemTime::try_from(expires), Ok(before));
+
+        assert_eq!(SystemTime::try_from(Expiration::Session), Err(ExpirationError::Session));
+
+        let mut cookie = Cookie::new("name", "value");
+        cookie.set_expires(Expiration::from_system_time(now));
+        assert_eq!(cookie.expires(), Some(Expiration::from_system_time(now)));
+
+        // The blanket conversion from `Into<Option<OffsetDateTime>>` remains.
+        fn expiration<T: Into<Option<OffsetDateTime>>>(value: T) -> Expiration {
+            Expiration::from(value)
+        }
+
+        assert_eq!(expiration(None), Expiration::Session);
+        let epoch = Expiration::from_system_time(UNIX_EPOCH);
+        assert_eq!(expiration(OffsetDateTime::UNIX_EPOCH), epoch);
+    }
+
+    #[test]
+    fn validate() {
+        use crate::ValidationError::*;
+
+        assert_eq!(Cookie::new("session_id", "abc=123").validate(), Ok(()));
+        assert_eq!(Cookie::new("a", "").validate(), Ok(()));
+        assert_eq!(Cookie::new("a", "\"b\"").validate(), Ok(()));
+
+        assert_eq!(Cookie::new("", "value").validate(), Err(EmptyName));
+        assert_eq!(Cookie::new("my name", "value").validate(),
+            Err(InvalidName { index: 2, byte: b' ' }));
+        assert_eq!(Cookie::new("a=b", "value").validate(),
+            Err(InvalidName { index: 1, byte: b'=' }));
+        assert_eq!(Cookie::new("a\x7f", "value").validate(),
+            Err(InvalidName { index: 1, byte: 0x7f }));
+        assert_eq!(Cookie::new("bad name", "bad;value").validate(),
+            Err(InvalidName { index: 3, byte: b' ' }));
+
+        assert_eq!(Cookie::new("name", "a;b").validate(),
+            Err(InvalidValue { index: 1, byte: b';' }));
+        assert_eq!(Cookie::new("name", "\"a;b\"").validate(),
+            Err(InvalidValue { index: 2, byte: b';' }));
+        assert_eq!(Cookie::new("name", "\"ab").validate(),
+            Err(InvalidValue { index: 0, byte: b'"' }));
+        assert_eq!(Cookie::new("name", "ab\n").validate(),
+            Err(InvalidValue { index: 2, byte: b'\n' }));
+        assert_eq!(Cookie::new("name", "é").validate(),
+            Err(InvalidValue { index: 0, byte: 0xc3 }));
+
+        // Parsing doesn't validate.
+        let cookie = Cookie::parse("name=a b").unwrap();
+        assert_eq!(cookie.validate(), Err(InvalidValue { index: 1, byte: b' ' }));
+    }
+
+    #[test]
+    fn debug_attributes() {
+        let absent = Cookie::new("a", "b").debug_attributes();
+        assert!(absent.contains("SameSite=unset"));
+        assert!(absent.contains("Secure=unset;"));
+        assert!(absent.contains("HttpOnly=unset"));
+        assert!(absent.contains("Expires=unset"));
+
+        let cookie = Cookie::parse("a=b; SameSite=None; HttpOnly; Max-Age=0; Foo; Bar=baz")
+            .unwrap();
+
+        let debug = cookie.debug_attributes();
+        assert!(debug.contains("SameSite=None"));
+        assert!(debug.contains("Secure=unset (implied)"));
+        assert!(debug.contains("HttpOnly=true"));
+        assert!(debug.contains("Max-Age=0"));
+        assert!(debug.ends_with("; Foo=present; Bar=\"baz\""));
+        assert_ne!(debug, absent);
+
+        let cookie = Cookie::build(("a", "b"))
+            .secure(false)
+            .partitioned(false)
+            .expires(time::macros::datetime!(2015-10-21 7:28:00 UTC))
+            .build();
+
+        let debug = cookie.debug_attributes();
+        assert!(debug.contains("Secure=false"));
+        assert!(debug.contains("Partitioned=false"));
+        assert!(debug.contains("Domain=unset"));
+        assert!(debug.contains("Expires=Wed, 21 Oct 2015 07:28:00 GMT"));
+
+        // None of these states are visible in the wire format.
+        assert_eq!(cookie.to_string(), "a=b; Expires=Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(Cookie::build(("a", "b")).expires(None).build().to_string(), "a=b");
+        assert!(Cookie::build(("a", "b")).expires(None).build()
+            .debug_attributes()
+            .contains("Expires=Session"));
+    }
+
+    #[test]
+    #[cfg(feature = "percent-encode")]
+    fn encoded_with() {
+        use crate::encoding::{AsciiSet, DEFAULT_ENCODE_SET};
+        use percent_encoding::NON_ALPHANUMERIC;
+
+        const DOTS: &AsciiSet = &DEFAULT_ENCODE_SET.add(b'.');
+        const SPACES: &AsciiSet = &percent_encoding::CONTROLS.add(b' ');
+
+        let cookie = Cookie::build(("my.name", "v1.0; ok")).domain("rust-lang.org").build();
+        assert_eq!(cookie.encoded_with(DEFAULT_ENCODE_SET).to_string(),
+            cookie.encoded().to_string());
+        assert_eq!(cookie.encoded_with(DOTS).to_string(),
+            "my%2Ename=v1%2E0%3B%20ok; Domain=rust-lang.org");
+        assert_eq!(cookie.encoded_with(DOTS).stripped().to_string(), "my%2Ename=v1%2E0%3B%20ok");
+        assert_eq!(cookie.stripped().encoded_with(SPACES).to_string(), "my.name=v1.0;%20ok");
+        assert_eq!(cookie.encoded_with(NON_ALPHANUMERIC).stripped().to_string(),
+            "my%2Ename=v1%2E0%3B%20ok");
+
+        // The encoded cookie decodes to the original.
+        let encoded = cookie.encoded_with(DOTS).to_string();
+        let decoded = Cookie::parse_encoded(encoded).unwrap();
+        assert_eq!(decoded.name_value(), ("my.name", "v1.0; ok"));
+    }
+
+    #[test]
+    fn add_extension_rejects_delimiters() {
+        use crate::ValidationError::InvalidExtension;
+
+        let add = |name: &'static str, value: Option<&'static str>| {
+            let mut cookie = Cookie::new("a", "b");
+            let result = cookie.add_extension(name, value);
+            assert_eq!(cookie.extensions().count(), result.is_ok() as usize);
+            result
+        };
+
+        assert_eq!(add("X", Some("1")), Ok(()));
+        assert_eq!(add("X", Some("")), Ok(()));
+        assert_eq!(add("X", None), Ok(()));
+        assert_eq!(add("X", Some("1; Domain=evil.com")), Err(InvalidExtension));
+        assert_eq!(add("X", Some("1\r\nSet-Cookie: evil=1")), Err(InvalidExtension));
+        assert_eq!(add("X; Domain", None), Err(InvalidExtension));
+        assert_eq!(add("X=1", None), Err(InvalidExtension));
+        assert_eq!(add("X\n", None), Err(InvalidExtension));
+        assert_eq!(add("", None), Err(InvalidExtension));
+
+        let result = Cookie::build(("a", "b")).extension("X", Some("1; Secure"));
+        assert_eq!(result.unwrap_err(), InvalidExtension);
+    }
+
     #[test]
     #[ignore]
     fn format_date_wraps() {
@@ -1818,6 +4249,33 @@ mod tests {
         assert_eq!(cookie.name_value(), ("foo !%?=", "bar;;, a"));
     }
 
+    #[test]
+    #[cfg(feature = "percent-encode")]
+    fn was_decoded() {
+        let cookie = Cookie::parse_encoded("foo=bar%20baz; Path=/a%20b").unwrap();
+        assert_eq!(cookie.value(), "bar baz");
+        assert_eq!(cookie.path(), Some("/a%20b"));
+        assert!(cookie.was_decoded());
+        assert!(cookie.clone().into_owned().was_decoded());
+
+        let cookie = Cookie::parse_encoded("foo%3D=bar").unwrap();
+        assert_eq!(cookie.name(), "foo=");
+        assert!(cookie.was_decoded());
+
+        let cookie = Cookie::parse_encoded("foo=bar; Path=/a%20b").unwrap();
+        assert_eq!(cookie.value(), "bar");
+        assert!(!cookie.was_decoded());
+
+        let cookie = Cookie::parse("foo=bar%20baz").unwrap();
+        assert!(!cookie.was_decoded());
+
+        let decoded: Vec<_> = Cookie::split_parse_encoded("a=1; b=%32")
+            .map(|c| c.unwrap().was_decoded())
+            .collect();
+        assert_eq!(decoded, [false, true]);
+        assert!(!Cookie::new("foo", "bar%20baz").was_decoded());
+    }
+
     #[test]
     fn split_parse() {
         let cases = [
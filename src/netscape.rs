@@ -0,0 +1,245 @@
+use time::OffsetDateTime;
+
+use crate::{Cookie, ParseError};
+
+/// The prefix curl adds to the domain field of `HttpOnly` cookies.
+const HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+/// Formats `cookie` as a single line, without a trailing newline, of a
+/// Netscape `cookies.txt` file.
+///
+/// A line consists of seven tab-separated fields:
+///
+/// ```text
+/// domain  include_subdomains  path  secure  expiration  name  value
+/// ```
+///
+/// The fields are populated as follows:
+///
+///   * `domain` is the cookie's domain as stored, including any leading `.`,
+///     or empty if the cookie has no domain. If the cookie is `HttpOnly`, the
+///     field is prefixed with `#HttpOnly_`, as is done by curl.
+///   * `include_subdomains` is `TRUE` if the domain has a leading `.`, as
+///     determined by [`Cookie::domain_has_leading_dot()`], and `FALSE`
+///     otherwise.
+///   * `path` is the cookie's path or `/` if the cookie has no path.
+///   * `secure` is `TRUE` if [`Cookie::secure()`] is `Some(true)` and `FALSE`
+///     otherwise.
+///   * `expiration` is the Unix timestamp of
+///     [`Cookie::expires_datetime()`], or `0` for session cookies.
+///   * `name` and `value` are the cookie's name and value, verbatim.
+///
+/// All other attributes are not represented and are thus lost.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::{Cookie, netscape};
+/// use cookie::time::OffsetDateTime;
+///
+/// let cookie = Cookie::build(("name", "value"))
+///     .domain(".rust-lang.org")
+///     .path("/docs")
+///     .secure(true)
+///     .http_only(true)
+///     .expires(OffsetDateTime::from_unix_timestamp(1700000000).unwrap())
+///     .build();
+///
+/// let line = netscape::to_netscape_line(&cookie);
+/// assert_eq!(line, "#HttpOnly_.rust-lang.org\tTRUE\t/docs\tTRUE\t1700000000\tname\tvalue");
+/// ```
+pub fn to_netscape_line(cookie: &Cookie<'_>) -> String {
+    let string = cookie.cookie_string.as_ref();
+    let domain = cookie.domain.as_ref().map_or("", |d| d.to_str(string));
+    let expires = cookie.expires_datetime().map_or(0, |t| t.unix_timestamp());
+    let flag = |value: bool| if value { "TRUE" } else { "FALSE" };
+
+    format!("{}{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        if cookie.http_only() == Some(true) { HTTP_ONLY_PREFIX } else { "" },
+        domain,
+        flag(cookie.domain_has_leading_dot()),
+        cookie.path().unwrap_or("/"),
+        flag(cookie.secure() == Some(true)),
+        expires,
+        cookie.name(),
+        cookie.value())
+}
+
+/// Parses a single line of a Netscape `cookies.txt` file, as written by
+/// [`to_netscape_line()`] or curl, into a `Cookie`. A trailing `\n` or `\r\n`
+/// is ignored.
+///
+/// The `domain` field, less any `#HttpOnly_` prefix, becomes the cookie's
+/// domain. If `include_subdomains` is `TRUE` and the domain has no leading
+/// `.`, one is added so that the cookie is written back unchanged by
+/// [`to_netscape_line()`]. The `HttpOnly` and `Secure` attributes are set only
+/// if present or `TRUE`, respectively, and an `expiration` of `0` denotes a
+/// session cookie with no `Expires` attribute. Boolean fields are
+/// case-insensitive.
+///
+/// # Errors
+///
+/// Returns [`ParseError::MissingPair`] if the line has fewer than seven
+/// fields, [`ParseError::EmptyName`] if the name is empty, and
+/// [`ParseError::InvalidExpires`] if `expiration` is not a valid Unix
+/// timestamp. Comment and blank lines are not valid cookie lines; use
+/// [`from_netscape_file()`] to parse an entire file.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::netscape;
+///
+/// let line = "#HttpOnly_.rust-lang.org\tTRUE\t/docs\tFALSE\t0\tname\tvalue";
+/// let cookie = netscape::from_netscape_line(line).unwrap();
+/// assert_eq!(cookie.name_value(), ("name", "value"));
+/// assert_eq!(cookie.domain(), Some("rust-lang.org"));
+/// assert!(cookie.domain_has_leading_dot());
+/// assert_eq!(cookie.path(), Some("/docs"));
+/// assert_eq!(cookie.http_only(), Some(true));
+/// assert_eq!(cookie.secure(), None);
+/// assert_eq!(cookie.expires(), None);
+/// ```
+pub fn from_netscape_line(line: &str) -> Result<Cookie<'static>, ParseError> {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let (http_only, line) = match line.strip_prefix(HTTP_ONLY_PREFIX) {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let mut fields = line.splitn(7, '\t');
+    let mut next = || fields.next().ok_or(ParseError::MissingPair);
+    let (domain, subdomains, path, secure) = (next()?, next()?, next()?, next()?);
+    let (expires, name, value) = (next()?, next()?, next()?);
+    if name.is_empty() {
+        return Err(ParseError::EmptyName);
+    }
+
+    let mut cookie = Cookie::new(name.to_string(), value.to_string());
+    if !domain.is_empty() {
+        match subdomains.eq_ignore_ascii_case("TRUE") && !domain.starts_with('.') {
+            true => cookie.set_domain(format!(".{}", domain)),
+            false => cookie.set_domain(domain.to_string()),
+        }
+    }
+
+    cookie.set_path(path.to_string());
+    if secure.eq_ignore_ascii_case("TRUE") {
+        cookie.set_secure(true);
+    }
+
+    if http_only {
+        cookie.set_http_only(true);
+    }
+
+    let timestamp = expires.parse::<i64>().map_err(|_| ParseError::InvalidExpires)?;
+    if timestamp != 0 {
+        let time = OffsetDateTime::from_unix_timestamp(timestamp)
+            .map_err(|_| ParseError::InvalidExpires)?;
+
+        cookie.set_expires(time);
+    }
+
+    Ok(cookie)
+}
+
+/// Parses every cookie line in `file`, the contents of a Netscape
+/// `cookies.txt` file, as if by [`from_netscape_line()`]. Blank lines and
+/// comment lines, those beginning with `#` but not `#HttpOnly_`, are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::netscape;
+///
+/// let file = "# Netscape HTTP Cookie File\n\
+///     \n\
+///     .rust-lang.org\tTRUE\t/\tTRUE\t0\ta\tb\n\
+///     #HttpOnly_crates.io\tFALSE\t/\tFALSE\t0\tc\td\n";
+///
+/// let cookies = netscape::from_netscape_file(file)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(cookies.len(), 2);
+/// assert_eq!(cookies[0].name_value(), ("a", "b"));
+/// assert_eq!(cookies[1].name_value(), ("c", "d"));
+/// assert_eq!(cookies[1].http_only(), Some(true));
+/// ```
+pub fn from_netscape_file(
+    file: &str
+) -> impl Iterator<Item = Result<Cookie<'static>, ParseError>> + '_ {
+    file.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| !line.starts_with('#') || line.starts_with(HTTP_ONLY_PREFIX))
+        .map(from_netscape_line)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_netscape_file, from_netscape_line, to_netscape_line};
+    use crate::{Cookie, ParseError};
+    use time::OffsetDateTime;
+
+    #[test]
+    fn roundtrip_file() {
+        let expires = OffsetDateTime::from_unix_timestamp(2000000000).unwrap();
+        let cookies = [
+            Cookie::build(("session", "abc123"))
+                .domain(".example.com")
+                .path("/")
+                .secure(true)
+                .http_only(true)
+                .build(),
+            Cookie::build(("pref", "dark"))
+                .domain("example.com")
+                .path("/settings")
+                .expires(expires)
+                .build(),
+            Cookie::build(("empty", ""))
+                .domain("sub.example.com")
+                .path("/")
+                .build(),
+        ];
+
+        let lines: Vec<_> = cookies.iter().map(to_netscape_line).collect();
+        assert_eq!(lines, [
+            "#HttpOnly_.example.com\tTRUE\t/\tTRUE\t0\tsession\tabc123",
+            "example.com\tFALSE\t/settings\tFALSE\t2000000000\tpref\tdark",
+            "sub.example.com\tFALSE\t/\tFALSE\t0\tempty\t",
+        ]);
+
+        let file = format!("# Netscape HTTP Cookie File\n# comment\n\n{}\r\n", lines.join("\n"));
+        let parsed = from_netscape_file(&file).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(parsed.len(), cookies.len());
+        for (parsed, cookie) in parsed.iter().zip(cookies.iter()) {
+            assert!(parsed.eq_exact(cookie), "{} != {}", parsed, cookie);
+            assert_eq!(to_netscape_line(parsed), to_netscape_line(cookie));
+        }
+    }
+
+    #[test]
+    fn parse_lines() {
+        // curl may omit the leading dot of an `include_subdomains` domain.
+        let cookie = from_netscape_line("example.com\ttrue\t/\tfalse\t0\ta\tb").unwrap();
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert!(cookie.domain_has_leading_dot());
+
+        let cookie = from_netscape_line("\tFALSE\t/\tFALSE\t0\ta\tb=c\td\n").unwrap();
+        assert_eq!(cookie.domain(), None);
+        assert_eq!(cookie.name_value(), ("a", "b=c\td"));
+
+        let cookie = Cookie::build(("a", "b")).domain("example.com").build();
+        assert_eq!(to_netscape_line(&cookie), "example.com\tFALSE\t/\tFALSE\t0\ta\tb");
+
+        assert_eq!(from_netscape_line(""), Err(ParseError::MissingPair));
+        assert_eq!(from_netscape_line("# comment"), Err(ParseError::MissingPair));
+        assert_eq!(from_netscape_line("a\tTRUE\t/\tTRUE\t0\tname"), Err(ParseError::MissingPair));
+        assert_eq!(from_netscape_line("a\tTRUE\t/\tTRUE\t0\t\tb"), Err(ParseError::EmptyName));
+        assert_eq!(from_netscape_line("a\tTRUE\t/\tTRUE\tsoon\tn\tv"),
+            Err(ParseError::InvalidExpires));
+        assert_eq!(from_netscape_line("a\tTRUE\t/\tTRUE\t99999999999999\tn\tv"),
+            Err(ParseError::InvalidExpires));
+    }
+}
@@ -11,8 +11,10 @@ use std::ascii::AsciiExt;
 use percent_encoding::percent_decode;
 use time::{PrimitiveDateTime, Duration, OffsetDateTime};
 use time::{parsing::Parsable, macros::format_description, format_description::FormatItem};
+use time::format_description::well_known::Rfc3339;
 
 use crate::{Cookie, SameSite, CookieStr};
+use crate::expiration::MAX_DATETIME;
 
 // The three formats spec'd in http://tools.ietf.org/html/rfc2616#section-3.3.1.
 // Additional ones as encountered in the real world.
@@ -31,6 +33,20 @@ pub enum ParseError {
     EmptyName,
     /// Decoding the cookie's name or value resulted in invalid UTF-8.
     Utf8Error(Utf8Error),
+    /// The `Max-Age` attribute was missing a value or its value was not an
+    /// integer. Only returned when parsing strictly.
+    InvalidMaxAge,
+    /// The `Expires` attribute was missing a value or its value was not a
+    /// valid date. Only returned when parsing strictly or, from
+    /// [`netscape::from_netscape_line()`](crate::netscape::from_netscape_line),
+    /// when the expiration field is not a valid Unix timestamp.
+    InvalidExpires,
+    /// The `SameSite` attribute was missing a value or its value was not one
+    /// of `Strict`, `Lax`, or `None`. Only returned when parsing strictly.
+    InvalidSameSite,
+    /// An attribute's value contained a control character. Only returned when
+    /// parsing strictly.
+    InvalidAttributeValue,
 }
 
 impl ParseError {
@@ -42,6 +58,12 @@ impl ParseError {
             ParseError::Utf8Error(_) => {
                 "decoding the cookie's name or value resulted in invalid UTF-8"
             }
+            ParseError::InvalidMaxAge => "the cookie's Max-Age is not an integer",
+            ParseError::InvalidExpires => "the cookie's Expires is not a valid date",
+            ParseError::InvalidSameSite => "the cookie's SameSite is not Strict, Lax, or None",
+            ParseError::InvalidAttributeValue => {
+                "an attribute value of the cookie contains a control character"
+            }
         }
     }
 }
@@ -64,6 +86,181 @@ impl Error for ParseError {
     }
 }
 
+/// A non-fatal problem found while parsing a cookie.
+///
+/// Warnings are reported by [`Cookie::parse_with_warnings()`]. They do not
+/// prevent a cookie from being parsed but often indicate a malformed
+/// `Set-Cookie` header.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The known attribute with the contained name, such as `"Path"`, appeared
+    /// more than once. The last occurrence takes precedence.
+    DuplicateAttribute(&'static str),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Warning::DuplicateAttribute(name) => {
+                write!(f, "the cookie's {} attribute appears more than once", name)
+            }
+        }
+    }
+}
+
+/// Options that adjust how a cookie string is parsed.
+///
+/// The default configuration matches the behavior of [`Cookie::parse()`]. Use
+/// [`Cookie::parse_with_config()`] to parse with a custom configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::{Cookie, ParseConfig};
+///
+/// let config = ParseConfig::default().quoted_names(true);
+/// let c = Cookie::parse_with_config("\"a=b\"=c; Path=/", config).unwrap();
+/// assert_eq!(c.name_value(), ("\"a=b\"", "c"));
+/// assert_eq!(c.path(), Some("/"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct ParseConfig {
+    /// Whether a cookie name enclosed in double quotes may contain `=`.
+    ///
+    /// When `true`, the name/value separator is the first `=` _after_ a
+    /// leading double-quoted name, so `"a=b"=c` parses as a cookie named
+    /// `"a=b"` (quotes included) with value `c`. When `false`, the default,
+    /// the separator is always the first `=`. Names without a closing quote
+    /// are parsed as if this option were disabled.
+    pub quoted_names: bool,
+    /// The maximum `Max-Age` to accept, if any.
+    ///
+    /// When `Some`, a parsed `Max-Age` greater than the cap is clamped to the
+    /// cap. For example, a cap of 400 days mirrors the limit modern browsers
+    /// impose on cookie lifetimes. When `None`, the default, `Max-Age` values
+    /// are only clamped to the largest representable duration.
+    pub max_age_cap: Option<Duration>,
+    /// The character separating the name/value pair and attributes.
+    ///
+    /// Defaults to `;`, as in a `Set-Cookie` header. Other delimiters, such as
+    /// `\n`, allow parsing non-HTTP serializations of cookies.
+    pub delimiter: char,
+    /// Whether to recover from attributes that precede the name/value pair.
+    ///
+    /// When `true`, the name/value pair is the first segment that contains a
+    /// `=` and isn't a known attribute such as `Path`, and every other
+    /// segment, including those preceding it, is parsed as an attribute. For
+    /// example, `Secure; Path=/; a=b` parses as a cookie named `a` with value
+    /// `b` and the `Secure` and `Path` attributes. When `false`, the default,
+    /// the name/value pair is always the first segment.
+    pub lenient: bool,
+    /// Whether to reject malformed attributes instead of ignoring them.
+    ///
+    /// When `true`, parsing fails if `Max-Age` is not an integer, if `Expires`
+    /// is not an HTTP date, if `SameSite` is not `Strict`, `Lax`, or `None`,
+    /// or if any attribute value contains a control character. Non-standard
+    /// RFC 3339 `Expires` dates are also rejected. When `false`, the default,
+    /// such attributes are ignored. See [`Cookie::parse_strict()`].
+    pub strict: bool,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        ParseConfig {
+            quoted_names: false,
+            max_age_cap: None,
+            delimiter: ';',
+            lenient: false,
+            strict: false,
+        }
+    }
+}
+
+impl ParseConfig {
+    /// Sets [`ParseConfig::quoted_names`] to `enabled`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::ParseConfig;
+    ///
+    /// let config = ParseConfig::default().quoted_names(true);
+    /// assert!(config.quoted_names);
+    /// ```
+    #[inline]
+    pub fn quoted_names(mut self, enabled: bool) -> Self {
+        self.quoted_names = enabled;
+        self
+    }
+
+    /// Sets [`ParseConfig::max_age_cap`] to `cap`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::ParseConfig;
+    /// use cookie::time::Duration;
+    ///
+    /// let config = ParseConfig::default().max_age_cap(Duration::days(400));
+    /// assert_eq!(config.max_age_cap, Some(Duration::days(400)));
+    /// ```
+    #[inline]
+    pub fn max_age_cap<D: Into<Option<Duration>>>(mut self, cap: D) -> Self {
+        self.max_age_cap = cap.into();
+        self
+    }
+
+    /// Sets [`ParseConfig::delimiter`] to `delimiter`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::ParseConfig;
+    ///
+    /// let config = ParseConfig::default().delimiter('\n');
+    /// assert_eq!(config.delimiter, '\n');
+    /// ```
+    #[inline]
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets [`ParseConfig::lenient`] to `enabled`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::ParseConfig;
+    ///
+    /// let config = ParseConfig::default().lenient(true);
+    /// assert!(config.lenient);
+    /// ```
+    #[inline]
+    pub fn lenient(mut self, enabled: bool) -> Self {
+        self.lenient = enabled;
+        self
+    }
+
+    /// Sets [`ParseConfig::strict`] to `enabled`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::ParseConfig;
+    ///
+    /// let config = ParseConfig::default().strict(true);
+    /// assert!(config.strict);
+    /// ```
+    #[inline]
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+}
+
 #[cfg(feature = "percent-encode")]
 fn name_val_decoded(
     name: &str,
@@ -89,16 +286,144 @@ fn name_val_decoded(
     unreachable!("This function should never be called with 'percent-encode' disabled!")
 }
 
+/// An iterator over the segments of a cookie string separated by a delimiter.
+///
+/// Each segment is yielded along with the index of the first `=` in the
+/// segment, if any, both found in a single scan over the string's bytes.
+struct Segments<'a> {
+    string: &'a str,
+    delimiter: [u8; 4],
+    delimiter_len: usize,
+    /// The byte index of the next segment, or `None` if we're done.
+    next: Option<usize>,
+}
+
+impl<'a> Segments<'a> {
+    fn new(string: &'a str, delimiter: char) -> Self {
+        let mut bytes = [0; 4];
+        let delimiter_len = delimiter.encode_utf8(&mut bytes).len();
+        Segments { string, delimiter: bytes, delimiter_len, next: Some(0) }
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = (&'a str, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next?;
+        let bytes = self.string.as_bytes();
+        let delimiter = &self.delimiter[..self.delimiter_len];
+
+        // Both `delimiter` and `=` are complete UTF-8 sequences, so any match
+        // begins on a `char` boundary, making the slicing below safe.
+        let mut equals = None;
+        for (i, &byte) in bytes.iter().enumerate().skip(start) {
+            if byte == delimiter[0] && bytes[i..].starts_with(delimiter) {
+                self.next = Some(i + delimiter.len());
+                return Some((&self.string[start..i], equals));
+            }
+
+            if byte == b'=' && equals.is_none() {
+                equals = Some(i - start);
+            }
+        }
+
+        self.next = None;
+        Some((&self.string[start..], equals))
+    }
+}
+
+// Returns the canonical, lowercase name of the known attribute `key`, matched
+// case-insensitively, or `None` if `key` isn't a known attribute.
+fn canonical_attribute(key: &str) -> Option<&'static str> {
+    const ATTRIBUTES: [&str; 8] = [
+        "secure", "httponly", "max-age", "domain",
+        "path", "samesite", "partitioned", "expires",
+    ];
+
+    ATTRIBUTES.iter().copied().find(|name| key.eq_ignore_ascii_case(name))
+}
+
+// Returns a warning for each known attribute that appears more than once in
+// the attributes of `s`, all segments but the first, in order of the second
+// occurrence of each.
+pub(crate) fn duplicate_attributes(s: &str) -> Vec<Warning> {
+    let mut seen = vec![];
+    let mut warnings = vec![];
+    for (attr, equals) in Segments::new(s, ';').skip(1) {
+        let key = match equals {
+            Some(i) => attr[..i].trim(),
+            None => attr.trim(),
+        };
+
+        if let Some(name) = canonical_attribute(key) {
+            if !seen.contains(&name) {
+                seen.push(name);
+            } else if !warnings.contains(&Warning::DuplicateAttribute(display_name(name))) {
+                warnings.push(Warning::DuplicateAttribute(display_name(name)));
+            }
+        }
+    }
+
+    warnings
+}
+
+// Returns the conventional spelling of the canonical attribute name `name`.
+fn display_name(name: &'static str) -> &'static str {
+    match name {
+        "secure" => "Secure",
+        "httponly" => "HttpOnly",
+        "max-age" => "Max-Age",
+        "domain" => "Domain",
+        "path" => "Path",
+        "samesite" => "SameSite",
+        "partitioned" => "Partitioned",
+        "expires" => "Expires",
+        _ => name,
+    }
+}
+
 // This function does the real parsing but _does not_ set the `cookie_string` in
 // the returned cookie object. This only exists so that the borrow to `s` is
 // returned at the end of the call, allowing the `cookie_string` field to be
 // set in the outer `parse` function.
-fn parse_inner<'c>(s: &str, decode: bool) -> Result<Cookie<'c>, ParseError> {
-    let mut attributes = s.split(';');
+fn parse_inner<'c>(
+    s: &str,
+    decode: bool,
+    config: &ParseConfig
+) -> Result<Cookie<'c>, ParseError> {
+    // Determine the name = val. It's the first segment unless we're lenient,
+    // in which case it's the first segment that contains a `=` and isn't a
+    // known attribute, if any.
+    let mut segments = Segments::new(s, config.delimiter);
+    let first = segments.next().expect("there is always at least one segment");
+    let mut key_value_index = 0;
+    let (key_value, equals) = match first {
+        (_, None) if config.lenient => {
+            let found = Segments::new(s, config.delimiter)
+                .enumerate()
+                .find(|(_, (segment, equals))| match equals {
+                    Some(i) => canonical_attribute(segment[..*i].trim()).is_none(),
+                    None => false,
+                });
+
+            match found {
+                Some((i, segment)) => {
+                    key_value_index = i;
+                    segment
+                }
+                None => first,
+            }
+        }
+        _ => first,
+    };
+
+    let separator = match config.quoted_names {
+        true => quoted_name_separator(key_value),
+        false => equals,
+    };
 
-    // Determine the name = val.
-    let key_value = attributes.next().expect("first str::split().next() returns Some");
-    let (name, value) = match key_value.find('=') {
+    let (name, value) = match separator {
         Some(i) => (key_value[..i].trim(), key_value[(i + 1)..].trim()),
         None => return Err(ParseError::MissingPair)
     };
@@ -116,9 +441,13 @@ fn parse_inner<'c>(s: &str, decode: bool) -> Result<Cookie<'c>, ParseError> {
 
     // Create a cookie with all of the defaults. We'll fill things in while we
     // iterate through the parameters below.
+    let mut decoded = false;
     let (name, value) = if decode {
         match name_val_decoded(name, value)? {
-            Some((name, value)) => (name, value),
+            Some((name, value)) => {
+                decoded = true;
+                (name, value)
+            }
             None => indexed_names(s, name, value)
         }
     } else {
@@ -130,99 +459,409 @@ fn parse_inner<'c>(s: &str, decode: bool) -> Result<Cookie<'c>, ParseError> {
         cookie_string: None,
         expires: None,
         max_age: None,
+        max_age_raw: None,
         domain: None,
         path: None,
         secure: None,
         http_only: None,
         same_site: None,
         partitioned: None,
+        extensions: Vec::new(),
+        decoded,
     };
 
-    for attr in attributes {
-        let (key, value) = match attr.find('=') {
-            Some(i) => (attr[..i].trim(), Some(attr[(i + 1)..].trim())),
-            None => (attr.trim(), None),
-        };
+    // If the pair was the first segment, the remaining segments are exactly
+    // the attributes. Otherwise, rescan, skipping the pair.
+    if key_value_index == 0 {
+        for (attr, equals) in segments {
+            parse_attribute(&mut cookie, s, attr, equals, config)?;
+        }
+    } else {
+        let attributes = Segments::new(s, config.delimiter)
+            .enumerate()
+            .filter(|(i, _)| *i != key_value_index);
 
-        match (&*key.to_ascii_lowercase(), value) {
-            ("secure", _) => cookie.secure = Some(true),
-            ("httponly", _) => cookie.http_only = Some(true),
-            ("max-age", Some(mut v)) => cookie.max_age = {
-                let is_negative = v.starts_with('-');
-                if is_negative {
-                    v = &v[1..];
-                }
+        for (_, (attr, equals)) in attributes {
+            parse_attribute(&mut cookie, s, attr, equals, config)?;
+        }
+    }
 
-                if !v.chars().all(|d| d.is_digit(10)) {
-                    continue
-                }
+    Ok(cookie)
+}
 
-                // From RFC 6265 5.2.2: neg values indicate that the earliest
-                // expiration should be used, so set the max age to 0 seconds.
-                if is_negative {
-                    Some(Duration::ZERO)
-                } else {
-                    Some(v.parse::<i64>()
-                        .map(Duration::seconds)
-                        .unwrap_or_else(|_| Duration::seconds(i64::max_value())))
-                }
-            },
-            ("domain", Some(d)) if !d.is_empty() => {
-                cookie.domain = Some(CookieStr::indexed(d, s).expect("domain sub"));
-            },
-            ("path", Some(v)) => {
-                cookie.path = Some(CookieStr::indexed(v, s).expect("path sub"));
-            },
-            ("samesite", Some(v)) => {
-                if v.eq_ignore_ascii_case("strict") {
-                    cookie.same_site = Some(SameSite::Strict);
-                } else if v.eq_ignore_ascii_case("lax") {
-                    cookie.same_site = Some(SameSite::Lax);
-                } else if v.eq_ignore_ascii_case("none") {
-                    cookie.same_site = Some(SameSite::None);
+// Parses the attribute segment `attr` of `s`, whose first `=` is at `equals`,
+// into `cookie`. Unknown attributes are stored as extensions while invalid
+// known attributes are ignored unless `config.strict`, in which case they
+// result in an error.
+fn parse_attribute(
+    cookie: &mut Cookie<'_>,
+    s: &str,
+    attr: &str,
+    equals: Option<usize>,
+    config: &ParseConfig
+) -> Result<(), ParseError> {
+    let (key, value) = match equals {
+        Some(i) => (attr[..i].trim(), Some(attr[(i + 1)..].trim())),
+        None => (attr.trim(), None),
+    };
+
+    // Ignores the invalid attribute unless we're parsing strictly.
+    let invalid = |error| match config.strict {
+        true => Err(error),
+        false => Ok(()),
+    };
+
+    let has_control = matches!(value, Some(v) if v.bytes().any(|b| b.is_ascii_control()));
+    if config.strict && has_control {
+        return Err(ParseError::InvalidAttributeValue);
+    }
+
+    match (canonical_attribute(key), value) {
+        (Some("secure"), _) => cookie.secure = Some(true),
+        (Some("httponly"), _) => cookie.http_only = Some(true),
+        (Some("max-age"), Some(mut v)) => cookie.max_age = {
+            cookie.max_age_raw = Some(CookieStr::indexed(v, s).expect("max-age sub"));
+            let is_negative = v.starts_with('-');
+            if is_negative {
+                v = &v[1..];
+            }
+
+            // An empty value has no digits: ignore it rather than treating
+            // it as an overflowing number of seconds.
+            if v.is_empty() || !v.chars().all(|d| d.is_ascii_digit()) {
+                return invalid(ParseError::InvalidMaxAge);
+            }
+
+            // From RFC 6265 5.2.2: neg values indicate that the earliest
+            // expiration should be used, so set the max age to 0 seconds.
+            let max_age = if is_negative {
+                Duration::ZERO
+            } else {
+                v.parse::<i64>()
+                    .map(Duration::seconds)
+                    .unwrap_or_else(|_| Duration::seconds(i64::MAX))
+            };
+
+            match config.max_age_cap {
+                Some(cap) if max_age > cap => Some(cap),
+                _ => Some(max_age),
+            }
+        },
+        (Some("domain"), Some(d)) if !d.is_empty() => {
+            cookie.domain = Some(CookieStr::indexed(d, s).expect("domain sub"));
+        },
+        (Some("path"), Some(v)) => {
+            cookie.path = Some(CookieStr::indexed(v, s).expect("path sub"));
+        },
+        (Some("samesite"), Some(v)) => {
+            if v.eq_ignore_ascii_case("strict") {
+                cookie.same_site = Some(SameSite::Strict);
+            } else if v.eq_ignore_ascii_case("lax") {
+                cookie.same_site = Some(SameSite::Lax);
+            } else if v.eq_ignore_ascii_case("none") {
+                cookie.same_site = Some(SameSite::None);
+            } else {
+                // We do nothing here, for now, unless we're parsing strictly.
+                // When/if the `SameSite` attribute becomes standard, the spec
+                // says that we should ignore this cookie, i.e, fail to parse
+                // it, when an invalid value is passed in. The draft is at
+                // http://httpwg.org/http-extensions/draft-ietf-httpbis-cookie-same-site.html.
+                return invalid(ParseError::InvalidSameSite);
+            }
+        }
+        (Some("partitioned"), _) => cookie.partitioned = Some(true),
+        (Some("expires"), Some(v)) => {
+            if let Ok(time) = parse_http_date(v) {
+                cookie.expires = Some(time.into())
+            } else if let (false, Ok(time)) = (config.strict, OffsetDateTime::parse(v, &Rfc3339)) {
+                // Non-standard, but emitted by `Display::iso_expires()`.
+                cookie.expires = Some(time.into())
+            } else if let Some(clamped) = clamp_overlong_year(v) {
+                // Some servers emit years beyond 9999, which we can't
+                // represent. Clamp them to the latest valid date-time.
+                if parse_http_date(&clamped).is_ok() {
+                    cookie.expires = Some(MAX_DATETIME.into())
                 } else {
-                    // We do nothing here, for now. When/if the `SameSite`
-                    // attribute becomes standard, the spec says that we should
-                    // ignore this cookie, i.e, fail to parse it, when an
-                    // invalid value is passed in. The draft is at
-                    // http://httpwg.org/http-extensions/draft-ietf-httpbis-cookie-same-site.html.
+                    return invalid(ParseError::InvalidExpires);
                 }
+            } else {
+                return invalid(ParseError::InvalidExpires);
             }
-            ("partitioned", _) => cookie.partitioned = Some(true),
-            ("expires", Some(v)) => {
-                let tm = parse_date(v, &FMT1)
-                    .or_else(|_| parse_date(v, &FMT2))
-                    .or_else(|_| parse_date(v, &FMT3))
-                    .or_else(|_| parse_date(v, &FMT4));
-                    // .or_else(|_| parse_date(v, &FMT5));
-
-                if let Ok(time) = tm {
-                    cookie.expires = Some(time.into())
-                }
+        }
+        (Some("max-age"), None) => return invalid(ParseError::InvalidMaxAge),
+        (Some("samesite"), None) => return invalid(ParseError::InvalidSameSite),
+        (Some("expires"), None) => return invalid(ParseError::InvalidExpires),
+        (None, value) if !key.is_empty() => {
+            // We're going to be permissive here. If we have no idea what
+            // this is, then it's something nonstandard. We store it as an
+            // extension, which callers may inspect, but don't emit an error.
+            let key = CookieStr::indexed(key, s).expect("extension name sub");
+            let value = value.map(|v| CookieStr::indexed(v, s).expect("extension value sub"));
+            cookie.extensions.push((key, value));
+        }
+        _ => {
+            // The attribute is empty or is a known attribute with a missing
+            // or invalid value. We ignore it without emitting an error.
+        }
+    }
+
+    Ok(())
+}
+
+// Parses the `;`-separated attributes in `attrs`, which has no name/value pair,
+// and applies them to `cookie`, leaving all other attributes untouched.
+pub(crate) fn apply_attributes(cookie: &mut Cookie<'_>, attrs: &str) {
+    // Attributes are parsed into a scratch cookie as indexes into `attrs`,
+    // which we then resolve into owned strings since `cookie` doesn't own it.
+    let config = ParseConfig::default();
+    let mut parsed = Cookie::new("", "");
+    for (attr, equals) in Segments::new(attrs, config.delimiter) {
+        // Parsing is never strict here, so this never fails.
+        let _ = parse_attribute(&mut parsed, attrs, attr, equals, &config);
+    }
+
+    let attrs = Cow::Borrowed(attrs);
+    let resolve = |string: CookieStr<'_>| {
+        CookieStr::Concrete(Cow::Owned(string.to_str(Some(&attrs)).to_string()))
+    };
+
+    if let Some(domain) = parsed.domain {
+        cookie.domain = Some(resolve(domain));
+    }
+
+    if let Some(path) = parsed.path {
+        cookie.path = Some(resolve(path));
+    }
+
+    cookie.expires = parsed.expires.or(cookie.expires);
+    cookie.max_age = parsed.max_age.or(cookie.max_age);
+    if parsed.max_age_raw.is_some() {
+        cookie.max_age_raw = None;
+    }
+
+    cookie.secure = parsed.secure.or(cookie.secure);
+    cookie.http_only = parsed.http_only.or(cookie.http_only);
+    cookie.same_site = parsed.same_site.or(cookie.same_site);
+    cookie.partitioned = parsed.partitioned.or(cookie.partitioned);
+
+    for (name, value) in parsed.extensions {
+        cookie.extensions.push((resolve(name), value.map(resolve)));
+    }
+}
+
+// Returns the index of the first `=` in `pair` following a leading, quoted
+// name, if there is one, or the index of the first `=` otherwise.
+fn quoted_name_separator(pair: &str) -> Option<usize> {
+    let start = pair.len() - pair.trim_start().len();
+    if pair[start..].starts_with('"') {
+        if let Some(i) = pair[(start + 1)..].find('"') {
+            let name_end = start + 1 + i + 1;
+            return pair[name_end..].find('=').map(|j| name_end + j);
+        }
+    }
+
+    pair.find('=')
+}
+
+/// Returns `true` if `name` is a valid cookie name as defined by RFC 6265: a
+/// non-empty `token` of visible ASCII characters excluding separators.
+///
+/// Cookie names are not validated when cookies are created or parsed. This
+/// function allows validating names before constructing a cookie.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::is_valid_name;
+///
+/// assert!(is_valid_name("session_id"));
+/// assert!(is_valid_name("__Host-id"));
+///
+/// assert!(!is_valid_name(""));
+/// assert!(!is_valid_name("my name"));
+/// assert!(!is_valid_name("a=b"));
+/// assert!(!is_valid_name("a;b"));
+/// ```
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && invalid_name_byte(name).is_none()
+}
+
+// Returns the index of the first byte in `name` that isn't a `token` character.
+fn invalid_name_byte(name: &str) -> Option<usize> {
+    // token = 1*<any CHAR except CTLs or separators>
+    const SEPARATORS: &[u8] = b"()<>@,;:\\\"/[]?={} \t";
+
+    name.bytes().position(|b| !(0x21..=0x7e).contains(&b) || SEPARATORS.contains(&b))
+}
+
+/// Returns `true` if `value` is a valid cookie value as defined by RFC 6265:
+/// a possibly empty sequence of `cookie-octet`s, optionally enclosed in double
+/// quotes. A `cookie-octet` is a visible ASCII character excluding `"`, `,`,
+/// `;`, and `\`.
+///
+/// Cookie values are not validated when cookies are created or parsed. This
+/// function allows validating values before constructing a cookie.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::is_valid_value;
+///
+/// assert!(is_valid_value("value"));
+/// assert!(is_valid_value(""));
+/// assert!(is_valid_value("\"quoted\""));
+/// assert!(is_valid_value("a=b"));
+///
+/// assert!(!is_valid_value("a b"));
+/// assert!(!is_valid_value("a;b"));
+/// assert!(!is_valid_value("\"unbalanced"));
+/// assert!(!is_valid_value("line\nbreak"));
+/// ```
+pub fn is_valid_value(value: &str) -> bool {
+    invalid_value_byte(value).is_none()
+}
+
+// Returns the index of the first byte in `value` that isn't a `cookie-octet`,
+// ignoring enclosing double quotes.
+fn invalid_value_byte(value: &str) -> Option<usize> {
+    // cookie-value = *cookie-octet / ( DQUOTE *cookie-octet DQUOTE )
+    let (offset, value) = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(unquoted) => (1, unquoted),
+        None => (0, value),
+    };
+
+    // cookie-octet = %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E
+    value.bytes()
+        .position(|b| !(0x21..=0x7e).contains(&b) || matches!(b, b'"' | b',' | b';' | b'\\'))
+        .map(|i| i + offset)
+}
+
+/// An error returned by [`Cookie::validate()`] when a cookie's name or value
+/// is not valid as defined by RFC 6265, or by [`Cookie::add_extension()`]
+/// when an extension attribute is invalid.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The cookie's name is empty.
+    EmptyName,
+    /// The cookie's name contains `byte`, at byte offset `index`, which is not
+    /// a `token` character: it's a control character, a space, a separator,
+    /// or not ASCII.
+    InvalidName {
+        /// The byte offset of `byte` in the name.
+        index: usize,
+        /// The offending byte.
+        byte: u8,
+    },
+    /// The cookie's value contains `byte`, at byte offset `index`, which is
+    /// not a `cookie-octet`: it's a control character, a space, one of `"`,
+    /// `,`, `;`, or `\`, or not ASCII. A value may be enclosed in double
+    /// quotes; an unbalanced quote is reported as invalid.
+    InvalidValue {
+        /// The byte offset of `byte` in the value.
+        index: usize,
+        /// The offending byte.
+        byte: u8,
+    },
+    /// An extension attribute's name is empty or contains `=`, or its name or
+    /// value contains `;` or an ASCII control character, any of which would
+    /// allow the extension to inject other attributes or headers. Only
+    /// returned by [`Cookie::add_extension()`](crate::Cookie::add_extension())
+    /// and [`CookieBuilder::extension()`](crate::CookieBuilder::extension()).
+    InvalidExtension,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationError::EmptyName => write!(f, "the cookie's name is empty"),
+            ValidationError::InvalidName { index, byte } => {
+                write!(f, "the cookie's name contains invalid byte {:#04x} at {}", byte, index)
             }
-            _ => {
-                // We're going to be permissive here. If we have no idea what
-                // this is, then it's something nonstandard. We're not going to
-                // store it (because it's not compliant), but we're also not
-                // going to emit an error.
+            ValidationError::InvalidValue { index, byte } => {
+                write!(f, "the cookie's value contains invalid byte {:#04x} at {}", byte, index)
+            }
+            ValidationError::InvalidExtension => {
+                write!(f, "the extension attribute's name or value is invalid")
             }
         }
     }
+}
 
-    Ok(cookie)
+impl Error for ValidationError { }
+
+// Validates `name` and `value` as for `Cookie::validate()`.
+pub(crate) fn validate(name: &str, value: &str) -> Result<(), ValidationError> {
+    if name.is_empty() {
+        return Err(ValidationError::EmptyName);
+    }
+
+    if let Some(index) = invalid_name_byte(name) {
+        return Err(ValidationError::InvalidName { index, byte: name.as_bytes()[index] });
+    }
+
+    if let Some(index) = invalid_value_byte(value) {
+        return Err(ValidationError::InvalidValue { index, byte: value.as_bytes()[index] });
+    }
+
+    Ok(())
+}
+
+// Validates the extension attribute `name`, with value `value`, as for
+// `Cookie::add_extension()`.
+pub(crate) fn validate_extension(name: &str, value: Option<&str>) -> Result<(), ValidationError> {
+    fn is_delimiter(b: u8) -> bool {
+        b == b';' || b.is_ascii_control()
+    }
+
+    if name.is_empty()
+        || name.bytes().any(|b| b == b'=' || is_delimiter(b))
+        || matches!(value, Some(v) if v.bytes().any(is_delimiter))
+    {
+        return Err(ValidationError::InvalidExtension);
+    }
+
+    Ok(())
 }
 
-pub(crate) fn parse_cookie<'c, S>(cow: S, decode: bool) -> Result<Cookie<'c>, ParseError>
+pub(crate) fn parse_cookie<'c, S>(
+    cow: S,
+    decode: bool,
+    config: &ParseConfig
+) -> Result<Cookie<'c>, ParseError>
     where S: Into<Cow<'c, str>>
 {
     let s = cow.into();
-    let mut cookie = parse_inner(&s, decode)?;
+    let mut cookie = parse_inner(&s, decode, config)?;
     cookie.cookie_string = Some(s);
     Ok(cookie)
 }
 
+fn parse_http_date(s: &str) -> Result<OffsetDateTime, time::Error> {
+    parse_date(s, &FMT1)
+        .or_else(|_| parse_date(s, &FMT2))
+        .or_else(|_| parse_date(s, &FMT3))
+        .or_else(|_| parse_date(s, &FMT4))
+        // .or_else(|_| parse_date(s, &FMT5))
+}
+
+// If the date `s` contains a year greater than 9999, returns `s` with the year
+// replaced by 9999. Otherwise returns `None`.
+fn clamp_overlong_year(s: &str) -> Option<String> {
+    let year = s.split([' ', '-'])
+        .find(|t| t.len() > 4 && t.bytes().all(|b| b.is_ascii_digit()))?;
+
+    if year.trim_start_matches('0').len() <= 4 {
+        return None;
+    }
+
+    let start = year.as_ptr() as usize - s.as_ptr() as usize;
+    Some(format!("{}9999{}", &s[..start], &s[(start + year.len())..]))
+}
+
 pub(crate) fn parse_date(s: &str, format: &impl Parsable) -> Result<OffsetDateTime, time::Error> {
     // Parse. Handle "abbreviated" dates like Chromium. See cookie#162.
+    #[allow(deprecated)]
     let mut date = format.parse(s.as_bytes())?;
     if let Some(y) = date.year().or_else(|| date.year_last_two().map(|v| v as i32)) {
         let offset = match y {
@@ -239,8 +878,8 @@ pub(crate) fn parse_date(s: &str, format: &impl Parsable) -> Result<OffsetDateTi
 
 #[cfg(test)]
 mod tests {
-    use super::parse_date;
-    use crate::{Cookie, SameSite};
+    use super::{parse_date, is_valid_name, is_valid_value};
+    use crate::{Cookie, SameSite, ParseConfig, ParseError};
     use time::Duration;
 
     macro_rules! assert_eq_parse {
@@ -288,6 +927,61 @@ mod tests {
         assert_eq_parse!("foo=bar; SameSite=nOne", expected);
     }
 
+    #[test]
+    fn parse_partitioned_roundtrip() {
+        for string in &["a=b; Partitioned; Secure", "a=b; Secure; Partitioned"] {
+            let c = Cookie::parse(*string).unwrap();
+            assert_eq!(c.partitioned(), Some(true));
+            assert_eq!(c.secure(), Some(true));
+            assert_eq!(c.to_string(), "a=b; Partitioned; Secure");
+        }
+
+        // `Partitioned` implies `Secure` in the output, which is then parsed.
+        let c = Cookie::parse("a=b; partitioned; Path=/").unwrap();
+        assert_eq!(c.partitioned(), Some(true));
+        assert_eq!(c.secure(), None);
+        assert_eq!(c.to_string(), "a=b; Partitioned; Secure; Path=/");
+
+        let reparsed = Cookie::parse(c.to_string()).unwrap();
+        assert_eq!(reparsed.partitioned(), Some(true));
+        assert_eq!(reparsed.secure(), Some(true));
+        assert_eq!(reparsed.path(), Some("/"));
+        assert_eq!(reparsed.to_string(), c.to_string());
+
+        let built = Cookie::build(("a", "b")).partitioned(true).secure(true).build();
+        assert_eq!(Cookie::parse(built.to_string()).unwrap(), built);
+    }
+
+    #[test]
+    fn parse_same_site_none_roundtrip() {
+        // `==` doesn't compare `SameSite`, so check it explicitly.
+        let c = Cookie::parse("foo=bar; SameSite=None").unwrap();
+        assert_eq!(c.same_site(), Some(SameSite::None));
+        assert_eq!(c.secure(), None);
+        assert_eq!(c.to_string(), "foo=bar; SameSite=None; Secure");
+
+        for value in &["none", "NONE", "nOnE"] {
+            let c = Cookie::parse(format!("foo=bar; samesite={}", value)).unwrap();
+            assert_eq!(c.same_site(), Some(SameSite::None));
+        }
+
+        // Reparsing the output retains `SameSite=None` and the emitted `Secure`.
+        let original = Cookie::build(("foo", "bar")).same_site(SameSite::None).build();
+        let reparsed = Cookie::parse(original.to_string()).unwrap();
+        assert_eq!(reparsed.same_site(), Some(SameSite::None));
+        assert_eq!(reparsed.secure(), Some(true));
+        assert_eq!(reparsed.to_string(), original.to_string());
+
+        // An explicit `secure(false)` is honored but, as `Secure` is then
+        // absent from the output, a reparsed cookie emits it again.
+        let mut insecure = original.clone();
+        insecure.set_secure(false);
+        assert_eq!(insecure.to_string(), "foo=bar; SameSite=None");
+        let reparsed = Cookie::parse(insecure.to_string()).unwrap();
+        assert_eq!(reparsed.same_site(), Some(SameSite::None));
+        assert_eq!(reparsed.to_string(), "foo=bar; SameSite=None; Secure");
+    }
+
     #[test]
     fn parse() {
         assert!(Cookie::parse("bar").is_err());
@@ -465,7 +1159,7 @@ mod tests {
     #[test]
     fn parse_very_large_max_ages() {
         let mut expected = Cookie::build(("foo", "bar"))
-            .max_age(Duration::seconds(i64::max_value()))
+            .max_age(Duration::seconds(i64::MAX))
             .build();
 
         let string = format!("foo=bar; Max-Age={}", 1u128 << 100);
@@ -477,11 +1171,11 @@ mod tests {
         let string = format!("foo=bar; Max-Age=-{}", 1u128 << 100);
         assert_eq_parse!(&string, expected);
 
-        let string = format!("foo=bar; Max-Age=-{}", i64::max_value());
+        let string = format!("foo=bar; Max-Age=-{}", i64::MAX);
         assert_eq_parse!(&string, expected);
 
-        let string = format!("foo=bar; Max-Age={}", i64::max_value());
-        expected.set_max_age(Duration::seconds(i64::max_value()));
+        let string = format!("foo=bar; Max-Age={}", i64::MAX);
+        expected.set_max_age(Duration::seconds(i64::MAX));
         assert_eq_parse!(&string, expected);
     }
 
@@ -512,4 +1206,422 @@ mod tests {
         let too_many_seconds = (max_seconds as u64) + 1;
         assert_eq_parse!(format!(" foo=bar; Max-Age={:?}", too_many_seconds), expected);
     }
+
+    #[test]
+    fn parse_quoted_names() {
+        let config = ParseConfig::default().quoted_names(true);
+        let parse = |s| Cookie::parse_with_config(s, config).unwrap();
+
+        let cookie = parse("\"a=b\"=c");
+        assert_eq!(cookie.name_value(), ("\"a=b\"", "c"));
+
+        let cookie = parse("  \"a=b\" = c ; Path=/; Secure");
+        assert_eq!(cookie.name_value(), ("\"a=b\"", "c"));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.secure(), Some(true));
+
+        let cookie = parse("\"a=b\"=\"c=d\"");
+        assert_eq!(cookie.name_value(), ("\"a=b\"", "\"c=d\""));
+
+        let cookie = parse("a=b=c");
+        assert_eq!(cookie.name_value(), ("a", "b=c"));
+
+        let cookie = parse("\"a=b");
+        assert_eq!(cookie.name_value(), ("\"a", "b"));
+
+        assert_eq!(Cookie::parse_with_config("\"a=b\"", config), Err(ParseError::MissingPair));
+
+        let cookie = Cookie::parse("\"a=b\"=c").unwrap();
+        assert_eq!(cookie.name_value(), ("\"a", "b\"=c"));
+    }
+
+    #[test]
+    fn parse_with_delimiter() {
+        let expected = Cookie::build(("foo", "bar"))
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .max_age(Duration::seconds(10))
+            .build();
+
+        let c = Cookie::parse_with_delimiter("foo=bar\nPath=/\nHttpOnly\nSameSite=Lax\nMax-Age=10", '\n');
+        assert_eq!(c.unwrap(), expected);
+
+        let c = Cookie::parse_with_delimiter("foo=bar\r\nPath=/\r\nHttpOnly\r\nSameSite=Lax\r\nMax-Age=10\r\n", '\n');
+        assert_eq!(c.unwrap(), expected);
+
+        let c = Cookie::parse_with_delimiter("foo=bar, Path=/, HttpOnly, SameSite=Lax, Max-Age=10", ',');
+        assert_eq!(c.unwrap(), expected);
+
+        // With an alternate delimiter, `;` is part of the value.
+        let c = Cookie::parse_with_delimiter("foo=bar; baz\nHttpOnly", '\n').unwrap();
+        assert_eq!(c.value(), "bar; baz");
+        assert_eq!(c.http_only(), Some(true));
+    }
+
+    #[test]
+    fn parse_max_age_cap() {
+        let cap = Duration::days(400);
+        let config = ParseConfig::default().max_age_cap(cap);
+        let parse = |s| Cookie::parse_with_config(s, config).unwrap();
+
+        assert_eq!(parse("foo=bar; Max-Age=99999999").max_age(), Some(cap));
+        assert_eq!(parse("foo=bar; Max-Age=9223372036854775807").max_age(), Some(cap));
+        assert_eq!(parse("foo=bar; Max-Age=34560000").max_age(), Some(cap));
+        assert_eq!(parse("foo=bar; Max-Age=3600").max_age(), Some(Duration::hours(1)));
+        assert_eq!(parse("foo=bar; Max-Age=-1").max_age(), Some(Duration::ZERO));
+        assert_eq!(parse("foo=bar").max_age(), None);
+
+        let cookie = Cookie::parse("foo=bar; Max-Age=99999999").unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::seconds(99999999)));
+    }
+
+    #[test]
+    fn parse_overlong_expires_year() {
+        let max = time::macros::datetime!(9999-12-31 23:59:59.999_999 UTC);
+        let expected = Cookie::build(("foo", "bar")).expires(max).build();
+
+        assert_eq_parse!("foo=bar; Expires=Wed, 21 Oct 99999 07:28:00 GMT", expected);
+        assert_eq_parse!("foo=bar; Expires=Wed, 21-Oct-123456 07:28:00 GMT", expected);
+        assert_eq_parse!("foo=bar; Expires=Wed Oct 21 07:28:00 99999", expected);
+
+        let cookie = Cookie::parse("foo=bar; Expires=Wed, 21 Oct 99999 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires_datetime(), Some(max));
+        assert_eq!(&cookie.to_string(), "foo=bar; Expires=Fri, 31 Dec 9999 23:59:59 GMT");
+
+        // Years that fit are unaffected, even with leading zeros.
+        let cookie = Cookie::parse("foo=bar; Expires=Wed, 21 Oct 09999 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires(), None);
+        let cookie = Cookie::parse("foo=bar; Expires=Wed, 21 Oct 9999 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires_datetime().map(|t| t.month()), Some(time::Month::October));
+
+        // Otherwise invalid dates are still ignored.
+        let cookie = Cookie::parse("foo=bar; Expires=Wed, 41 Oct 99999 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires(), None);
+        let cookie = Cookie::parse("foo=bar; Expires=99999").unwrap();
+        assert_eq!(cookie.expires(), None);
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let lenient = ParseConfig::default().lenient(true);
+        let parse = |s| Cookie::parse_with_config(s, lenient);
+
+        assert_eq!(Cookie::parse("Secure; a=b"), Err(ParseError::MissingPair));
+        let expected = Cookie::build(("a", "b")).secure(true).build();
+        assert_eq!(parse("Secure; a=b").unwrap(), expected);
+        assert_eq!(parse(" Secure ;a=b ").unwrap(), expected);
+
+        let expected = Cookie::build(("a", "b")).secure(true).http_only(true).path("/").build();
+        assert_eq!(parse("Secure; HttpOnly; a=b; Path=/").unwrap(), expected);
+
+        // Known attributes are never taken to be the pair.
+        let expected = Cookie::build(("a", "b")).secure(true).path("/").build();
+        assert_eq!(parse("Secure; Path=/; a=b").unwrap(), expected);
+        assert_eq!(parse("Secure; path = /; a=b").unwrap(), expected);
+        assert_eq!(parse("Secure; Path=/"), Err(ParseError::MissingPair));
+
+        // Otherwise, the first segment with a `=` is the pair.
+        let c = parse("Secure; a=b; c=d").unwrap();
+        assert_eq!(c.name_value(), ("a", "b"));
+
+        // Well-formed cookies parse as usual.
+        let expected = Cookie::build(("a", "b")).secure(true).build();
+        assert_eq!(parse("a=b; Secure").unwrap(), expected);
+
+        assert_eq!(parse("Secure; HttpOnly"), Err(ParseError::MissingPair));
+        assert_eq!(parse(""), Err(ParseError::MissingPair));
+        assert_eq!(parse("Secure; =b"), Err(ParseError::EmptyName));
+    }
+
+    #[test]
+    fn apply_attributes() {
+        let mut cookie = Cookie::new("name", "value");
+        cookie.apply_attributes("Secure; Path=/foo; Max-Age=60");
+        assert_eq!(cookie.name_value(), ("name", "value"));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.path(), Some("/foo"));
+        assert_eq!(cookie.max_age(), Some(Duration::seconds(60)));
+        assert_eq!(cookie.http_only(), None);
+        assert_eq!(cookie.domain(), None);
+
+        // A leading `;`, unknown attributes, and odd casing are all fine.
+        cookie.apply_attributes("; httponly; Foo=Bar; DOMAIN=example.com");
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.path(), Some("/foo"));
+        assert_eq!(cookie.max_age(), Some(Duration::seconds(60)));
+
+        // Later attributes override earlier ones.
+        cookie.apply_attributes("Path=/bar; Max-Age=10; SameSite=Lax");
+        assert_eq!(cookie.path(), Some("/bar"));
+        assert_eq!(cookie.max_age(), Some(Duration::seconds(10)));
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+
+        // Attributes are applied to parsed cookies without disturbing them.
+        let mut cookie = Cookie::parse("a=b; Path=/; Secure").unwrap();
+        cookie.apply_attributes("Domain=rust-lang.org; Partitioned");
+        assert_eq!(cookie.name_value(), ("a", "b"));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.domain(), Some("rust-lang.org"));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.partitioned(), Some(true));
+
+        cookie.apply_attributes("");
+        assert_eq!(cookie.domain(), Some("rust-lang.org"));
+    }
+
+    #[test]
+    fn parse_extensions() {
+        let cookie = Cookie::parse("a=b; HostOnly").unwrap();
+        assert_eq!(cookie.extensions().collect::<Vec<_>>(), [("HostOnly", None)]);
+        assert_eq!(cookie.to_string(), "a=b; HostOnly");
+
+        let cookie = Cookie::parse("a=b; Priority = High; Path=/; SameParty; X=").unwrap();
+        let extensions: Vec<_> = cookie.extensions().collect();
+        assert_eq!(extensions, [("Priority", Some("High")), ("SameParty", None), ("X", Some(""))]);
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.to_string(), "a=b; Path=/; Priority=High; SameParty; X=");
+
+        // Empty segments and invalid known attributes aren't extensions.
+        let cookie = Cookie::parse("a=b; ; =x; Max-Age=abc; Domain=; SameSite=Foo").unwrap();
+        assert_eq!(cookie.extensions().count(), 0);
+        assert_eq!(cookie.max_age(), None);
+
+        // Extensions survive conversion and are considered by `eq_exact`.
+        let cookie = Cookie::parse("a=b; Priority=High").unwrap();
+        assert!(cookie.clone().into_owned().eq_exact(&cookie));
+        assert!(cookie.reborrow().eq_exact(&cookie));
+        assert!(!cookie.eq_exact(&Cookie::parse("a=b; Priority=Low").unwrap()));
+        assert!(!cookie.eq_exact(&Cookie::parse("a=b").unwrap()));
+
+        let mut built = Cookie::new("a", "b");
+        built.add_extension("Priority", Some("High")).unwrap();
+        assert!(built.eq_exact(&cookie));
+
+        let mut cookie = Cookie::new("a", "b");
+        cookie.apply_attributes("Foo=Bar; Secure; Baz");
+        let extensions: Vec<_> = cookie.extensions().collect();
+        assert_eq!(extensions, [("Foo", Some("Bar")), ("Baz", None)]);
+    }
+
+    #[test]
+    fn valid_names() {
+        for name in &["a", "name", "__Secure-id", "x.y_z~!#$%&'*+^`|", "0123"] {
+            assert!(is_valid_name(name), "{:?} should be valid", name);
+        }
+
+        let invalid = [
+            "", " a", "a ", "a b", "a\tb", "a=b", "a;b", "a,b", "(a)", "<a>", "a@b",
+            "a:b", "a\\b", "\"a\"", "a/b", "[a]", "a?", "{a}", "a\x7f", "a\0", "né",
+        ];
+
+        for name in &invalid {
+            assert!(!is_valid_name(name), "{:?} should be invalid", name);
+        }
+    }
+
+    #[test]
+    fn valid_values() {
+        let valid = ["", "a", "value", "a=b", "!#$%&'()*+-./:<=>?@[]^_`{|}~", "\"\"", "\"quoted\""];
+        for value in &valid {
+            assert!(is_valid_value(value), "{:?} should be valid", value);
+        }
+
+        let invalid = [
+            " ", "a b", "a\tb", "a\r\nb", "\0", "a\x7f", "a;b", "a,b", "a\\b",
+            "\"", "\"a", "a\"", "a\"b", "\"a\"b\"", "\"a b\"", "é",
+        ];
+
+        for value in &invalid {
+            assert!(!is_valid_value(value), "{:?} should be invalid", value);
+        }
+    }
+
+    #[test]
+    fn parse_strict() {
+        let ok = "foo=bar; Max-Age=-10; Expires=Wed, 21 Oct 2015 07:28:00 GMT; \
+            SameSite=strict; Path=/; Domain=example.com; Secure; Priority=High";
+        let cookie = Cookie::parse_strict(ok).unwrap();
+        assert!(cookie.eq_exact(&Cookie::parse(ok).unwrap()));
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+
+        let cases = [
+            ("foo=bar; Max-Age=soon", ParseError::InvalidMaxAge),
+            ("foo=bar; Max-Age=1.5", ParseError::InvalidMaxAge),
+            ("foo=bar; Max-Age=", ParseError::InvalidMaxAge),
+            ("foo=bar; Max-Age", ParseError::InvalidMaxAge),
+            ("foo=bar; Expires=tomorrow", ParseError::InvalidExpires),
+            ("foo=bar; Expires=2015-10-21T07:28:00Z", ParseError::InvalidExpires),
+            ("foo=bar; Expires", ParseError::InvalidExpires),
+            ("foo=bar; SameSite=Sometimes", ParseError::InvalidSameSite),
+            ("foo=bar; SameSite=", ParseError::InvalidSameSite),
+            ("foo=bar; Path=/a\x07b", ParseError::InvalidAttributeValue),
+            ("foo=bar; Ext=\x7f", ParseError::InvalidAttributeValue),
+        ];
+
+        for (string, error) in cases.iter() {
+            assert_eq!(Cookie::parse_strict(*string).unwrap_err(), *error, "{}", string);
+
+            // The lenient parser still accepts these, ignoring the attribute.
+            assert_eq!(Cookie::parse(*string).unwrap().name_value(), ("foo", "bar"));
+        }
+
+        assert_eq!(Cookie::parse("foo=bar; Max-Age=soon").unwrap().max_age(), None);
+        assert_eq!(Cookie::parse("foo=bar; SameSite=Sometimes").unwrap().same_site(), None);
+        assert!(Cookie::parse("foo=bar; Expires=2015-10-21T07:28:00Z").unwrap().expires().is_some());
+
+        // Overlong years are still clamped, as they are valid HTTP dates.
+        let cookie = Cookie::parse_strict("foo=bar; Expires=Fri, 01 Jan 10000 00:00:00 GMT");
+        assert!(cookie.unwrap().expires_datetime().is_some());
+
+        // Errors in the name/value pair take precedence.
+        assert_eq!(Cookie::parse_strict("=bar; Max-Age=x").unwrap_err(), ParseError::EmptyName);
+    }
+
+    #[test]
+    fn name_whitespace() {
+        let string = "  foo  =bar";
+        let cookie = Cookie::parse(string).unwrap();
+        assert_eq!(cookie.name(), "foo");
+
+        // The raw name is the trimmed slice of the original string.
+        let raw = cookie.name_raw().unwrap();
+        assert_eq!(raw, "foo");
+        assert_eq!(raw.as_ptr(), string[2..].as_ptr());
+
+        // Internal whitespace is preserved by every parser while surrounding
+        // whitespace is trimmed.
+        for &(string, name) in &[
+            ("fo o=bar", "fo o"),
+            ("  fo o  =bar", "fo o"),
+            ("fo\to=bar", "fo\to"),
+            ("\tf o o\t= bar ", "f o o"),
+        ] {
+            let cookie = Cookie::parse(string).unwrap();
+            assert_eq!(cookie.name(), name);
+            assert_eq!(cookie.name_raw(), Some(name));
+            assert_eq!(cookie.value(), "bar");
+            assert!(!crate::is_valid_name(cookie.name()));
+
+            assert_eq!(Cookie::parse_strict(string).unwrap().name(), name);
+            let split: Vec<_> = Cookie::split_parse(string).collect();
+            assert_eq!(split[0].as_ref().unwrap().name(), name);
+
+            #[cfg(feature = "percent-encode")]
+            assert_eq!(Cookie::parse_encoded(string).unwrap().name(), name);
+        }
+
+        // A name that is all whitespace is empty.
+        assert_eq!(Cookie::parse("   =bar"), Err(ParseError::EmptyName));
+    }
+
+    #[test]
+    fn max_age_raw() {
+        let cases = [
+            ("a=b; Max-Age=10", Some("10"), Some(Duration::seconds(10))),
+            ("a=b; max-age= 010 ", Some("010"), Some(Duration::seconds(10))),
+            ("a=b; Max-Age=-5", Some("-5"), Some(Duration::ZERO)),
+            ("a=b; Max-Age=99999999999999999999", Some("99999999999999999999"),
+                Some(Duration::seconds(i64::MAX))),
+            ("a=b; Max-Age=1.5", Some("1.5"), None),
+            ("a=b; Max-Age=", Some(""), None),
+            ("a=b; Max-Age= ", Some(""), None),
+            ("a=b; Max-Age=-", Some("-"), None),
+            ("a=b; Max-Age", None, None),
+            ("a=b", None, None),
+            ("a=b; Max-Age=1; Max-Age=2", Some("2"), Some(Duration::seconds(2))),
+        ];
+
+        for &(string, raw, max_age) in cases.iter() {
+            let cookie = Cookie::parse(string).unwrap();
+            assert_eq!(cookie.max_age_raw(), raw, "{}", string);
+            assert_eq!(cookie.max_age(), max_age, "{}", string);
+            assert_eq!(cookie.reborrow().max_age_raw(), raw);
+        }
+
+        // The raw value is forgotten once the max-age changes.
+        let mut cookie = Cookie::parse("a=b; Max-Age=10").unwrap();
+        cookie.set_max_age(Duration::seconds(10));
+        assert_eq!(cookie.max_age_raw(), None);
+
+        let mut cookie = Cookie::parse("a=b; Max-Age=10").unwrap();
+        cookie.apply_attributes("Max-Age=20");
+        assert_eq!(cookie.max_age_raw(), None);
+        assert_eq!(cookie.max_age(), Some(Duration::seconds(20)));
+
+        let mut cookie = Cookie::parse("a=b; Max-Age=10").unwrap();
+        cookie.apply_attributes("Path=/");
+        assert_eq!(cookie.max_age_raw(), Some("10"));
+
+        // Cookies not parsed from a borrowed string have no raw values.
+        let cookie = Cookie::parse(String::from("a=b; Max-Age=10")).unwrap();
+        assert_eq!(cookie.max_age_raw(), None);
+        assert_eq!(Cookie::new("a", "b").max_age_raw(), None);
+    }
+
+    #[test]
+    fn parse_with_warnings() {
+        use crate::Warning::DuplicateAttribute;
+
+        let (cookie, warnings) = Cookie::parse_with_warnings("a=b; Path=/; Path=/x").unwrap();
+        assert_eq!(cookie.path(), Some("/x"));
+        assert_eq!(warnings, [DuplicateAttribute("Path")]);
+
+        let (_, warnings) = Cookie::parse_with_warnings("a=b; Secure; path=/; SECURE; \
+            Max-Age=1; secure; Foo; Foo; PATH=/x").unwrap();
+        assert_eq!(warnings, [DuplicateAttribute("Secure"), DuplicateAttribute("Path")]);
+
+        let (_, warnings) = Cookie::parse_with_warnings("a=b; Path=/; Domain=x; Max-Age=1; \
+            Expires=Wed, 21 Oct 2015 07:28:00 GMT; Secure; HttpOnly; SameSite=Lax").unwrap();
+        assert!(warnings.is_empty());
+
+        // Invalid values are still duplicates; the pair is not an attribute.
+        let (_, warnings) = Cookie::parse_with_warnings("Path=a; Max-Age=x; Max-Age").unwrap();
+        assert_eq!(warnings, [DuplicateAttribute("Max-Age")]);
+
+        assert_eq!(Cookie::parse_with_warnings("; Path=/; Path=/").unwrap_err(),
+            ParseError::MissingPair);
+    }
+
+    #[test]
+    fn obsolete_comment_attributes() {
+        let cookie = Cookie::parse("a=b; Comment=hello").unwrap();
+        assert_eq!(cookie.extensions().collect::<Vec<_>>(), [("Comment", Some("hello"))]);
+        assert_eq!(cookie.to_string(), "a=b; Comment=hello");
+        assert_eq!(Cookie::parse(cookie.to_string()).unwrap().to_string(), cookie.to_string());
+
+        let cookie = Cookie::parse("a=b; Comment=\"hi; there\"; Path=/; \
+            CommentURL=\"http://example.com/why\"; Version=1").unwrap();
+
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.extensions().collect::<Vec<_>>(), [
+            ("Comment", Some("\"hi")),
+            ("there\"", None),
+            ("CommentURL", Some("\"http://example.com/why\"")),
+            ("Version", Some("1")),
+        ]);
+
+        let cookie = Cookie::parse("a=b; comment=\"hi\"; Secure").unwrap();
+        assert_eq!(cookie.extensions().collect::<Vec<_>>(), [("comment", Some("\"hi\""))]);
+        assert_eq!(cookie.to_string(), "a=b; Secure; comment=\"hi\"");
+        assert_eq!(cookie.into_owned().extensions().count(), 1);
+    }
+
+    #[test]
+    fn segments_match_split() {
+        use super::Segments;
+
+        let strings = ["", ";", "a=b", "a=b;", ";a=b", "a=b; c; d=e=f;;", "==;=", "é=ü;ß", "a→b=c→d"];
+        for s in strings.iter() {
+            for delimiter in [';', '=', '\n', '→'].iter() {
+                let expected: Vec<_> = s.split(*delimiter)
+                    .map(|seg| (seg, seg.find('=')))
+                    .collect();
+
+                let actual: Vec<_> = Segments::new(s, *delimiter).collect();
+                assert_eq!(actual, expected, "{:?} split by {:?}", s, delimiter);
+            }
+        }
+    }
 }
@@ -0,0 +1,55 @@
+/// Returns the _default-path_ of a cookie set in response to a request for
+/// `uri_path`, the path portion of the request URI, as specified by the
+/// [RFC 6265 §5.1.4] algorithm.
+///
+/// If `uri_path` is empty, doesn't begin with `/`, or contains no `/` other
+/// than the first character, the default-path is `/`. Otherwise, it is
+/// `uri_path` up to, but not including, the right-most `/`.
+///
+/// This is intended for client implementations, which should use the
+/// default-path as a cookie's path when the cookie has no `Path` attribute or
+/// its value doesn't begin with `/`.
+///
+/// [RFC 6265 §5.1.4]: https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.4
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::default_path_for;
+///
+/// assert_eq!(default_path_for("/"), "/");
+/// assert_eq!(default_path_for("/foo"), "/");
+/// assert_eq!(default_path_for("/foo/bar"), "/foo");
+/// assert_eq!(default_path_for("/foo/bar/"), "/foo/bar");
+/// assert_eq!(default_path_for("foo"), "/");
+/// ```
+pub fn default_path_for(uri_path: &str) -> String {
+    if !uri_path.starts_with('/') {
+        return "/".into();
+    }
+
+    match uri_path.rfind('/') {
+        Some(i) if i > 0 => uri_path[..i].into(),
+        _ => "/".into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::default_path_for;
+
+    #[test]
+    fn default_path() {
+        assert_eq!(default_path_for("/"), "/");
+        assert_eq!(default_path_for("/foo"), "/");
+        assert_eq!(default_path_for("/foo/bar"), "/foo");
+        assert_eq!(default_path_for("foo"), "/");
+
+        assert_eq!(default_path_for(""), "/");
+        assert_eq!(default_path_for("foo/bar"), "/");
+        assert_eq!(default_path_for("//"), "/");
+        assert_eq!(default_path_for("/foo/"), "/foo");
+        assert_eq!(default_path_for("/foo/bar/baz.html"), "/foo/bar");
+        assert_eq!(default_path_for("//foo"), "/");
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::marker::PhantomData;
 use std::borrow::{Borrow, BorrowMut, Cow};
 
@@ -18,9 +19,11 @@ use crate::{CookieJar, Cookie};
 ///
 /// [HTTP RFC6265 draft]:
 /// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-cookie-name-prefixes
-pub struct PrefixedJar<P: Prefix, J> {
+///
+/// `M` is the type of the parent jar's [metadata](CookieJar::set_meta()).
+pub struct PrefixedJar<P: Prefix, J, M = ()> {
     parent: J,
-    _prefix: PhantomData<fn() -> P>,
+    _prefix: PhantomData<fn() -> (P, M)>,
 }
 
 /// The [`"__Host-"`] cookie [`Prefix`].
@@ -39,6 +42,46 @@ pub struct Host;
 /// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-the-__secure-prefix
 pub struct Secure;
 
+/// The [`"__Host-"`] cookie [`Prefix`] for [partitioned] (CHIPS) cookies.
+///
+/// This prefix is identical to [`Host`] except that it additionally marks
+/// cookies as [partitioned]. Because both prefixes share the same prefix
+/// string, a cookie added via one can be retrieved via the other.
+///
+/// See [`Prefix`] and [`PrefixedJar`] for usage details.
+///
+/// [`"__Host-"`]:
+/// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-the-__host-prefix
+/// [partitioned]: https://developer.mozilla.org/en-US/docs/Web/Privacy/Partitioned_cookies
+pub struct HostPartitioned;
+
+/// A requirement of the [`"__Host-"`] prefix that a cookie fails to meet.
+///
+/// Returned by [`Cookie::host_prefix_violations()`].
+///
+/// [`"__Host-"`]:
+/// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-the-__host-prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HostPrefixViolation {
+    /// The cookie's [`secure`](Cookie::secure()) flag is not `Some(true)`.
+    MissingSecure,
+    /// The cookie's [`path`](Cookie::path()) is not `Some("/")`.
+    PathNotRoot,
+    /// The cookie has a [`domain`](Cookie::domain()).
+    HasDomain,
+}
+
+impl fmt::Display for HostPrefixViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostPrefixViolation::MissingSecure => write!(f, "missing Secure"),
+            HostPrefixViolation::PathNotRoot => write!(f, "Path is not /"),
+            HostPrefixViolation::HasDomain => write!(f, "has Domain"),
+        }
+    }
+}
+
 /// Trait identifying [HTTP RFC6265 draft] cookie prefixes.
 ///
 /// A [`Prefix`] can be applied to cookies via a child [`PrefixedJar`], itself
@@ -56,7 +99,8 @@ pub struct Secure;
 pub trait Prefix: private::Sealed {
     /// The prefix string to prepend.
     ///
-    /// See [`Host::PREFIX`] and [`Secure::PREFIX`] for specifics.
+    /// See [`Host::PREFIX`], [`HostPartitioned::PREFIX`], and
+    /// [`Secure::PREFIX`] for specifics.
     const PREFIX: &'static str;
 
     /// Alias to [`Host`].
@@ -67,9 +111,14 @@ pub trait Prefix: private::Sealed {
     #[allow(non_upper_case_globals)]
     const Secure: Secure = Secure;
 
+    /// Alias to [`HostPartitioned`].
+    #[allow(non_upper_case_globals)]
+    const HostPartitioned: HostPartitioned = HostPartitioned;
+
     /// Modify `cookie` so it conforms to the requirements of `self`.
     ///
-    /// See [`Host::conform()`] and [`Secure::conform()`] for specifics.
+    /// See [`Host::conform()`], [`HostPartitioned::conform()`], and
+    /// [`Secure::conform()`] for specifics.
     //
     // This is the only required method. Everything else is shared across
     // implementations via the default implementations below and should not be
@@ -132,14 +181,14 @@ pub trait Prefix: private::Sealed {
     }
 }
 
-impl<P: Prefix, J> PrefixedJar<P, J> {
+impl<P: Prefix, J, M> PrefixedJar<P, J, M> {
     #[inline(always)]
     pub(crate) fn new(parent: J) -> Self {
         Self { parent, _prefix: PhantomData }
     }
 }
 
-impl<P: Prefix, J: Borrow<CookieJar>> PrefixedJar<P, J> {
+impl<P: Prefix, J: Borrow<CookieJar<M>>, M> PrefixedJar<P, J, M> {
     /// Fetches the `Cookie` inside this jar with the prefix `P` and removes the
     /// prefix before returning it. If the cookie isn't found, returns `None`.
     ///
@@ -162,9 +211,69 @@ impl<P: Prefix, J: Borrow<CookieJar>> PrefixedJar<P, J> {
             .get(&P::prefixed_name(name))
             .map(|c| P::clip(c.clone()))
     }
+
+    /// Like [`PrefixedJar::get()`], but returns `None` if the stored cookie
+    /// doesn't meet the requirements of the prefix `P`, that is, if making it
+    /// [`conform`](Prefix::conform()) would modify it.
+    ///
+    /// Cookies added via [`PrefixedJar::add()`] always conform. This method
+    /// guards against a parent jar that was seeded with non-conforming
+    /// prefixed cookies from an untrusted source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, CookieJar};
+    /// use cookie::prefix::{Host, Secure};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.prefixed_mut(Host).add(("name", "value"));
+    /// assert!(jar.prefixed(Host).get_conforming("name").is_some());
+    ///
+    /// // A `__Host-` cookie with a `Domain` doesn't conform.
+    /// jar.add_original(Cookie::build(("__Host-bad", "value"))
+    ///     .secure(true)
+    ///     .path("/")
+    ///     .domain("example.com"));
+    ///
+    /// assert!(jar.prefixed(Host).get("bad").is_some());
+    /// assert!(jar.prefixed(Host).get_conforming("bad").is_none());
+    ///
+    /// // A `__Secure-` cookie without `Secure` doesn't conform.
+    /// jar.add_original(("__Secure-bad", "value"));
+    /// assert!(jar.prefixed(Secure).get("bad").is_some());
+    /// assert!(jar.prefixed(Secure).get_conforming("bad").is_none());
+    /// ```
+    pub fn get_conforming(&self, name: &str) -> Option<Cookie<'static>> {
+        let cookie = self.parent.borrow().get(&P::prefixed_name(name))?;
+        match P::conform(cookie.clone()).eq_exact(cookie) {
+            true => Some(P::clip(cookie.clone())),
+            false => None,
+        }
+    }
+
+    /// Returns `true` if the parent jar contains a cookie with the name `name`
+    /// prefixed with `P`. Unlike [`PrefixedJar::get()`], this doesn't clone
+    /// the cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::CookieJar;
+    /// use cookie::prefix::{Host, Secure};
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.prefixed_mut(Host).add(("name", "value"));
+    /// assert!(jar.prefixed(Host).contains("name"));
+    /// assert!(!jar.prefixed(Secure).contains("name"));
+    /// assert!(!jar.prefixed(Host).contains("other"));
+    /// ```
+    pub fn contains(&self, name: &str) -> bool {
+        self.parent.borrow().contains(&P::prefixed_name(name))
+    }
 }
 
-impl<P: Prefix, J: BorrowMut<CookieJar>> PrefixedJar<P, J> {
+impl<P: Prefix, J: BorrowMut<CookieJar<M>>, M> PrefixedJar<P, J, M> {
     /// Adds `cookie` to the parent jar. The cookie's name is prefixed with `P`,
     /// and the cookie's attributes are made to [`conform`](Prefix::conform()).
     ///
@@ -300,6 +409,66 @@ impl Prefix for Host {
     }
 }
 
+impl Prefix for HostPartitioned {
+    /// The [`"__Host-"` prefix] string.
+    ///
+    /// [`"__Host-"` prefix]:
+    /// https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-rfc6265bis#name-the-__host-prefix
+    const PREFIX: &'static str = Host::PREFIX;
+
+    /// Modify `cookie` so it conforms to the prefix's requirements.
+    ///
+    /// **Note: this method is called automatically by [`PrefixedJar`]. It _does
+    /// not need to_ and _should not_ be called manually under normal
+    /// circumstances.**
+    ///
+    /// Partitioned cookies, as specified by [CHIPS], must be set with the
+    /// `Secure` attribute, and are recommended to additionally satisfy the
+    /// requirements of the `__Host-` prefix. As such, to make a cookie
+    /// conform, this method:
+    ///
+    ///   * Makes the cookie conform to [`Host`] via [`Host::conform()`].
+    ///   * Sets [`partitioned`](Cookie::set_partitioned()) to `true`.
+    ///
+    /// [CHIPS]: https://datatracker.ietf.org/doc/html/draft-cutler-httpbis-partitioned-cookies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, prefix::HostPartitioned};
+    ///
+    /// // A cookie with some non-conformant properties.
+    /// let cookie = Cookie::build(("name", "some-value"))
+    ///     .secure(false)
+    ///     .path("/foo/bar")
+    ///     .domain("rocket.rs")
+    ///     .http_only(true);
+    ///
+    /// // Add the cookie to the jar.
+    /// let mut jar = CookieJar::new();
+    /// jar.prefixed_mut(HostPartitioned).add(cookie);
+    ///
+    /// // Fetch the cookie: notice it's been made to conform.
+    /// let cookie = jar.prefixed(HostPartitioned).get("name").unwrap();
+    /// assert_eq!(cookie.name(), "name");
+    /// assert_eq!(cookie.value(), "some-value");
+    /// assert_eq!(cookie.secure(), Some(true));
+    /// assert_eq!(cookie.path(), Some("/"));
+    /// assert_eq!(cookie.domain(), None);
+    /// assert_eq!(cookie.partitioned(), Some(true));
+    /// assert_eq!(cookie.http_only(), Some(true));
+    ///
+    /// // The cookie is emitted with all of the attributes CHIPS requires.
+    /// let raw = jar.get("__Host-name").unwrap().to_string();
+    /// assert_eq!(raw, "__Host-name=some-value; HttpOnly; Partitioned; Secure; Path=/");
+    /// ```
+    fn conform(cookie: Cookie<'_>) -> Cookie<'_> {
+        let mut cookie = Host::conform(cookie);
+        cookie.set_partitioned(true);
+        cookie
+    }
+}
+
 impl Prefix for Secure {
     /// The [`"__Secure-"` prefix] string.
     ///
@@ -363,5 +532,6 @@ mod private {
     pub trait Sealed {}
 
     impl Sealed for super::Host {}
+    impl Sealed for super::HostPartitioned {}
     impl Sealed for super::Secure {}
 }
@@ -95,6 +95,40 @@ impl SameSite {
             SameSite::Lax | SameSite::Strict => false
         }
     }
+
+    /// Returns `true` if `self` is at least as strict as `other` and `false`
+    /// otherwise. Policies are ordered from least to most strict as `None`,
+    /// `Lax`, `Strict`.
+    ///
+    /// A cookie without a `SameSite` attribute is treated by most browsers as
+    /// `Lax`. To compare the attribute of such a cookie, use
+    /// `cookie.same_site().unwrap_or(SameSite::Lax)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Cookie, SameSite};
+    ///
+    /// assert!(SameSite::Strict.is_at_least(SameSite::Lax));
+    /// assert!(SameSite::Lax.is_at_least(SameSite::Lax));
+    /// assert!(!SameSite::None.is_at_least(SameSite::Lax));
+    /// assert!(!SameSite::Lax.is_at_least(SameSite::Strict));
+    ///
+    /// let c = Cookie::new("name", "value");
+    /// assert!(c.same_site().unwrap_or(SameSite::Lax).is_at_least(SameSite::Lax));
+    /// ```
+    #[inline]
+    pub fn is_at_least(&self, other: SameSite) -> bool {
+        self.strictness() >= other.strictness()
+    }
+
+    fn strictness(&self) -> u8 {
+        match *self {
+            SameSite::None => 0,
+            SameSite::Lax => 1,
+            SameSite::Strict => 2,
+        }
+    }
 }
 
 impl fmt::Display for SameSite {
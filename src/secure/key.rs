@@ -1,4 +1,6 @@
 use std::convert::TryFrom;
+use std::str::FromStr;
+use std::{fs, io, path::Path};
 
 const SIGNING_KEY_LEN: usize = 32;
 const ENCRYPTION_KEY_LEN: usize = 32;
@@ -16,6 +18,13 @@ const_assert!(crate::secure::private::KEY_LEN == ENCRYPTION_KEY_LEN);
 /// [`PrivateJar`](crate::PrivateJar) and [`SignedJar`](crate::SignedJar). A
 /// single instance of a `Key` can be used for both a `PrivateJar` and a
 /// `SignedJar` simultaneously with no notable security implications.
+///
+/// This is because no key material is shared between the two: the
+/// [signing](Key::signing()) and [encryption](Key::encryption()) keys are the
+/// first and last 32 bytes, respectively, of the 64-byte master key. Provided
+/// the master key is uniformly random, as it is when generated by
+/// [`Key::generate()`] or derived via HKDF by [`Key::derive_from()`], the two
+/// keys are independent.
 #[cfg_attr(all(nightly, doc), doc(cfg(any(feature = "private", feature = "signed"))))]
 #[derive(Clone)]
 pub struct Key([u8; COMBINED_KEY_LENGTH /* SIGNING | ENCRYPTION */]);
@@ -35,6 +44,18 @@ impl std::fmt::Debug for Key {
 }
 
 impl Key {
+    /// The length, in bytes, of a master key: the signing key followed by the
+    /// encryption key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Key;
+    ///
+    /// assert_eq!(Key::generate().master().len(), Key::LEN);
+    /// ```
+    pub const LEN: usize = COMBINED_KEY_LENGTH;
+
     // An empty key structure, to be filled.
     const fn zero() -> Self {
         Key([0; COMBINED_KEY_LENGTH])
@@ -146,7 +167,7 @@ impl Key {
     }
 
     /// Returns the raw bytes of a key suitable for signing cookies. Guaranteed
-    /// to be at least 32 bytes.
+    /// to be at least 32 bytes and disjoint from [`Key::encryption()`].
     ///
     /// # Example
     ///
@@ -161,7 +182,7 @@ impl Key {
     }
 
     /// Returns the raw bytes of a key suitable for encrypting cookies.
-    /// Guaranteed to be at least 32 bytes.
+    /// Guaranteed to be at least 32 bytes and disjoint from [`Key::signing()`].
     ///
     /// # Example
     ///
@@ -189,6 +210,188 @@ impl Key {
     pub fn master(&self) -> &[u8] {
         &self.0
     }
+
+    /// Returns the master key of `self`, both the signing and encryption keys,
+    /// encoded as standard, padded base64. The key can be decoded with
+    /// [`Key::from_base64()`].
+    ///
+    /// This is suitable for storing a key in an environment variable or
+    /// configuration file. The result must be kept secret.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Key;
+    ///
+    /// let key = Key::generate();
+    /// let encoded = key.to_base64();
+    /// assert_eq!(Key::from_base64(&encoded).unwrap(), key);
+    /// ```
+    pub fn to_base64(&self) -> String {
+        crate::secure::base64::encode(self.master())
+    }
+
+    /// Decodes a `Key` from `string`, a master key encoded as standard, padded
+    /// base64, as returned by [`Key::to_base64()`]. Leading and trailing
+    /// whitespace in `string` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyError::InvalidBase64`] if `string` isn't valid base64 and
+    /// [`KeyError::InvalidLength`] if the decoded key isn't exactly
+    /// [`Key::LEN`] bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Key, KeyError};
+    ///
+    /// # /*
+    /// let key = Key::from_base64(&std::env::var("COOKIE_KEY").unwrap()).unwrap();
+    /// # */
+    ///
+    /// let key = Key::generate();
+    /// assert_eq!(Key::from_base64(&key.to_base64()).unwrap(), key);
+    ///
+    /// let err = Key::from_base64("c2hvcnQ=").unwrap_err();
+    /// assert!(matches!(err, KeyError::InvalidLength(5)));
+    ///
+    /// let err = Key::from_base64("not base64!").unwrap_err();
+    /// assert!(matches!(err, KeyError::InvalidBase64));
+    /// ```
+    pub fn from_base64(string: &str) -> Result<Key, KeyError> {
+        let bytes = crate::secure::base64::decode(string.trim())
+            .map_err(|_| KeyError::InvalidBase64)?;
+
+        if bytes.len() != Key::LEN {
+            return Err(KeyError::InvalidLength(bytes.len()));
+        }
+
+        Key::try_from(&*bytes)
+    }
+
+    /// Decodes a `Key` from `string`, a master key encoded as hexadecimal with
+    /// two digits per byte. Both uppercase and lowercase digits are accepted,
+    /// and leading and trailing whitespace in `string` is ignored. This is
+    /// also the format accepted by `Key`'s [`FromStr`] implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyError::InvalidHex`] if `string` contains a non-hex digit
+    /// or an odd number of digits and [`KeyError::InvalidLength`] if the
+    /// decoded key isn't exactly [`Key::LEN`] bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{Key, KeyError};
+    ///
+    /// let hex: String = (0..64u8).map(|b| format!("{:02x}", b)).collect();
+    /// let key = Key::from_hex(&hex).unwrap();
+    /// assert_eq!(key.master(), &(0..64).collect::<Vec<u8>>()[..]);
+    /// assert_eq!(hex.parse::<Key>().unwrap(), key);
+    ///
+    /// let err = Key::from_hex("abcd").unwrap_err();
+    /// assert!(matches!(err, KeyError::InvalidLength(2)));
+    ///
+    /// let err = Key::from_hex("not hex!").unwrap_err();
+    /// assert!(matches!(err, KeyError::InvalidHex));
+    /// ```
+    pub fn from_hex(string: &str) -> Result<Key, KeyError> {
+        fn digit(byte: u8) -> Result<u8, KeyError> {
+            match byte {
+                b'0'..=b'9' => Ok(byte - b'0'),
+                b'a'..=b'f' => Ok(byte - b'a' + 10),
+                b'A'..=b'F' => Ok(byte - b'A' + 10),
+                _ => Err(KeyError::InvalidHex),
+            }
+        }
+
+        let pairs = string.trim().as_bytes().chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(KeyError::InvalidHex);
+        }
+
+        let bytes = pairs
+            .map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?))
+            .collect::<Result<Vec<u8>, KeyError>>()?;
+
+        if bytes.len() != Key::LEN {
+            return Err(KeyError::InvalidLength(bytes.len()));
+        }
+
+        Key::try_from(&*bytes)
+    }
+
+    /// Reads a `Key` from the file at `path`.
+    ///
+    /// The file may contain either the raw bytes of a master key or the master
+    /// key encoded as standard, padded base64, optionally surrounded by
+    /// whitespace, as written by [`Key::write_to_file()`]. The encoding is
+    /// detected automatically. As with [`Key::from()`], the key material must
+    /// be at least 64 bytes.
+    ///
+    /// Key files should only be readable by the user the application runs as.
+    /// This method does not check the permissions of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or if the key material is
+    /// too short, in which case the error's kind is
+    /// [`io::ErrorKind::InvalidData`] and it wraps a [`KeyError`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use cookie::Key;
+    ///
+    /// let key = Key::from_file("/etc/app/cookie.key").expect("valid key file");
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Key> {
+        let data = fs::read(path)?;
+        let decoded = std::str::from_utf8(&data).ok()
+            .and_then(|s| crate::secure::base64::decode(s.trim()).ok())
+            .filter(|bytes| bytes.len() >= COMBINED_KEY_LENGTH);
+
+        let material = decoded.as_deref().unwrap_or(&data);
+        Key::try_from(material).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the master key of `self` to the file at `path` as base64,
+    /// followed by a newline, creating the file if it does not exist and
+    /// truncating it if it does. The file can be read back with
+    /// [`Key::from_file()`].
+    ///
+    /// On Unix, a newly created file is readable and writable only by its
+    /// owner (mode `0600`). The permissions of an existing file are left
+    /// unchanged. On other platforms, the platform's default permissions are
+    /// used. In all cases, ensure the file is not readable by other users.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use cookie::Key;
+    ///
+    /// let key = Key::generate();
+    /// key.write_to_file("/etc/app/cookie.key").expect("written key file");
+    /// assert_eq!(Key::from_file("/etc/app/cookie.key").unwrap(), key);
+    /// ```
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        use std::io::Write;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)] {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(path)?;
+        file.write_all(self.to_base64().as_bytes())?;
+        file.write_all(b"\n")?;
+        file.sync_all()
+    }
 }
 
 /// An error indicating an issue with generating or constructing a key.
@@ -200,6 +403,13 @@ pub enum KeyError {
     ///
     /// See [`Key::from()`] for minimum requirements.
     TooShort(usize),
+    /// A key passed to [`Key::from_base64()`] was not valid base64.
+    InvalidBase64,
+    /// A key passed to [`Key::from_base64()`] or [`Key::from_hex()`] decoded
+    /// to a number of bytes (`.0`) other than [`Key::LEN`].
+    InvalidLength(usize),
+    /// A key passed to [`Key::from_hex()`] was not valid hexadecimal.
+    InvalidHex,
 }
 
 impl std::error::Error for KeyError { }
@@ -211,6 +421,12 @@ impl std::fmt::Display for KeyError {
                 write!(f, "key material is too short: expected >= {} bytes, got {} bytes",
                        COMBINED_KEY_LENGTH, n)
             }
+            KeyError::InvalidBase64 => write!(f, "key is not valid standard base64"),
+            KeyError::InvalidHex => write!(f, "key is not valid hexadecimal"),
+            KeyError::InvalidLength(n) => {
+                write!(f, "decoded key has the wrong length: expected {} bytes, got {} bytes",
+                       COMBINED_KEY_LENGTH, n)
+            }
         }
     }
 }
@@ -251,6 +467,25 @@ impl TryFrom<&[u8]> for Key {
     }
 }
 
+impl FromStr for Key {
+    type Err = KeyError;
+
+    /// Decodes a `Key` from hexadecimal as by [`Key::from_hex()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::Key;
+    ///
+    /// let hex = "00".repeat(Key::LEN);
+    /// let key: Key = hex.parse().unwrap();
+    /// assert_eq!(key.master(), &[0; Key::LEN][..]);
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Key::from_hex(string)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Key;
@@ -296,6 +531,38 @@ mod test {
         assert_ne!(key_2.encryption(), key_a.encryption());
     }
 
+    #[test]
+    #[cfg(all(feature = "signed", feature = "private"))]
+    fn signing_and_encryption_are_separate() {
+        use crate::{Cookie, CookieJar};
+
+        let key = Key::generate();
+        assert_ne!(key.signing(), key.encryption());
+        assert_eq!([key.signing(), key.encryption()].concat(), key.master());
+
+        // A signed value doesn't decrypt and an encrypted value doesn't verify.
+        let mut jar = CookieJar::new();
+        jar.signed_mut(&key).add(("signed", "value"));
+        jar.private_mut(&key).add(("private", "value"));
+
+        let signed = jar.get("signed").cloned().unwrap();
+        let private = jar.get("private").cloned().unwrap();
+        assert!(jar.private(&key).decrypt(signed.clone()).is_none());
+        assert!(jar.signed(&key).verify(private.clone()).is_none());
+
+        // Even when the names match.
+        let private = Cookie::new("signed", private.value().to_string());
+        let signed = Cookie::new("private", signed.value().to_string());
+        assert!(jar.private(&key).decrypt(signed).is_none());
+        assert!(jar.signed(&key).verify(private).is_none());
+
+        // And the same holds for a key derived from a master key.
+        #[cfg(feature = "key-expansion")] {
+            let key = Key::derive_from(&[7; 32]);
+            assert_ne!(key.signing(), key.encryption());
+        }
+    }
+
     #[test]
     fn non_deterministic_generate() {
         let key_a = Key::generate();
@@ -305,6 +572,88 @@ mod test {
         assert_ne!(key_a.encryption(), key_b.encryption());
     }
 
+    #[test]
+    fn file_roundtrip() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("cookie-key-test-{}", std::process::id()));
+
+        let key = Key::generate();
+        key.write_to_file(&path).unwrap();
+        assert_eq!(Key::from_file(&path).unwrap(), key);
+
+        #[cfg(unix)] {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Raw key material is read as-is.
+        std::fs::write(&path, key.master()).unwrap();
+        assert_eq!(Key::from_file(&path).unwrap(), key);
+
+        // Too-short material is rejected.
+        std::fs::write(&path, &key.master()[..32]).unwrap();
+        let err = Key::from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn from_hex() {
+        use super::KeyError;
+
+        let key = Key::generate();
+        let hex: String = key.master().iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex.len(), 128);
+        assert_eq!(Key::from_hex(&hex).unwrap(), key);
+        assert_eq!(Key::from_hex(&hex.to_uppercase()).unwrap(), key);
+        assert_eq!(Key::from_hex(&format!(" {}\n", hex)).unwrap(), key);
+        assert_eq!(hex.parse::<Key>().unwrap(), key);
+
+        // Too short and too long.
+        assert!(matches!(Key::from_hex(&hex[..126]), Err(KeyError::InvalidLength(63))));
+        assert!(matches!(Key::from_hex(&format!("{}00", hex)), Err(KeyError::InvalidLength(65))));
+        assert!(matches!(Key::from_hex(""), Err(KeyError::InvalidLength(0))));
+
+        // Invalid hex.
+        assert!(matches!(Key::from_hex(&hex[..127]), Err(KeyError::InvalidHex)));
+        assert!(matches!(Key::from_hex(&format!("{}zz", &hex[..126])), Err(KeyError::InvalidHex)));
+        assert!(matches!(Key::from_hex(&format!("{}é", &hex[..126])), Err(KeyError::InvalidHex)));
+        assert!(matches!("+1".repeat(64).parse::<Key>(), Err(KeyError::InvalidHex)));
+    }
+
+    #[test]
+    fn base64_roundtrip() {
+        use super::KeyError;
+
+        let key = Key::generate();
+        let encoded = key.to_base64();
+        assert_eq!(encoded.len(), 88);
+        assert_eq!(Key::from_base64(&encoded).unwrap(), key);
+        assert_eq!(Key::from_base64(&format!(" {}\n", encoded)).unwrap(), key);
+
+        #[cfg(feature = "signed")] {
+            use crate::CookieJar;
+
+            let decoded = Key::from_base64(&encoded).unwrap();
+            let mut jar = CookieJar::new();
+            jar.signed_mut(&key).add(("a", "value"));
+            jar.signed_mut(&decoded).add(("b", "value"));
+            assert_eq!(jar.get("a").unwrap().value(), jar.get("b").unwrap().value());
+            assert_eq!(jar.signed(&decoded).get("a").unwrap().value(), "value");
+        }
+
+        let short = crate::secure::base64::encode(&key.master()[..63]);
+        assert!(matches!(Key::from_base64(&short), Err(KeyError::InvalidLength(63))));
+
+        let long = crate::secure::base64::encode([key.master(), &[0]].concat());
+        assert!(matches!(Key::from_base64(&long), Err(KeyError::InvalidLength(65))));
+
+        assert!(matches!(Key::from_base64(&encoded[1..]), Err(KeyError::InvalidBase64)));
+        assert!(matches!(Key::from_base64(""), Err(KeyError::InvalidLength(0))));
+    }
+
     #[test]
     fn debug_does_not_leak_key() {
         let key = Key::generate();
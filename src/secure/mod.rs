@@ -1,6 +1,6 @@
 extern crate rand;
 
-mod base64 {
+pub(crate) mod base64 {
     use base64::{DecodeError, Engine, prelude::BASE64_STANDARD};
 
     /// Encode `input` as the standard base64 with padding.
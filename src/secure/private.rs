@@ -2,9 +2,10 @@ extern crate aes_gcm;
 
 use std::convert::TryInto;
 use std::borrow::{Borrow, BorrowMut};
+use std::marker::PhantomData;
 
 use crate::secure::{base64, rand, Key};
-use crate::{Cookie, CookieJar};
+use crate::{AddError, Cookie, CookieJar};
 
 use self::aes_gcm::aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit, Payload};
 use self::aes_gcm::Aes256Gcm;
@@ -16,6 +17,52 @@ pub(crate) const NONCE_LEN: usize = 12;
 pub(crate) const TAG_LEN: usize = 16;
 pub(crate) const KEY_LEN: usize = 32;
 
+/// Encrypts `plaintext` with authenticated encryption under `key`, binding it
+/// to the associated data `aad`, and returns the base64 encoding of
+/// `[nonce | ciphertext | tag]`.
+pub(crate) fn seal(key: &[u8], aad: &[u8], plaintext: &[u8]) -> String {
+    // Create a vec to hold the [nonce | plaintext | tag].
+    let mut data = vec![0; NONCE_LEN + plaintext.len() + TAG_LEN];
+
+    // Split data into three: nonce, input/output, tag. Copy input.
+    let (nonce, in_out) = data.split_at_mut(NONCE_LEN);
+    let (in_out, tag) = in_out.split_at_mut(plaintext.len());
+    in_out.copy_from_slice(plaintext);
+
+    // Fill nonce piece with random data.
+    let mut rng = self::rand::thread_rng();
+    rng.try_fill_bytes(nonce).expect("couldn't random fill nonce");
+    let nonce = GenericArray::clone_from_slice(nonce);
+
+    // Perform the actual sealing operation.
+    let aead = Aes256Gcm::new(GenericArray::from_slice(key));
+    let aad_tag = aead.encrypt_in_place_detached(&nonce, aad, in_out)
+        .expect("encryption failure!");
+
+    // Copy the tag into the tag piece.
+    tag.copy_from_slice(&aad_tag);
+
+    // Base64 encode [nonce | encrypted value | tag].
+    base64::encode(&data)
+}
+
+/// Verifies and decrypts `sealed`, as produced by [`seal()`] with the same
+/// `key` and `aad`, returning the plaintext. If there's a problem, returns an
+/// `Err` with a string describing the issue.
+pub(crate) fn open(key: &[u8], aad: &[u8], sealed: &str) -> Result<Vec<u8>, &'static str> {
+    let data = base64::decode(sealed).map_err(|_| "bad base64 value")?;
+    if data.len() <= NONCE_LEN {
+        return Err("length of decoded data is <= NONCE_LEN");
+    }
+
+    let (nonce, cipher) = data.split_at(NONCE_LEN);
+    let payload = Payload { msg: cipher, aad };
+
+    let aead = Aes256Gcm::new(GenericArray::from_slice(key));
+    aead.decrypt(GenericArray::from_slice(nonce), payload)
+        .map_err(|_| "invalid key/nonce/value: bad seal")
+}
+
 /// A child cookie jar that provides authenticated encryption for its cookies.
 ///
 /// A _private_ child jar signs and encrypts all the cookies added to it and
@@ -23,68 +70,67 @@ pub(crate) const KEY_LEN: usize = 32;
 /// `PrivateJar` are simultaneously assured confidentiality, integrity, and
 /// authenticity. In other words, clients cannot discover nor tamper with the
 /// contents of a cookie, nor can they fabricate cookie data.
+///
+/// `M` is the type of the parent jar's [metadata](CookieJar::set_meta()).
 #[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
-pub struct PrivateJar<J> {
+pub struct PrivateJar<J, M = ()> {
     parent: J,
-    key: [u8; KEY_LEN]
+    key: [u8; KEY_LEN],
+    fallback_keys: Vec<[u8; KEY_LEN]>,
+    _meta: PhantomData<fn() -> M>,
 }
 
-impl<J> PrivateJar<J> {
+impl<J, M> PrivateJar<J, M> {
     /// Creates a new child `PrivateJar` with parent `parent` and key `key`.
     /// This method is typically called indirectly via the `signed` method of
     /// `CookieJar`.
-    pub(crate) fn new(parent: J, key: &Key) -> PrivateJar<J> {
-        PrivateJar { parent, key: key.encryption().try_into().expect("enc key len") }
+    pub(crate) fn new(parent: J, key: &Key) -> PrivateJar<J, M> {
+        let key = key.encryption().try_into().expect("enc key len");
+        PrivateJar { parent, key, fallback_keys: vec![], _meta: PhantomData }
+    }
+
+    /// Creates a new child `PrivateJar` with parent `parent` that encrypts with
+    /// the first key in `keys` and decrypts with any of them, trying each in
+    /// order. This method is typically called indirectly via the
+    /// `private_rotatable` method of `CookieJar`.
+    ///
+    /// Panics if `keys` is empty.
+    pub(crate) fn new_rotatable(parent: J, keys: &[&Key]) -> PrivateJar<J, M> {
+        let (key, fallback_keys) = keys.split_first()
+            .expect("`PrivateJar` requires at least one key");
+
+        let mut jar = PrivateJar::new(parent, key);
+        jar.fallback_keys = fallback_keys.iter()
+            .map(|key| key.encryption().try_into().expect("enc key len"))
+            .collect();
+
+        jar
     }
 
     /// Encrypts the cookie's value with authenticated encryption providing
     /// confidentiality, integrity, and authenticity.
     fn encrypt_cookie(&self, cookie: &mut Cookie) {
-        // Create a vec to hold the [nonce | cookie value | tag].
-        let cookie_val = cookie.value().as_bytes();
-        let mut data = vec![0; NONCE_LEN + cookie_val.len() + TAG_LEN];
-
-        // Split data into three: nonce, input/output, tag. Copy input.
-        let (nonce, in_out) = data.split_at_mut(NONCE_LEN);
-        let (in_out, tag) = in_out.split_at_mut(cookie_val.len());
-        in_out.copy_from_slice(cookie_val);
-
-        // Fill nonce piece with random data.
-        let mut rng = self::rand::thread_rng();
-        rng.try_fill_bytes(nonce).expect("couldn't random fill nonce");
-        let nonce = GenericArray::clone_from_slice(nonce);
-
-        // Perform the actual sealing operation, using the cookie's name as
-        // associated data to prevent value swapping.
-        let aad = cookie.name().as_bytes();
-        let aead = Aes256Gcm::new(GenericArray::from_slice(&self.key));
-        let aad_tag = aead.encrypt_in_place_detached(&nonce, aad, in_out)
-            .expect("encryption failure!");
-
-        // Copy the tag into the tag piece.
-        tag.copy_from_slice(&aad_tag);
-
-        // Base64 encode [nonce | encrypted value | tag].
-        cookie.set_value(base64::encode(&data));
+        // Use the cookie's name as associated data to prevent value swapping.
+        let sealed = seal(&self.key, cookie.name().as_bytes(), cookie.value().as_bytes());
+        cookie.set_value(sealed);
     }
 
     /// Given a sealed value `str` and a key name `name`, where the nonce is
     /// prepended to the original value and then both are Base64 encoded,
     /// verifies and decrypts the sealed value and returns it. If there's a
     /// problem, returns an `Err` with a string describing the issue.
+    ///
+    /// The value is decrypted with the primary key and then with each of the
+    /// fallback keys, if any, in order until decryption succeeds.
     fn unseal(&self, name: &str, value: &str) -> Result<String, &'static str> {
-        let data = base64::decode(value).map_err(|_| "bad base64 value")?;
-        if data.len() <= NONCE_LEN {
-            return Err("length of decoded data is <= NONCE_LEN");
+        let mut result = open(&self.key, name.as_bytes(), value);
+        for key in &self.fallback_keys {
+            if result.is_err() {
+                result = open(key, name.as_bytes(), value);
+            }
         }
 
-        let (nonce, cipher) = data.split_at(NONCE_LEN);
-        let payload = Payload { msg: cipher, aad: name.as_bytes() };
-
-        let aead = Aes256Gcm::new(GenericArray::from_slice(&self.key));
-        aead.decrypt(GenericArray::from_slice(nonce), payload)
-            .map_err(|_| "invalid key/nonce/value: bad seal")
-            .and_then(|s| String::from_utf8(s).map_err(|_| "bad unsealed utf8"))
+        result.and_then(|s| String::from_utf8(s).map_err(|_| "bad unsealed utf8"))
     }
 
     /// Authenticates and decrypts `cookie`, returning the plaintext version if
@@ -122,7 +168,7 @@ impl<J> PrivateJar<J> {
     }
 }
 
-impl<J: Borrow<CookieJar>> PrivateJar<J> {
+impl<J: Borrow<CookieJar<M>>, M> PrivateJar<J, M> {
     /// Returns a reference to the `Cookie` inside this jar with the name `name`
     /// and authenticates and decrypts the cookie's value, returning a `Cookie`
     /// with the decrypted value. If the cookie cannot be found, or the cookie
@@ -147,11 +193,18 @@ impl<J: Borrow<CookieJar>> PrivateJar<J> {
     }
 }
 
-impl<J: BorrowMut<CookieJar>> PrivateJar<J> {
+impl<J: BorrowMut<CookieJar<M>>, M> PrivateJar<J, M> {
     /// Adds `cookie` to the parent jar. The cookie's value is encrypted with
     /// authenticated encryption assuring confidentiality, integrity, and
     /// authenticity.
     ///
+    /// The value is always encrypted, even if it is _already_ sealed. Adding a
+    /// cookie retrieved from the parent jar, rather than from the private jar,
+    /// thus seals its value twice: retrieving it from the private jar then
+    /// yields the sealed value, not the original plaintext. Retrieve cookies
+    /// via [`PrivateJar::get()`] before adding them back, or use
+    /// [`PrivateJar::reseal()`] to add a cookie that may already be sealed.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -170,6 +223,67 @@ impl<J: BorrowMut<CookieJar>> PrivateJar<J> {
         self.parent.borrow_mut().add(cookie);
     }
 
+    /// Adds `cookie`, which may already be sealed, to the parent jar, sealing
+    /// its value exactly once.
+    ///
+    /// If the value of `cookie` decrypts under the keys of `self` as the value
+    /// of a cookie with the same name, as it does for a cookie retrieved from
+    /// the parent jar, it is decrypted and sealed anew with the primary key.
+    /// Otherwise, the value is sealed as by [`PrivateJar::add()`]. Either
+    /// way, retrieving the cookie from the private jar yields the plaintext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.private_mut(&key).add(Cookie::new("name", "value"));
+    ///
+    /// // `add()` would seal the already sealed value a second time.
+    /// let sealed = jar.get("name").cloned().unwrap();
+    /// jar.private_mut(&key).reseal(sealed);
+    /// assert_eq!(jar.private(&key).get("name").unwrap().value(), "value");
+    ///
+    /// jar.private_mut(&key).reseal(Cookie::new("name", "plain"));
+    /// assert_eq!(jar.private(&key).get("name").unwrap().value(), "plain");
+    /// ```
+    pub fn reseal<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
+        let mut cookie = cookie.into();
+        if let Ok(value) = self.unseal(cookie.name(), cookie.value()) {
+            cookie.set_value(value);
+        }
+
+        self.add(cookie);
+    }
+
+    /// Adds `cookie` to the parent jar exactly as [`PrivateJar::add()`] does if
+    /// the resulting encrypted cookie satisfies the parent jar's policies,
+    /// returning an error and leaving the jar unchanged otherwise.
+    ///
+    /// The policies are checked against the encrypted cookie, which is
+    /// larger than `cookie`. See [`CookieJar::try_add()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.set_max_cookie_size(128);
+    ///
+    /// assert!(jar.private_mut(&key).try_add(("name", "value")).is_ok());
+    /// assert!(jar.private_mut(&key).try_add(("big", "x".repeat(128))).is_err());
+    /// assert!(jar.get("big").is_none());
+    /// ```
+    pub fn try_add<C: Into<Cookie<'static>>>(&mut self, cookie: C) -> Result<(), AddError> {
+        let mut cookie = cookie.into();
+        self.encrypt_cookie(&mut cookie);
+        self.parent.borrow_mut().try_add(cookie)
+    }
+
     /// Adds an "original" `cookie` to parent jar. The cookie's value is
     /// encrypted with authenticated encryption assuring confidentiality,
     /// integrity, and authenticity. Adding an original cookie does not affect
@@ -226,9 +340,48 @@ impl<J: BorrowMut<CookieJar>> PrivateJar<J> {
     }
 }
 
+/// Splits the value of a cookie encrypted by a [`PrivateJar`] into its base64
+/// encoded nonce and its base64 encoded payload, the ciphertext followed by
+/// the authentication tag, without decrypting it.
+///
+/// Because the nonce is a multiple of three bytes long, both slices are valid
+/// base64 on their own. Returns `None` if `value` is too short to contain a
+/// nonce and tag. This is intended for debugging; use [`PrivateJar::get()`] or
+/// [`PrivateJar::decrypt()`] to obtain plaintext values.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::{CookieJar, Key, inspect_private};
+///
+/// let mut jar = CookieJar::new();
+/// jar.private_mut(&Key::generate()).add(("name", "value"));
+///
+/// let sealed = jar.get("name").unwrap().value();
+/// let (nonce, payload) = inspect_private(sealed).unwrap();
+/// assert_eq!(nonce.len(), 16);
+/// assert_eq!(format!("{}{}", nonce, payload), sealed);
+///
+/// assert!(inspect_private("too short").is_none());
+/// ```
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "private")))]
+pub fn inspect_private(value: &str) -> Option<(&str, &str)> {
+    // The base64 length of the `NONCE_LEN` byte nonce and of a sealed empty
+    // value, i.e, of `NONCE_LEN + TAG_LEN` bytes.
+    const BASE64_NONCE_LEN: usize = 16;
+    const BASE64_MIN_LEN: usize = 40;
+
+    if value.len() < BASE64_MIN_LEN || !value.is_char_boundary(BASE64_NONCE_LEN) {
+        return None;
+    }
+
+    Some(value.split_at(BASE64_NONCE_LEN))
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{CookieJar, Cookie, Key};
+    use crate::{AddError, CookieJar, Cookie, Key};
+    use super::inspect_private;
 
     #[test]
     fn simple() {
@@ -244,6 +397,227 @@ mod test {
         assert_secure_behaviour!(jar, jar.private_mut(&key));
     }
 
+    #[test]
+    fn double_seal() {
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.private_mut(&key).add(("name", "value"));
+        let sealed = jar.get("name").cloned().unwrap();
+
+        // Adding a cookie from the parent jar seals its value a second time.
+        jar.private_mut(&key).add(sealed.clone());
+        assert_ne!(jar.get("name").unwrap().value(), sealed.value());
+        assert_eq!(jar.private(&key).get("name").unwrap().value(), sealed.value());
+
+        jar.private_mut(&key).add_original(sealed.clone());
+        assert_eq!(jar.private(&key).get("name").unwrap().value(), sealed.value());
+
+        // Values are always sealed, even ones that open under another name.
+        jar.private_mut(&key).add(("other", sealed.value().to_string()));
+        assert_eq!(jar.private(&key).get("other").unwrap().value(), sealed.value());
+
+        // Adding the retrieved plaintext round-trips.
+        let cookie = jar.private(&key).get("other").unwrap();
+        jar.private_mut(&key).add(cookie);
+        assert_eq!(jar.private(&key).get("other").unwrap().value(), sealed.value());
+
+        // `reseal` guards against double sealing.
+        jar.private_mut(&key).reseal(sealed.clone());
+        assert_ne!(jar.get("name").unwrap().value(), sealed.value());
+        assert_eq!(jar.private(&key).get("name").unwrap().value(), "value");
+
+        jar.private_mut(&key).reseal(("name", "plain"));
+        assert_eq!(jar.private(&key).get("name").unwrap().value(), "plain");
+
+        // Values are only unsealed under the same name.
+        jar.private_mut(&key).reseal(("other", sealed.value().to_string()));
+        assert_eq!(jar.private(&key).get("other").unwrap().value(), sealed.value());
+
+        // Values sealed with a fallback key are sealed anew with the primary.
+        let new_key = Key::generate();
+        jar.private_rotatable_mut(&[&new_key, &key]).reseal(sealed.clone());
+        assert_eq!(jar.private(&new_key).get("name").unwrap().value(), "value");
+        assert!(jar.private(&key).get("name").is_none());
+    }
+
+    #[test]
+    fn rotation() {
+        let (old_key, new_key) = (Key::generate(), Key::generate());
+        let mut jar = CookieJar::new();
+        jar.private_mut(&old_key).add(("name", "value"));
+        let sealed = jar.get("name").cloned().unwrap();
+
+        // The new key alone can't decrypt; the rotatable jar can.
+        assert!(jar.private(&new_key).get("name").is_none());
+        let rotated = jar.private_rotatable(&[&new_key, &old_key]);
+        assert_eq!(rotated.get("name").unwrap().value(), "value");
+        assert_eq!(rotated.decrypt(sealed.clone()).unwrap().value(), "value");
+
+        // Only the first key is used for encryption.
+        jar.private_rotatable_mut(&[&new_key, &old_key]).add(("new", "value"));
+        assert_eq!(jar.private(&new_key).get("new").unwrap().value(), "value");
+        assert!(jar.private(&old_key).get("new").is_none());
+
+        // Re-adding a decrypted cookie seals it with the new key.
+        let cookie = jar.private_rotatable(&[&new_key, &old_key]).get("name").unwrap();
+        jar.private_rotatable_mut(&[&new_key, &old_key]).add(cookie);
+        assert_eq!(jar.private(&new_key).get("name").unwrap().value(), "value");
+
+        // A value sealed with a fallback key is sealed again with the new one.
+        jar.private_rotatable_mut(&[&new_key, &old_key]).add(sealed.clone());
+        assert_eq!(jar.private(&new_key).get("name").unwrap().value(), sealed.value());
+
+        // Keys that aren't listed don't decrypt.
+        jar.private_mut(&Key::generate()).add(("other", "value"));
+        assert!(jar.private_rotatable(&[&new_key, &old_key]).get("other").is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotation_requires_key() {
+        CookieJar::new().private_rotatable(&[]);
+    }
+
+    #[test]
+    fn seal_jar_roundtrip() {
+        use time::{macros::datetime, Duration};
+
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.add_original(("original", "value"));
+        jar.add(Cookie::build(("full", "a value; with=stuff"))
+            .domain(".rust-lang.org")
+            .path("/foo")
+            .secure(false)
+            .http_only(true)
+            .same_site(crate::SameSite::Lax)
+            .partitioned(true)
+            .max_age(Duration::hours(1) + Duration::milliseconds(500))
+            .expires(datetime!(2030-01-02 03:04:05.5 +01:00))
+            .extension("Priority", Some("High")).unwrap()
+            .extension("SameParty", None::<&str>).unwrap());
+        jar.add(Cookie::build(("session", "1")).expires(crate::Expiration::Session));
+        jar.add(Cookie::parse("parsed=1; Domain=.example.com; SameSite=None; X=").unwrap());
+        jar.add(("empty", ""));
+        jar.private_mut(&key).add(("private", "secret"));
+
+        let blob = jar.seal_jar(&key).unwrap();
+        let opened = CookieJar::<()>::open_jar(&blob, &key).unwrap();
+        assert_eq!(opened.iter().count(), 6);
+        assert_eq!(opened.delta().count(), 0);
+        for cookie in jar.iter() {
+            let opened = opened.get(cookie.name()).unwrap();
+            assert!(opened.eq_exact(cookie), "{:?} != {:?}", opened, cookie);
+            assert_eq!(opened.to_string(), cookie.to_string());
+        }
+
+        // `Secure` is implied, not set, and the leading `.` is preserved.
+        let parsed = opened.get("parsed").unwrap();
+        assert_eq!(parsed.secure(), None);
+        let expected = Cookie::parse("parsed=1; Domain=.example.com; SameSite=None; X=").unwrap();
+        assert!(parsed.eq_exact(&expected));
+        assert_eq!(parsed.to_string(), "parsed=1; SameSite=None; Secure; Domain=example.com; X=");
+
+        let full = opened.get("full").unwrap();
+        assert_eq!(full.expires_datetime().unwrap().offset().whole_hours(), 1);
+        assert_eq!(opened.private(&key).get("private").unwrap().value(), "secret");
+
+        // Removed cookies are not sealed.
+        jar.remove("original");
+        let opened = CookieJar::<()>::open_jar(&jar.seal_jar(&key).unwrap(), &key).unwrap();
+        assert!(opened.get("original").is_none());
+        assert_eq!(opened.iter().count(), 5);
+
+        // Empty jars are fine too.
+        let blob = CookieJar::new().seal_jar(&key).unwrap();
+        assert_eq!(CookieJar::<()>::open_jar(&blob, &key).unwrap().iter().count(), 0);
+    }
+
+    #[test]
+    fn seal_jar_detects_tampering() {
+        use crate::OpenError;
+
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.add(("name", "value"));
+        let blob = jar.seal_jar(&key).unwrap();
+
+        let mut data = crate::secure::base64::decode(&blob).unwrap();
+        for i in 0..data.len() {
+            data[i] ^= 1;
+            let tampered = crate::secure::base64::encode(&data);
+            let result = CookieJar::<()>::open_jar(&tampered, &key);
+            assert_eq!(result.unwrap_err(), OpenError::Unauthentic);
+            data[i] ^= 1;
+        }
+
+        let truncated = &blob[..(blob.len() - 4)];
+        assert_eq!(CookieJar::<()>::open_jar(truncated, &key).unwrap_err(), OpenError::Unauthentic);
+        assert_eq!(CookieJar::<()>::open_jar("", &key).unwrap_err(), OpenError::Unauthentic);
+        assert_eq!(CookieJar::<()>::open_jar("!!", &key).unwrap_err(), OpenError::Unauthentic);
+
+        // Sealed cookie values are not jars.
+        jar.private_mut(&key).add(("cookie-jar", "value"));
+        let value = jar.get("cookie-jar").unwrap().value();
+        assert_eq!(CookieJar::<()>::open_jar(value, &key).unwrap_err(), OpenError::Unauthentic);
+
+        // Authentic, but not a jar.
+        let not_a_jar = super::seal(key.encryption(), b"\xffcookie-jar", b"\0\0");
+        assert_eq!(CookieJar::<()>::open_jar(&not_a_jar, &key).unwrap_err(), OpenError::Malformed);
+    }
+
+    #[test]
+    fn decrypt_malformed() {
+        let key = Key::from(&[0u8; 64]);
+        let jar = CookieJar::new();
+        let sealed = "lObeZJorGVyeSWUA8khTO/8UCzFVBY9g0MGU6/J3NN1R5x11dn2JIA==";
+        let malformed = [
+            "", "a", "£", "!!!!", "AAAA", &"A".repeat(16), &sealed[..16], &sealed[..40],
+            &sealed[..(sealed.len() - 4)], &"é".repeat(30), &format!("{}é", &sealed[..15]),
+        ];
+
+        for value in &malformed {
+            let c = Cookie::new("name", value.to_string());
+            assert!(jar.private(&key).decrypt(c).is_none(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn inspect() {
+        let sealed = "lObeZJorGVyeSWUA8khTO/8UCzFVBY9g0MGU6/J3NN1R5x11dn2JIA==";
+        let (nonce, payload) = inspect_private(sealed).unwrap();
+        assert_eq!(nonce, "lObeZJorGVyeSWUA");
+        assert_eq!(payload, "8khTO/8UCzFVBY9g0MGU6/J3NN1R5x11dn2JIA==");
+
+        let nonce = crate::secure::base64::decode(nonce).unwrap();
+        let payload = crate::secure::base64::decode(payload).unwrap();
+        assert_eq!(nonce.len(), super::NONCE_LEN);
+        assert_eq!(payload.len(), "Tamper-proof".len() + super::TAG_LEN);
+
+        // An empty plaintext seals to exactly the minimum length.
+        let minimum = "A".repeat(38) + "==";
+        assert!(inspect_private(&minimum).is_some());
+        assert!(inspect_private(&minimum[..39]).is_none());
+        assert!(inspect_private("").is_none());
+    }
+
+    #[test]
+    fn try_add() {
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.set_max_cookie_size(150);
+
+        // The plaintext cookie is within the limit, but the result isn't.
+        let value = "x".repeat(100);
+        assert!(jar.try_add(("plain", value.clone())).is_ok());
+        let err = jar.private_mut(&key).try_add(("big", value)).unwrap_err();
+        assert!(matches!(err, AddError::TooLarge { size, limit: 150 } if size > 150));
+        assert!(jar.get("big").is_none());
+
+        assert!(jar.private_mut(&key).try_add(("name", "value")).is_ok());
+        assert_eq!(jar.private(&key).get("name").unwrap().value(), "value");
+    }
+
     #[test]
     fn roundtrip() {
         // Secret is SHA-256 hash of 'Super secret!' passed through HKDF-SHA256.
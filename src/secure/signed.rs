@@ -1,17 +1,134 @@
 use std::convert::TryInto;
 use std::borrow::{Borrow, BorrowMut};
+use std::marker::PhantomData;
 
-use sha2::Sha256;
-use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha384, Sha512};
+use hmac::{Hmac, Mac, digest::KeyInit};
 
 use crate::secure::{base64, Key};
-use crate::{Cookie, CookieJar};
+use crate::{AddError, Cookie, CookieJar};
 
 // Keep these in sync, and keep the key len synced with the `signed` docs as
-// well as the `KEYS_INFO` const in secure::Key.
+// well as the `KEYS_INFO` const in secure::Key. The digest len is that of the
+// default `SignatureAlgorithm::Sha256`.
 pub(crate) const BASE64_DIGEST_LEN: usize = 44;
 pub(crate) const KEY_LEN: usize = 32;
 
+/// The HMAC algorithm used by a [`SignedJar`] to sign cookies.
+///
+/// The default, and the algorithm used by [`CookieJar::signed()`] and
+/// [`CookieJar::signed_mut()`], is [`SignatureAlgorithm::Sha256`]. Use
+/// [`CookieJar::signed_with()`] and [`CookieJar::signed_mut_with()`] to sign
+/// with a different algorithm. A cookie verifies only under the algorithm
+/// and key it was signed with.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum SignatureAlgorithm {
+    /// HMAC-SHA256 with a 44 byte base64 digest. The default.
+    Sha256,
+    /// HMAC-SHA384 with a 64 byte base64 digest.
+    Sha384,
+    /// HMAC-SHA512 with an 88 byte base64 digest.
+    Sha512,
+}
+
+// Deriving requires `#[default]`, which is newer than our MSRV.
+#[allow(clippy::derivable_impls)]
+impl Default for SignatureAlgorithm {
+    fn default() -> Self {
+        SignatureAlgorithm::Sha256
+    }
+}
+
+impl SignatureAlgorithm {
+    /// Returns the length of the base64 encoded digest that is prepended to
+    /// the value of a cookie signed with `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::SignatureAlgorithm;
+    ///
+    /// assert_eq!(SignatureAlgorithm::Sha256.base64_digest_len(), 44);
+    /// assert_eq!(SignatureAlgorithm::Sha384.base64_digest_len(), 64);
+    /// assert_eq!(SignatureAlgorithm::Sha512.base64_digest_len(), 88);
+    /// ```
+    pub fn base64_digest_len(self) -> usize {
+        match self {
+            SignatureAlgorithm::Sha256 => BASE64_DIGEST_LEN,
+            SignatureAlgorithm::Sha384 => 64,
+            SignatureAlgorithm::Sha512 => 88,
+        }
+    }
+
+    /// Returns the HMAC of `value` under `key`.
+    fn sign(self, key: &[u8], value: &[u8]) -> Vec<u8> {
+        fn sign<M: Mac + KeyInit>(key: &[u8], value: &[u8]) -> Vec<u8> {
+            let mut mac = <M as Mac>::new_from_slice(key).expect("good key");
+            mac.update(value);
+            mac.finalize().into_bytes().to_vec()
+        }
+
+        match self {
+            SignatureAlgorithm::Sha256 => sign::<Hmac<Sha256>>(key, value),
+            SignatureAlgorithm::Sha384 => sign::<Hmac<Sha384>>(key, value),
+            SignatureAlgorithm::Sha512 => sign::<Hmac<Sha512>>(key, value),
+        }
+    }
+
+    /// Returns `true` if `digest` is the HMAC of `value` under `key`. The
+    /// comparison is constant-time.
+    fn verify(self, key: &[u8], value: &[u8], digest: &[u8]) -> bool {
+        fn verify<M: Mac + KeyInit>(key: &[u8], value: &[u8], digest: &[u8]) -> bool {
+            let mut mac = <M as Mac>::new_from_slice(key).expect("good key");
+            mac.update(value);
+            mac.verify_slice(digest).is_ok()
+        }
+
+        match self {
+            SignatureAlgorithm::Sha256 => verify::<Hmac<Sha256>>(key, value, digest),
+            SignatureAlgorithm::Sha384 => verify::<Hmac<Sha384>>(key, value, digest),
+            SignatureAlgorithm::Sha512 => verify::<Hmac<Sha512>>(key, value, digest),
+        }
+    }
+}
+
+/// An error indicating that a cookie's value failed to verify.
+///
+/// Returned by [`SignedJar::get_verified()`] when a cookie is present but its
+/// value isn't authentic, which may indicate tampering.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The value is too short to contain a digest.
+    MissingDigest,
+    /// The digest is not valid base64.
+    InvalidDigest,
+    /// The digest does not authenticate the value.
+    Mismatch,
+}
+
+impl VerifyError {
+    /// Returns a description of this error as a string.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            VerifyError::MissingDigest => "missing or invalid digest",
+            VerifyError::InvalidDigest => "bad base64 digest",
+            VerifyError::Mismatch => "value did not verify",
+        }
+    }
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::error::Error for VerifyError { }
+
 /// A child cookie jar that authenticates its cookies.
 ///
 /// A _signed_ child jar signs all the cookies added to it and verifies cookies
@@ -19,50 +136,65 @@ pub(crate) const KEY_LEN: usize = 32;
 /// integrity and authenticity. In other words, clients cannot tamper with the
 /// contents of a cookie nor can they fabricate cookie values, but the data is
 /// visible in plaintext.
+///
+/// `M` is the type of the parent jar's [metadata](CookieJar::set_meta()).
 #[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
-pub struct SignedJar<J> {
+pub struct SignedJar<J, M = ()> {
     parent: J,
     key: [u8; KEY_LEN],
+    algorithm: SignatureAlgorithm,
+    _meta: PhantomData<fn() -> M>,
 }
 
-impl<J> SignedJar<J> {
+impl<J, M> SignedJar<J, M> {
     /// Creates a new child `SignedJar` with parent `parent` and key `key`. This
     /// method is typically called indirectly via the `signed{_mut}` methods of
     /// `CookieJar`.
-    pub(crate) fn new(parent: J, key: &Key) -> SignedJar<J> {
-        SignedJar { parent, key: key.signing().try_into().expect("sign key len") }
+    pub(crate) fn new(parent: J, key: &Key) -> SignedJar<J, M> {
+        SignedJar::with_algorithm(parent, key, SignatureAlgorithm::default())
+    }
+
+    /// Creates a new child `SignedJar` with parent `parent` and key `key` that
+    /// signs with `algorithm`. This method is typically called indirectly via
+    /// the `signed{_mut}_with` methods of `CookieJar`.
+    pub(crate) fn with_algorithm(
+        parent: J,
+        key: &Key,
+        algorithm: SignatureAlgorithm
+    ) -> SignedJar<J, M> {
+        let key = key.signing().try_into().expect("sign key len");
+        SignedJar { parent, key, algorithm, _meta: PhantomData }
     }
 
     /// Signs the cookie's value providing integrity and authenticity.
     fn sign_cookie(&self, cookie: &mut Cookie) {
-        // Compute HMAC-SHA256 of the cookie's value.
-        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("good key");
-        mac.update(cookie.value().as_bytes());
+        // Compute the HMAC of the cookie's value.
+        let digest = self.algorithm.sign(&self.key, cookie.value().as_bytes());
 
         // Cookie's new value is [MAC | original-value].
-        let mut new_value = base64::encode(&mac.finalize().into_bytes());
+        let mut new_value = base64::encode(digest);
         new_value.push_str(cookie.value());
         cookie.set_value(new_value);
     }
 
     /// Given a signed value `str` where the signature is prepended to `value`,
     /// verifies the signed value and returns it. If there's a problem, returns
-    /// an `Err` with a string describing the issue.
-    fn _verify(&self, cookie_value: &str) -> Result<String, &'static str> {
-        if !cookie_value.is_char_boundary(BASE64_DIGEST_LEN) {
-            return Err("missing or invalid digest");
+    /// an `Err` describing the issue.
+    fn _verify(&self, cookie_value: &str) -> Result<String, VerifyError> {
+        let digest_len = self.algorithm.base64_digest_len();
+        if !cookie_value.is_char_boundary(digest_len) {
+            return Err(VerifyError::MissingDigest);
         }
 
         // Split [MAC | original-value] into its two parts.
-        let (digest_str, value) = cookie_value.split_at(BASE64_DIGEST_LEN);
-        let digest = base64::decode(digest_str).map_err(|_| "bad base64 digest")?;
+        let (digest_str, value) = cookie_value.split_at(digest_len);
+        let digest = base64::decode(digest_str).map_err(|_| VerifyError::InvalidDigest)?;
 
         // Perform the verification.
-        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("good key");
-        mac.update(value.as_bytes());
-        mac.verify_slice(&digest)
-            .map(|_| value.to_string())
-            .map_err(|_| "value did not verify")
+        match self.algorithm.verify(&self.key, value.as_bytes(), &digest) {
+            true => Ok(value.to_string()),
+            false => Err(VerifyError::Mismatch),
+        }
     }
 
     /// Verifies the authenticity and integrity of `cookie`, returning the
@@ -100,7 +232,7 @@ impl<J> SignedJar<J> {
     }
 }
 
-impl<J: Borrow<CookieJar>> SignedJar<J> {
+impl<J: Borrow<CookieJar<M>>, M> SignedJar<J, M> {
     /// Returns a reference to the `Cookie` inside this jar with the name `name`
     /// and verifies the authenticity and integrity of the cookie's value,
     /// returning a `Cookie` with the authenticated value. If the cookie cannot
@@ -123,12 +255,51 @@ impl<J: Borrow<CookieJar>> SignedJar<J> {
     pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
         self.parent.borrow().get(name).and_then(|c| self.verify(c.clone()))
     }
+
+    /// Like [`SignedJar::get()`], but distinguishes between a cookie that is
+    /// absent and one that is present but fails to verify. Returns `Ok(None)`
+    /// if there is no cookie with the name `name`, `Ok(Some(cookie))` with the
+    /// authenticated value if the cookie verifies, and `Err` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// assert!(jar.signed(&key).get_verified("name").unwrap().is_none());
+    ///
+    /// jar.signed_mut(&key).add(("name", "value"));
+    /// let cookie = jar.signed(&key).get_verified("name").unwrap().unwrap();
+    /// assert_eq!(cookie.value(), "value");
+    ///
+    /// jar.add(("name", "tampered"));
+    /// assert!(jar.signed(&key).get_verified("name").is_err());
+    /// ```
+    pub fn get_verified(&self, name: &str) -> Result<Option<Cookie<'static>>, VerifyError> {
+        let mut cookie = match self.parent.borrow().get(name) {
+            Some(cookie) => cookie.clone(),
+            None => return Ok(None),
+        };
+
+        let value = self._verify(cookie.value())?;
+        cookie.set_value(value);
+        Ok(Some(cookie))
+    }
 }
 
-impl<J: BorrowMut<CookieJar>> SignedJar<J> {
+impl<J: BorrowMut<CookieJar<M>>, M> SignedJar<J, M> {
     /// Adds `cookie` to the parent jar. The cookie's value is signed assuring
     /// integrity and authenticity.
     ///
+    /// The value is always signed, even if it is _already_ signed. Adding a
+    /// cookie retrieved from the parent jar, rather than from the signed jar,
+    /// thus signs its value twice: retrieving it from the signed jar then
+    /// yields the signed value, not the original plaintext. Retrieve cookies
+    /// via [`SignedJar::get()`] before adding them back, or use
+    /// [`SignedJar::resign()`] to add a cookie that may already be signed.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -148,6 +319,71 @@ impl<J: BorrowMut<CookieJar>> SignedJar<J> {
         self.parent.borrow_mut().add(cookie);
     }
 
+    /// Adds `cookie`, which may already be signed, to the parent jar, signing
+    /// its value exactly once.
+    ///
+    /// If the value of `cookie` verifies under the key and algorithm of
+    /// `self`, as it does for a cookie retrieved from the parent jar, it is
+    /// added unchanged. Otherwise, the value is signed as by
+    /// [`SignedJar::add()`]. Either way, retrieving the cookie from the signed
+    /// jar yields the plaintext.
+    ///
+    /// Signatures don't bind a cookie's name, so a value signed for one
+    /// cookie verifies for all of them. As such, only pass values that
+    /// originate from this jar, never plaintext that a client controls: a
+    /// client could pass off a signed value it obtained as plaintext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.signed_mut(&key).add(("name", "value"));
+    ///
+    /// // `add()` would sign the already signed value a second time.
+    /// let signed = jar.get("name").cloned().unwrap();
+    /// jar.signed_mut(&key).resign(signed);
+    /// assert_eq!(jar.signed(&key).get("name").unwrap().value(), "value");
+    ///
+    /// jar.signed_mut(&key).resign(("name", "plain"));
+    /// assert_eq!(jar.signed(&key).get("name").unwrap().value(), "plain");
+    /// ```
+    pub fn resign<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
+        let cookie = cookie.into();
+        match self._verify(cookie.value()) {
+            Ok(_) => self.parent.borrow_mut().add(cookie),
+            Err(_) => self.add(cookie),
+        }
+    }
+
+    /// Adds `cookie` to the parent jar exactly as [`SignedJar::add()`] does if
+    /// the resulting signed cookie satisfies the parent jar's policies,
+    /// returning an error and leaving the jar unchanged otherwise.
+    ///
+    /// The policies are checked against the signed cookie, which is
+    /// larger than `cookie`. See [`CookieJar::try_add()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cookie::{CookieJar, Cookie, Key};
+    ///
+    /// let key = Key::generate();
+    /// let mut jar = CookieJar::new();
+    /// jar.set_max_cookie_size(128);
+    ///
+    /// assert!(jar.signed_mut(&key).try_add(("name", "value")).is_ok());
+    /// assert!(jar.signed_mut(&key).try_add(("big", "x".repeat(128))).is_err());
+    /// assert!(jar.get("big").is_none());
+    /// ```
+    pub fn try_add<C: Into<Cookie<'static>>>(&mut self, cookie: C) -> Result<(), AddError> {
+        let mut cookie = cookie.into();
+        self.sign_cookie(&mut cookie);
+        self.parent.borrow_mut().try_add(cookie)
+    }
+
     /// Adds an "original" `cookie` to this jar. The cookie's value is signed
     /// assuring integrity and authenticity. Adding an original cookie does not
     /// affect the [`CookieJar::delta()`] computation. This method is intended
@@ -203,9 +439,41 @@ impl<J: BorrowMut<CookieJar>> SignedJar<J> {
     }
 }
 
+/// Splits the value of a cookie signed by a [`SignedJar`] into its base64
+/// encoded digest and its plaintext payload without verifying it.
+///
+/// Returns `None` if `value` is too short to contain a digest. Assumes the
+/// default [`SignatureAlgorithm::Sha256`] was used to sign. This is intended
+/// for debugging; use [`SignedJar::get()`] or [`SignedJar::verify()`]
+/// to obtain authentic values.
+///
+/// # Example
+///
+/// ```rust
+/// use cookie::{CookieJar, Key, inspect_signed};
+///
+/// let mut jar = CookieJar::new();
+/// jar.signed_mut(&Key::generate()).add(("name", "value"));
+///
+/// let signed = jar.get("name").unwrap().value();
+/// let (digest, payload) = inspect_signed(signed).unwrap();
+/// assert_eq!(digest.len(), 44);
+/// assert_eq!(payload, "value");
+///
+/// assert!(inspect_signed("too short").is_none());
+/// ```
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "signed")))]
+pub fn inspect_signed(value: &str) -> Option<(&str, &str)> {
+    match value.is_char_boundary(BASE64_DIGEST_LEN) {
+        true => Some(value.split_at(BASE64_DIGEST_LEN)),
+        false => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{CookieJar, Cookie, Key};
+    use crate::{AddError, CookieJar, Cookie, Key};
+    use super::{inspect_signed, SignatureAlgorithm};
 
     #[test]
     fn simple() {
@@ -241,6 +509,159 @@ mod test {
         assert_eq!(signed.get("signed_with_ring016").unwrap().value(), "Tamper-proof");
     }
 
+    #[test]
+    fn get_verified() {
+        use super::VerifyError;
+
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        assert_eq!(jar.signed(&key).get_verified("name"), Ok(None));
+
+        jar.signed_mut(&key).add(("name", "value"));
+        let cookie = jar.signed(&key).get_verified("name").unwrap().unwrap();
+        assert_eq!(cookie.value(), "value");
+
+        let signed = jar.get("name").unwrap().value().to_string();
+        let tampered = format!("{}tampered", &signed[..44]);
+        jar.add(("name", tampered));
+        assert!(jar.signed(&key).get("name").is_none());
+        assert_eq!(jar.signed(&key).get_verified("name"), Err(VerifyError::Mismatch));
+
+        jar.add(("name", "short"));
+        assert_eq!(jar.signed(&key).get_verified("name"), Err(VerifyError::MissingDigest));
+
+        let not_base64 = format!("{}value", "!".repeat(44));
+        jar.add(("name", not_base64));
+        assert_eq!(jar.signed(&key).get_verified("name"), Err(VerifyError::InvalidDigest));
+    }
+
+    #[test]
+    fn inspect() {
+        let digest = "8SgnqI5E2+8rnzfmBbAtCQXUl1rULkEG24FLSNDGG6Q=";
+        let value = format!("{}Tamper-proof", digest);
+        assert_eq!(inspect_signed(&value), Some((digest, "Tamper-proof")));
+        assert_eq!(inspect_signed(digest), Some((digest, "")));
+        assert_eq!(inspect_signed(&digest[1..]), None);
+        assert_eq!(inspect_signed(""), None);
+
+        // The 44th byte is in the middle of a `char`.
+        let value = format!("{}é", &digest[..43]);
+        assert_eq!(inspect_signed(&value), None);
+    }
+
+    #[test]
+    fn try_add() {
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.set_max_cookie_size(120);
+
+        // The plaintext cookie is within the limit, but the result isn't.
+        let value = "x".repeat(100);
+        assert!(jar.try_add(("plain", value.clone())).is_ok());
+        let err = jar.signed_mut(&key).try_add(("big", value)).unwrap_err();
+        assert!(matches!(err, AddError::TooLarge { size, limit: 120 } if size > 120));
+        assert!(jar.get("big").is_none());
+
+        assert!(jar.signed_mut(&key).try_add(("name", "value")).is_ok());
+        assert_eq!(jar.signed(&key).get("name").unwrap().value(), "value");
+    }
+
+    #[test]
+    fn double_sign() {
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.signed_mut(&key).add(("name", "value"));
+        let signed = jar.get("name").cloned().unwrap();
+
+        // Adding a cookie from the parent jar signs its value a second time.
+        jar.signed_mut(&key).add(signed.clone());
+        assert_ne!(jar.get("name").unwrap().value(), signed.value());
+        assert_eq!(jar.signed(&key).get("name").unwrap().value(), signed.value());
+
+        jar.signed_mut(&key).add_original(signed.clone());
+        assert_eq!(jar.signed(&key).get("name").unwrap().value(), signed.value());
+
+        // Signatures don't bind the name, but values are always signed anew.
+        jar.signed_mut(&key).add(("other", signed.value().to_string()));
+        assert_eq!(jar.signed(&key).get("other").unwrap().value(), signed.value());
+
+        // `resign` guards against double signing.
+        jar.signed_mut(&key).resign(signed.clone());
+        assert_eq!(jar.get("name").unwrap().value(), signed.value());
+        assert_eq!(jar.signed(&key).get("name").unwrap().value(), "value");
+
+        jar.signed_mut(&key).resign(("name", "plain"));
+        assert_eq!(jar.signed(&key).get("name").unwrap().value(), "plain");
+
+        // Values signed with another key are signed anew.
+        let other = Key::generate();
+        jar.signed_mut(&other).resign(signed.clone());
+        assert_eq!(jar.signed(&other).get("name").unwrap().value(), signed.value());
+    }
+
+    #[test]
+    fn verify_malformed() {
+        let key = Key::from(&[0u8; 64]);
+        let jar = CookieJar::new();
+        let digest = "8SgnqI5E2+8rnzfmBbAtCQXUl1rULkEG24FLSNDGG6Q=";
+        let malformed = [
+            "", "a", "£", &digest[..43], &digest[..40], "!!!!", &"é".repeat(30),
+            &format!("{}é", &digest[..43]), &format!("{}value", "!".repeat(44)),
+        ];
+
+        for value in &malformed {
+            let c = Cookie::new("name", value.to_string());
+            assert!(jar.signed(&key).verify(c).is_none(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn algorithms() {
+        let key = Key::from(&[0u8; 64]);
+        let algorithms = [
+            (SignatureAlgorithm::Sha256, "YBcbxRIGgiQull2WMFn6QPQ66QrQVmjfxobgiflbiao="),
+            (SignatureAlgorithm::Sha384, "sFuE/XUXamMFYW92Hos2fPJEbh/sgnSCvm3+64pTJUICiowC7xX7mhsk6AJzm74C"),
+            (SignatureAlgorithm::Sha512, "cnPZh9x6TLrNhOOIt/AZbpEK/gK7OCU73AiV2cZ9/JYNSvJ1HLt4YitPKCm2AP76WBrg3GsjPJVpy6MQ6tMvPg=="),
+        ];
+
+        for &(algorithm, digest) in &algorithms {
+            assert_eq!(digest.len(), algorithm.base64_digest_len());
+
+            let mut jar = CookieJar::new();
+            assert_simple_behaviour!(jar, jar.signed_mut_with(&key, algorithm));
+            assert_secure_behaviour!(jar, jar.signed_mut_with(&key, algorithm));
+
+            jar.signed_mut_with(&key, algorithm).add(("name", "value"));
+            let signed = jar.get("name").unwrap().value().to_string();
+            assert_eq!(signed, format!("{}value", digest));
+            assert_eq!(jar.signed_with(&key, algorithm).get("name").unwrap().value(), "value");
+
+            // A jar with the same key and algorithm verifies the cookie.
+            let mut other = CookieJar::new();
+            other.add_original(("name", signed.clone()));
+            assert_eq!(other.signed_with(&key, algorithm).get("name").unwrap().value(), "value");
+
+            // But not one with a different key or algorithm.
+            assert!(other.signed_with(&Key::generate(), algorithm).get("name").is_none());
+            for &(other_algorithm, _) in &algorithms {
+                if other_algorithm != algorithm {
+                    assert!(other.signed_with(&key, other_algorithm).get("name").is_none());
+                }
+            }
+
+            // Re-adding the verified cookie signs it the same way again.
+            let cookie = jar.signed_with(&key, algorithm).get("name").unwrap();
+            jar.signed_mut_with(&key, algorithm).add(cookie);
+            assert_eq!(jar.get("name").unwrap().value(), signed);
+        }
+
+        assert_eq!(SignatureAlgorithm::default(), SignatureAlgorithm::Sha256);
+        let mut jar = CookieJar::new();
+        jar.signed_mut(&key).add(("name", "value"));
+        let default = jar.signed_with(&key, SignatureAlgorithm::Sha256);
+        assert_eq!(default.get("name").unwrap().value(), "value");
+    }
+
     #[test]
     fn issue_178() {
         let data = "x=yyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyyy£";
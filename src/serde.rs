@@ -0,0 +1,242 @@
+use std::fmt;
+
+use ::serde::de::{self, Deserialize, Deserializer};
+use ::serde::ser::{self, Serialize, Serializer};
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+use crate::{Cookie, Expiration, SameSite};
+
+/// The serialized form of a `Cookie`. `S` is `&str` when serializing and
+/// `String` when deserializing. `max_age` is in whole seconds.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[serde(rename = "Cookie")]
+struct CookieRepr<S> {
+    name: S,
+    value: S,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires: Option<Expiration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_age: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    domain: Option<S>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<S>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secure: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    same_site: Option<SameSite>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    partitioned: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extensions: Vec<(S, Option<S>)>,
+}
+
+/// Serializes the resolved name, value, and attributes of a cookie as a
+/// struct named `Cookie`. Unset attributes are omitted. The `Domain` is
+/// serialized as stored, including any leading `.`, and `Max-Age` is
+/// serialized in whole seconds.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl Serialize for Cookie<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let string = self.cookie_string.as_ref();
+        let repr = CookieRepr {
+            name: self.name(),
+            value: self.value(),
+            expires: self.expires(),
+            max_age: self.max_age().map(|d| d.whole_seconds()),
+            domain: self.domain.as_ref().map(|d| d.to_str(string)),
+            path: self.path(),
+            secure: self.secure(),
+            http_only: self.http_only(),
+            same_site: self.same_site(),
+            partitioned: self.partitioned(),
+            extensions: self.extensions().collect(),
+        };
+
+        repr.serialize(serializer)
+    }
+}
+
+/// Deserializes a cookie serialized by the [`Serialize`] implementation.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Cookie<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CookieRepr::<String>::deserialize(deserializer)?;
+
+        let mut cookie = Cookie::new(repr.name, repr.value);
+        if let Some(expires) = repr.expires {
+            cookie.set_expires(expires);
+        }
+
+        if let Some(domain) = repr.domain {
+            cookie.set_domain(domain);
+        }
+
+        if let Some(path) = repr.path {
+            cookie.set_path(path);
+        }
+
+        cookie.set_max_age(repr.max_age.map(Duration::seconds));
+        cookie.set_secure(repr.secure);
+        cookie.set_http_only(repr.http_only);
+        cookie.set_same_site(repr.same_site);
+        cookie.set_partitioned(repr.partitioned);
+        for (name, value) in repr.extensions {
+            cookie.add_extension(name, value).map_err(de::Error::custom)?;
+        }
+
+        Ok(cookie)
+    }
+}
+
+/// Serializes as the unit variant `Strict`, `Lax`, or `None` of an enum
+/// named `SameSite`.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl Serialize for SameSite {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, variant) = match self {
+            SameSite::Strict => (0, "Strict"),
+            SameSite::Lax => (1, "Lax"),
+            SameSite::None => (2, "None"),
+        };
+
+        serializer.serialize_unit_variant("SameSite", index, variant)
+    }
+}
+
+#[derive(::serde::Deserialize)]
+#[serde(rename = "SameSite")]
+enum SameSiteRepr {
+    Strict,
+    Lax,
+    None,
+}
+
+/// Deserializes a `SameSite` serialized by the [`Serialize`] implementation.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for SameSite {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SameSiteRepr::deserialize(deserializer)? {
+            SameSiteRepr::Strict => SameSite::Strict,
+            SameSiteRepr::Lax => SameSite::Lax,
+            SameSiteRepr::None => SameSite::None,
+        })
+    }
+}
+
+/// Serializes as a variant of an enum named `Expiration`: either the unit
+/// variant `Session` or the newtype variant `DateTime` containing an RFC 3339
+/// formatted date-time.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl Serialize for Expiration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Expiration::DateTime(time) => {
+                let time = time.format(&Rfc3339).map_err(ser::Error::custom)?;
+                serializer.serialize_newtype_variant("Expiration", 0, "DateTime", &time)
+            }
+            Expiration::Session => serializer.serialize_unit_variant("Expiration", 1, "Session"),
+        }
+    }
+}
+
+#[derive(::serde::Deserialize)]
+#[serde(rename = "Expiration")]
+enum ExpirationRepr {
+    DateTime(String),
+    Session,
+}
+
+/// Deserializes an `Expiration` serialized by the [`Serialize`]
+/// implementation.
+#[cfg_attr(all(nightly, doc), doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Expiration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ExpirationRepr::deserialize(deserializer)? {
+            ExpirationRepr::DateTime(time) => OffsetDateTime::parse(&time, &Rfc3339)
+                .map(Expiration::DateTime)
+                .map_err(|e| de::Error::custom(InvalidDateTime(e))),
+            ExpirationRepr::Session => Ok(Expiration::Session),
+        }
+    }
+}
+
+struct InvalidDateTime(time::error::Parse);
+
+impl fmt::Display for InvalidDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid RFC 3339 date-time: {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cookie, Expiration, SameSite};
+    use time::{macros::datetime, Duration};
+
+    #[test]
+    fn roundtrip_full_cookie() {
+        let cookie = Cookie::build(("name", "value"))
+            .expires(datetime!(2030-01-02 03:04:05.5 +01:00))
+            .max_age(Duration::hours(1))
+            .domain(".rust-lang.org")
+            .path("/docs")
+            .secure(true)
+            .http_only(false)
+            .same_site(SameSite::Lax)
+            .partitioned(true)
+            .extension("Priority", Some("High")).unwrap()
+            .extension("SameParty", None::<&str>).unwrap()
+            .build();
+
+        let json = serde_json::to_string(&cookie).unwrap();
+        assert_eq!(json, concat!(
+            r#"{"name":"name","value":"value","#,
+            r#""expires":{"DateTime":"2030-01-02T03:04:05.5+01:00"},"max_age":3600,"#,
+            r#""domain":".rust-lang.org","path":"/docs","secure":true,"http_only":false,"#,
+            r#""same_site":"Lax","partitioned":true,"#,
+            r#""extensions":[["Priority","High"],["SameParty",null]]}"#,
+        ));
+
+        let deserialized: Cookie<'static> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.eq_exact(&cookie));
+        assert_eq!(deserialized.to_string(), cookie.to_string());
+    }
+
+    #[test]
+    fn roundtrip_parsed_cookie() {
+        let string = "a=b; SameSite=None; Path=/; Max-Age=60; Expires=Wed, 21 Oct 2015 07:28:00 GMT";
+        let cookie = Cookie::parse(string).unwrap();
+        let json = serde_json::to_string(&cookie).unwrap();
+        let deserialized: Cookie<'static> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.eq_exact(&cookie));
+        assert_eq!(deserialized.secure(), None);
+        assert_eq!(deserialized.same_site(), Some(SameSite::None));
+
+        let cookie = Cookie::build(("a", "b")).expires(Expiration::Session).build();
+        let json = serde_json::to_string(&cookie).unwrap();
+        assert_eq!(json, r#"{"name":"a","value":"b","expires":"Session"}"#);
+        let deserialized: Cookie<'static> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.eq_exact(&cookie));
+
+        let cookie = Cookie::build(("a", "b")).expires_in(Duration::MIN).build();
+        let json = serde_json::to_string(&cookie).unwrap();
+        assert_eq!(json, r#"{"name":"a","value":"b","expires":{"DateTime":"1970-01-01T00:00:00Z"}}"#);
+    }
+
+    #[test]
+    fn deserialize_minimal_and_invalid() {
+        let cookie: Cookie<'static> = serde_json::from_str(r#"{"name":"a","value":"b"}"#).unwrap();
+        assert!(cookie.eq_exact(&Cookie::new("a", "b")));
+
+        assert!(serde_json::from_str::<Cookie<'static>>(r#"{"name":"a"}"#).is_err());
+        assert!(serde_json::from_str::<SameSite>(r#""Sometimes""#).is_err());
+        assert!(serde_json::from_str::<Expiration>(r#"{"DateTime":"yesterday"}"#).is_err());
+
+        let json = r#"{"name":"a","value":"b","extensions":[["X","1; Domain=evil.com"]]}"#;
+        assert!(serde_json::from_str::<Cookie<'static>>(json).is_err());
+        assert_eq!(serde_json::from_str::<SameSite>(r#""Strict""#).unwrap(), SameSite::Strict);
+    }
+}
@@ -0,0 +1,292 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::marker::PhantomData;
+
+use crate::{Cookie, CookieJar};
+
+/// Persistent storage for cookies, keyed by cookie name.
+///
+/// A `CookieStore` backs a [`BackedJar`], obtained via [`CookieJar::backed()`]
+/// and [`CookieJar::backed_mut()`], which reads through to the store for
+/// cookies missing from the jar and writes additions and removals through to
+/// the store. Implementations are responsible for handling any storage errors
+/// themselves.
+///
+/// `CookieStore` is implemented for `&mut S` for any `S: CookieStore`, so a
+/// store can be lent to a `BackedJar` rather than moved into it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use cookie::{Cookie, CookieStore};
+///
+/// #[derive(Default)]
+/// struct MemoryStore(HashMap<String, String>);
+///
+/// impl CookieStore for MemoryStore {
+///     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+///         let string = self.0.get(name)?;
+///         Cookie::parse(string.clone()).ok()
+///     }
+///
+///     fn store(&mut self, cookie: &Cookie<'static>) {
+///         self.0.insert(cookie.name().to_string(), cookie.to_string());
+///     }
+///
+///     fn remove(&mut self, name: &str) {
+///         self.0.remove(name);
+///     }
+/// }
+/// ```
+pub trait CookieStore {
+    /// Returns the cookie named `name` from the store, if any.
+    fn load(&self, name: &str) -> Option<Cookie<'static>>;
+
+    /// Stores `cookie`, replacing any stored cookie with the same name.
+    fn store(&mut self, cookie: &Cookie<'static>);
+
+    /// Removes the cookie named `name` from the store, if any.
+    fn remove(&mut self, name: &str);
+}
+
+impl<S: CookieStore + ?Sized> CookieStore for &mut S {
+    fn load(&self, name: &str) -> Option<Cookie<'static>> {
+        (**self).load(name)
+    }
+
+    fn store(&mut self, cookie: &Cookie<'static>) {
+        (**self).store(cookie)
+    }
+
+    fn remove(&mut self, name: &str) {
+        (**self).remove(name)
+    }
+}
+
+/// A child jar that reads through and writes through to a [`CookieStore`].
+///
+/// Obtained via [`CookieJar::backed()`] and [`CookieJar::backed_mut()`].
+///
+/// Retrievals are made from the parent jar first and, if the cookie isn't
+/// present in the parent jar, from the store. Additions and removals are made
+/// to both the parent jar and the store, so the parent jar's
+/// [delta](CookieJar::delta()) continues to track changes as usual.
+///
+/// `M` is the type of the parent jar's [metadata](CookieJar::set_meta()).
+pub struct BackedJar<S, J, M = ()> {
+    parent: J,
+    store: S,
+    _meta: PhantomData<fn() -> M>,
+}
+
+impl<S, J, M> BackedJar<S, J, M> {
+    /// Creates a new child `BackedJar` with parent `parent` and store `store`.
+    /// This method is typically called indirectly via the `backed` method of
+    /// `CookieJar`.
+    pub(crate) fn new(parent: J, store: S) -> BackedJar<S, J, M> {
+        BackedJar { parent, store, _meta: PhantomData }
+    }
+
+    /// Returns a reference to the backing store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consumes `self`, returning the backing store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+impl<S: CookieStore, J: Borrow<CookieJar<M>>, M> BackedJar<S, J, M> {
+    /// Returns the cookie named `name` from the parent jar or, if the parent
+    /// jar has no such cookie, from the store. Cookies loaded from the store
+    /// are not added to the parent jar. If the cookie was removed from the
+    /// parent jar via [`CookieJar::remove()`], `None` is returned and the
+    /// store is not consulted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use cookie::{Cookie, CookieStore};
+    /// # #[derive(Default)]
+    /// # struct MemoryStore(HashMap<String, String>);
+    /// # impl CookieStore for MemoryStore {
+    /// #     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+    /// #         Cookie::parse(self.0.get(name)?.clone()).ok()
+    /// #     }
+    /// #     fn store(&mut self, cookie: &Cookie<'static>) {
+    /// #         self.0.insert(cookie.name().to_string(), cookie.to_string());
+    /// #     }
+    /// #     fn remove(&mut self, name: &str) { self.0.remove(name); }
+    /// # }
+    /// use cookie::CookieJar;
+    ///
+    /// let mut store = MemoryStore::default();
+    /// store.store(&Cookie::new("stored", "value"));
+    ///
+    /// let mut jar = CookieJar::new();
+    /// jar.add_original(("original", "value"));
+    ///
+    /// let backed = jar.backed(&mut store);
+    /// assert_eq!(backed.get("original").unwrap().value(), "value");
+    /// assert_eq!(backed.get("stored").unwrap().value(), "value");
+    /// assert!(backed.get("missing").is_none());
+    /// assert!(jar.get("stored").is_none());
+    ///
+    /// jar.add_original(("stored", "shadowed"));
+    /// jar.remove("stored");
+    /// assert!(jar.backed(&mut store).get("stored").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        let parent = self.parent.borrow();
+        match parent.get(name) {
+            Some(cookie) => Some(cookie.clone()),
+            None if parent.is_removed(name) => None,
+            None => self.store.load(name),
+        }
+    }
+}
+
+impl<S: CookieStore, J: BorrowMut<CookieJar<M>>, M> BackedJar<S, J, M> {
+    /// Adds `cookie` to the parent jar, as if by [`CookieJar::add()`], and
+    /// stores it in the store.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use cookie::{Cookie, CookieStore};
+    /// # #[derive(Default)]
+    /// # struct MemoryStore(HashMap<String, String>);
+    /// # impl CookieStore for MemoryStore {
+    /// #     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+    /// #         Cookie::parse(self.0.get(name)?.clone()).ok()
+    /// #     }
+    /// #     fn store(&mut self, cookie: &Cookie<'static>) {
+    /// #         self.0.insert(cookie.name().to_string(), cookie.to_string());
+    /// #     }
+    /// #     fn remove(&mut self, name: &str) { self.0.remove(name); }
+    /// # }
+    /// use cookie::CookieJar;
+    ///
+    /// let mut store = MemoryStore::default();
+    /// let mut jar = CookieJar::new();
+    /// jar.backed_mut(&mut store).add(("name", "value"));
+    ///
+    /// assert_eq!(jar.get("name").unwrap().value(), "value");
+    /// assert_eq!(jar.delta().count(), 1);
+    /// assert_eq!(store.load("name").unwrap().value(), "value");
+    /// ```
+    pub fn add<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
+        let cookie = cookie.into();
+        self.store.store(&cookie);
+        self.parent.borrow_mut().add(cookie);
+    }
+
+    /// Removes `cookie` from the parent jar, as if by [`CookieJar::remove()`],
+    /// and removes the cookie with the same name from the store.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use cookie::{Cookie, CookieStore};
+    /// # #[derive(Default)]
+    /// # struct MemoryStore(HashMap<String, String>);
+    /// # impl CookieStore for MemoryStore {
+    /// #     fn load(&self, name: &str) -> Option<Cookie<'static>> {
+    /// #         Cookie::parse(self.0.get(name)?.clone()).ok()
+    /// #     }
+    /// #     fn store(&mut self, cookie: &Cookie<'static>) {
+    /// #         self.0.insert(cookie.name().to_string(), cookie.to_string());
+    /// #     }
+    /// #     fn remove(&mut self, name: &str) { self.0.remove(name); }
+    /// # }
+    /// use cookie::CookieJar;
+    ///
+    /// let mut store = MemoryStore::default();
+    /// let mut jar = CookieJar::new();
+    /// jar.backed_mut(&mut store).add(("name", "value"));
+    /// jar.backed_mut(&mut store).remove("name");
+    ///
+    /// assert!(jar.get("name").is_none());
+    /// assert!(store.load("name").is_none());
+    /// ```
+    pub fn remove<C: Into<Cookie<'static>>>(&mut self, cookie: C) {
+        let cookie = cookie.into();
+        self.store.remove(cookie.name());
+        self.parent.borrow_mut().remove(cookie);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::CookieStore;
+    use crate::{Cookie, CookieJar};
+
+    /// A store that records every operation made on it.
+    #[derive(Default)]
+    struct MockStore {
+        cookies: HashMap<String, Cookie<'static>>,
+        log: Vec<String>,
+    }
+
+    impl CookieStore for MockStore {
+        fn load(&self, name: &str) -> Option<Cookie<'static>> {
+            self.cookies.get(name).cloned()
+        }
+
+        fn store(&mut self, cookie: &Cookie<'static>) {
+            self.log.push(format!("store {}", cookie.name()));
+            self.cookies.insert(cookie.name().to_string(), cookie.clone());
+        }
+
+        fn remove(&mut self, name: &str) {
+            self.log.push(format!("remove {}", name));
+            self.cookies.remove(name);
+        }
+    }
+
+    #[test]
+    fn delegates_to_store() {
+        let mut store = MockStore::default();
+        store.cookies.insert("stored".into(), Cookie::new("stored", "persisted"));
+
+        let mut jar = CookieJar::new();
+        jar.add_original(("original", "value"));
+        jar.add_original(("stored", "shadowed"));
+
+        // Reads prefer the jar, falling back to the store.
+        let backed = jar.backed(&mut store);
+        assert_eq!(backed.get("stored").unwrap().value(), "shadowed");
+        assert!(backed.get("missing").is_none());
+
+        // A pending removal in the jar hides the stored cookie.
+        jar.remove("stored");
+        let backed = jar.backed(&mut store);
+        assert!(backed.get("stored").is_none());
+
+        // Writes go to both the jar and the store.
+        let mut backed = jar.backed_mut(&mut store);
+        backed.add(Cookie::build(("new", "value")).path("/"));
+        backed.remove("original");
+        assert_eq!(backed.get("new").unwrap().path(), Some("/"));
+        assert!(backed.get("original").is_none());
+
+        assert_eq!(store.log, ["store new", "remove original"]);
+        assert_eq!(store.load("new").unwrap().value(), "value");
+        assert!(jar.get("original").is_none());
+        assert_eq!(jar.delta().count(), 3);
+
+        // A store can also be owned by the backed jar.
+        let mut backed = jar.backed_mut(MockStore::default());
+        backed.add(("owned", "value"));
+        let store = backed.into_store();
+        assert_eq!(store.log, ["store owned"]);
+        assert!(jar.get("owned").is_some());
+    }
+}